@@ -4,170 +4,43 @@ use rustle_lang::{
     syntax::parser::Parser,
     analysis::{self, symbols::SymbolKind},
     namespaces::NamespaceRegistry,
-    compile, Runtime, DrawCommand, Input, Origin, RenderMode, ShapeData, ShapeDesc,
+    render::{is_convex, tessellate_ndc, tessellate_screen_px, triangulate},
+    compile, Color, Runtime, DrawCommand, Input, RenderMode, ShapeDesc,
 };
 use rustle_lang::analysis::checker::type_name;
 
 
-/// Return screen pixel vertices (0,0 = top-left, y-down).
-fn tessellate_screen_px(data: &ShapeData) -> Vec<(f64, f64)> {
-    let m = &data.coord_meta;
-    let sx = |x: f64| m.x_to_screen_px(x);
-    let sy = |y: f64| m.y_to_screen_px(y);
-
-    let verts = match &data.desc {
-        ShapeDesc::Circle { center, radius } => {
-            (0..64usize).map(|i| {
-                let t = i as f64 / 64.0 * std::f64::consts::TAU;
-                (sx(center.0 + radius * t.cos()), sy(center.1 + radius * t.sin()))
-            }).collect()
-        }
-        ShapeDesc::Rect { center, size, origin } => {
-            let (w, h) = (size.0, size.1);
-            let (ax, ay) = (sx(center.0), sy(center.1));
-            let (min_x, max_x) = match origin {
-                Origin::TopLeft | Origin::BottomLeft | Origin::Left
-                    => (ax, ax + w),
-                Origin::TopRight | Origin::BottomRight | Origin::Right
-                    => (ax - w, ax),
-                Origin::Center | Origin::Top | Origin::Bottom
-                    => (ax - w / 2.0, ax + w / 2.0),
-            };
-            let (min_y, max_y) = match origin {
-                Origin::TopLeft | Origin::TopRight | Origin::Top
-                    => (ay, ay + h),
-                Origin::BottomLeft | Origin::BottomRight | Origin::Bottom
-                    => (ay - h, ay),
-                Origin::Center | Origin::Left | Origin::Right
-                    => (ay - h / 2.0, ay + h / 2.0),
-            };
-            vec![
-                (min_x, min_y), (max_x, min_y),
-                (max_x, max_y), (min_x, max_y),
-            ]
-        }
-        ShapeDesc::Line { from, to } => vec![
-            (sx(from.0), sy(from.1)),
-            (sx(to.0),   sy(to.1)),
-        ],
-        ShapeDesc::Polygon(pts) => pts.iter()
-            .map(|(x, y)| (sx(*x), sy(*y)))
-            .collect(),
-    };
-
-    // Apply transforms in screen pixel space.
-    // td.tx / td.ty are in user-space units — convert to screen px deltas:
-    //   x-right origins flip the x direction, y-up origins flip the y direction.
-    let x_sign: f64 = match m.origin {
-        Origin::TopRight | Origin::BottomRight | Origin::Right => -1.0,
-        _ => 1.0,
-    };
-    let y_sign: f64 = if m.origin.is_y_down() { 1.0 } else { -1.0 };
-
-    let mut result = verts;
-    for td in &data.transforms {
-        let tx_px = td.tx * x_sign;
-        let ty_px = td.ty * y_sign;
-        let n = result.len() as f64;
-        let (sum_x, sum_y) = result.iter().fold((0.0, 0.0), |(ax, ay), (x, y)| (ax + x, ay + y));
-        let (pivot_x, pivot_y) = (sum_x / n, sum_y / n);
-        // Rotation angle: td.angle is CCW in math (y-up) space.
-        // In screen pixels (y-down) the y axis is flipped, so CCW math = CW visually.
-        // To keep the same visual rotation as NDC, negate the angle.
-        let a = -td.angle;
-        let (cos_a, sin_a) = (a.cos(), a.sin());
-        result = result.into_iter().map(|(x, y)| {
-            let dx = (x - pivot_x) * td.sx;
-            let dy = (y - pivot_y) * td.sy;
-            let rx = dx * cos_a - dy * sin_a;
-            let ry = dx * sin_a + dy * cos_a;
-            (pivot_x + rx + tx_px, pivot_y + ry + ty_px)
-        }).collect();
-    }
-    result
+fn to_color32(c: Color) -> Color32 {
+    let ch = |v: f64| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    Color32::from_rgba_unmultiplied(ch(c.r), ch(c.g), ch(c.b), ch(c.a))
 }
 
-/// Convert a ShapeData to NDC vertices for display in the output tab.
-fn tessellate(data: &ShapeData) -> Vec<(f64, f64)> {
-    let m = &data.coord_meta;
-    let verts = match &data.desc {
-        ShapeDesc::Circle { center, radius } => {
-            let cx = m.x_to_ndc(center.0);
-            let cy = m.y_to_ndc(center.1);
-            let r  = m.w_to_ndc(*radius);
-            (0..64usize).map(|i| {
-                let t = i as f64 / 64.0 * std::f64::consts::TAU;
-                (cx + r * t.cos(), cy + r * t.sin())
-            }).collect()
-        }
-        ShapeDesc::Rect { center, size, origin } => {
-            let (w, h) = (size.0, size.1);
-            let ax = m.x_to_screen_px(center.0);
-            let ay = m.y_to_screen_px(center.1);
-            let (min_x, max_x) = match origin {
-                Origin::TopLeft | Origin::BottomLeft | Origin::Left
-                    => (ax, ax + w),
-                Origin::TopRight | Origin::BottomRight | Origin::Right
-                    => (ax - w, ax),
-                Origin::Center | Origin::Top | Origin::Bottom
-                    => (ax - w / 2.0, ax + w / 2.0),
-            };
-            let (min_y, max_y) = match origin {
-                Origin::TopLeft | Origin::TopRight | Origin::Top
-                    => (ay, ay + h),
-                Origin::BottomLeft | Origin::BottomRight | Origin::Bottom
-                    => (ay - h, ay),
-                Origin::Center | Origin::Left | Origin::Right
-                    => (ay - h / 2.0, ay + h / 2.0),
-            };
-            let snx = |s: f64| if m.px_width  > 0.0 { 2.0 * s / m.px_width  - 1.0 } else { s };
-            let sny = |s: f64| if m.px_height > 0.0 { 1.0 - 2.0 * s / m.px_height } else { s };
-            vec![
-                (snx(min_x), sny(min_y)), (snx(max_x), sny(min_y)),
-                (snx(max_x), sny(max_y)), (snx(min_x), sny(max_y)),
-            ]
+/// Split a polyline into `on`-long dash segments separated by `off`-long gaps.
+/// The pattern runs continuously around corners; `closed` adds the last → first edge.
+fn dash_segments(pts: &[egui::Pos2], closed: bool, on: f32, off: f32) -> Vec<[egui::Pos2; 2]> {
+    let mut out = Vec::new();
+    if pts.len() < 2 || on <= 0.0 { return out; }
+    let edges = pts.len() - 1 + usize::from(closed);
+    // Distance left in the current dash (`drawing`) or gap.
+    let (mut drawing, mut left) = (true, on);
+    for i in 0..edges {
+        let (a, b) = (pts[i], pts[(i + 1) % pts.len()]);
+        let len = a.distance(b);
+        let mut t = 0.0;
+        while t < len {
+            let step = left.min(len - t);
+            if drawing {
+                out.push([a.lerp(b, t / len), a.lerp(b, (t + step) / len)]);
+            }
+            t += step;
+            left -= step;
+            if left <= 0.0 {
+                drawing = !drawing || off <= 0.0;
+                left = if drawing { on } else { off };
+            }
         }
-        ShapeDesc::Line { from, to } => vec![
-            (m.x_to_ndc(from.0), m.y_to_ndc(from.1)),
-            (m.x_to_ndc(to.0),   m.y_to_ndc(to.1)),
-        ],
-        ShapeDesc::Polygon(pts) => pts.iter()
-            .map(|(x, y)| (m.x_to_ndc(*x), m.y_to_ndc(*y)))
-            .collect(),
-    };
-
-    // Apply accumulated transforms in NDC space
-    let mut result = verts;
-    for td in &data.transforms {
-        let tx = m.w_to_ndc(td.tx);
-        let ty = m.dy_to_ndc(td.ty);
-        let (cos_a, sin_a) = (td.angle.cos(), td.angle.sin());
-        let n = result.len() as f64;
-        let (sum_x, sum_y) = result.iter().fold((0.0, 0.0), |(ax, ay), (x, y)| (ax + x, ay + y));
-        let (pivot_x, pivot_y) = (sum_x / n, sum_y / n);
-        result = result.into_iter().map(|(x, y)| {
-            let dx = (x - pivot_x) * td.sx;
-            let dy = (y - pivot_y) * td.sy;
-            let rx = dx * cos_a - dy * sin_a;
-            let ry = dx * sin_a + dy * cos_a;
-            (pivot_x + rx + tx, pivot_y + ry + ty)
-        }).collect();
-    }
-    result
-}
-
-fn fmt_origin(o: &Origin) -> &'static str {
-    match o {
-        Origin::Center      => "center",
-        Origin::TopLeft     => "top_left",
-        Origin::TopRight    => "top_right",
-        Origin::BottomLeft  => "bottom_left",
-        Origin::BottomRight => "bottom_right",
-        Origin::Top         => "top",
-        Origin::Bottom      => "bottom",
-        Origin::Left        => "left",
-        Origin::Right       => "right",
     }
+    out
 }
 
 fn mono_row(ui: &mut egui::Ui, label: &str, value: &str) {
@@ -188,7 +61,7 @@ fn main() -> eframe::Result {
 // ─── App state ────────────────────────────────────────────────────────────────
 
 #[derive(PartialEq)]
-enum Tab { Errors, Symbols, Ast, Output, Canvas }
+enum Tab { Errors, Symbols, Ast, Output, Log, Canvas }
 
 struct App {
     source: String,
@@ -197,6 +70,20 @@ struct App {
     show_builtins: bool,
     runtime: Option<Runtime>,
     last_tick: std::time::Instant,
+    /// Seconds and frames since the sketch (re)started — fed to `input.time` / `input.frame`.
+    elapsed: f64,
+    frame: f64,
+    /// Pointer position in sketch coordinates, captured while drawing the canvas.
+    mouse: std::cell::Cell<(f64, f64)>,
+    /// `debug()` lines from the running sketch, newest last.
+    logs: Vec<String>,
+    timing: Timing,
+    /// While paused the runtime only ticks when Step is clicked, with `STEP_DT`.
+    paused: bool,
+    step_pending: bool,
+    /// Canvas view: sketch pixels are drawn at `pan + p * zoom` inside the canvas rect.
+    zoom: f32,
+    pan: egui::Vec2,
 }
 
 impl Default for App {
@@ -231,10 +118,65 @@ fn on_exit(s: State) -> State {
 }
 ");
         let result = run(&source, false);
-        Self { source, result, tab: Tab::Canvas, show_builtins: false, runtime: None, last_tick: std::time::Instant::now() }
+        Self {
+            source, result, tab: Tab::Canvas, show_builtins: false, runtime: None,
+            last_tick: std::time::Instant::now(), elapsed: 0.0, frame: 0.0,
+            mouse: std::cell::Cell::new((0.0, 0.0)),
+            logs: Vec::new(),
+            timing: Timing::default(),
+            paused: false, step_pending: false,
+            zoom: 1.0, pan: egui::Vec2::ZERO,
+        }
+    }
+}
+
+/// Loop budget per init/tick for sketches run in the editor, so a runaway
+/// loop errors out quickly instead of freezing the UI.
+const LOOP_BUDGET: u64 = 1_000_000;
+
+/// Most recent `debug()` lines kept for the Log tab — `debug` in `on_update`
+/// writes one line per frame.
+const MAX_LOG_LINES: usize = 1000;
+
+/// `input.dt` for a single Step while paused.
+const STEP_DT: f64 = 1.0 / 60.0;
+
+/// Frames averaged by the FPS / tick-time readout (about two seconds at 60 fps).
+const TIMING_WINDOW: usize = 120;
+
+/// Rolling frame and `tick` timings for the status bar.
+#[derive(Default)]
+struct Timing {
+    /// `(frame interval, tick duration)` in seconds, newest last.
+    samples: std::collections::VecDeque<(f64, f64)>,
+}
+
+impl Timing {
+    fn record(&mut self, frame_secs: f64, tick_secs: f64) {
+        self.samples.push_back((frame_secs, tick_secs));
+        if self.samples.len() > TIMING_WINDOW {
+            self.samples.pop_front();
+        }
+    }
+
+    fn clear(&mut self) { self.samples.clear(); }
+
+    /// `(frames per second, average tick in ms)`, once there are samples.
+    fn summary(&self) -> Option<(f64, f64)> {
+        if self.samples.is_empty() { return None; }
+        let n = self.samples.len() as f64;
+        let frame: f64 = self.samples.iter().map(|s| s.0).sum::<f64>() / n;
+        let tick:  f64 = self.samples.iter().map(|s| s.1).sum::<f64>() / n;
+        Some((if frame > 0.0 { 1.0 / frame } else { 0.0 }, tick * 1000.0))
     }
 }
 
+fn push_logs(logs: &mut Vec<String>, lines: Vec<String>) {
+    logs.extend(lines);
+    let excess = logs.len().saturating_sub(MAX_LOG_LINES);
+    logs.drain(..excess);
+}
+
 // ─── Run result ───────────────────────────────────────────────────────────────
 
 struct SymbolRow {
@@ -332,21 +274,34 @@ fn run(source: &str, _show_builtins: bool) -> RunResult {
 
 impl eframe::App for App {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // ── Tick runtime every frame ──────────────────────────────────────────
+        // ── Tick runtime every frame (once per Step while paused) ─────────────
         let now = std::time::Instant::now();
-        let dt = now.duration_since(self.last_tick).as_secs_f64().min(0.1);
+        let frame_secs = now.duration_since(self.last_tick).as_secs_f64();
+        let dt = if self.paused { STEP_DT } else { frame_secs.min(0.1) };
         self.last_tick = now;
-
-        if let Some(rt) = &mut self.runtime {
-            let input = Input { dt };
-            match rt.tick(&input) {
-                Ok(cmds) => self.result.draw_commands = cmds,
-                Err(e) => {
-                    self.result.errors.push(format!("[runtime] {}", e.message));
-                    self.runtime = None;
+        let should_tick = !self.paused || std::mem::take(&mut self.step_pending);
+
+        if should_tick && let Some(rt) = &mut self.runtime {
+            self.elapsed += dt;
+            let input = Input { dt, time: self.elapsed, frame: self.frame, mouse: self.mouse.get() };
+            self.frame += 1.0;
+            let tick_start = std::time::Instant::now();
+            // A failed frame keeps the sketch running on the last good picture.
+            let (cmds, err) = rt.try_tick(&input);
+            if !self.paused {
+                self.timing.record(frame_secs, tick_start.elapsed().as_secs_f64());
+            }
+            push_logs(&mut self.logs, rt.take_logs());
+            self.result.draw_commands = cmds;
+            if let Some(e) = err {
+                let msg = format!("[runtime] {}", e.message);
+                if self.result.errors.last() != Some(&msg) {
+                    self.result.errors.push(msg);
                 }
             }
-            ctx.request_repaint();
+            if !self.paused {
+                ctx.request_repaint();
+            }
         }
 
         egui::CentralPanel::default().show(ctx, |ui| {
@@ -374,6 +329,19 @@ impl eframe::App for App {
                         } else {
                             ui.label(RichText::new(format!("✗  {error_count} error(s)")).color(Color32::from_rgb(220, 80, 80)));
                         }
+                        if self.runtime.is_some() && self.paused {
+                            ui.label(RichText::new(format!(
+                                "paused at frame {}  ·  {} draw cmd(s)",
+                                self.frame, self.result.draw_commands.len(),
+                            )).monospace().color(Color32::GRAY));
+                        } else if self.runtime.is_some()
+                            && let Some((fps, tick_ms)) = self.timing.summary()
+                        {
+                            ui.label(RichText::new(format!(
+                                "{fps:.0} fps  ·  tick {tick_ms:.2} ms  ·  {} draw cmd(s)",
+                                self.result.draw_commands.len(),
+                            )).monospace().color(Color32::GRAY));
+                        }
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                             ui.checkbox(&mut self.show_builtins, "show builtins");
                             let running = self.runtime.is_some();
@@ -381,13 +349,54 @@ impl eframe::App for App {
                                 if ui.button("Stop").clicked() {
                                     if let Some(mut rt) = self.runtime.take() {
                                         let _ = rt.exit();
+                                        push_logs(&mut self.logs, rt.take_logs());
+                                    }
+                                }
+                                if ui.button("Restart").clicked()
+                                    && let Some(rt) = &mut self.runtime
+                                {
+                                    match rt.reset() {
+                                        Ok(()) => {
+                                            self.elapsed = 0.0;
+                                            self.frame = 0.0;
+                                            self.timing.clear();
+                                        }
+                                        Err(e) => {
+                                            self.result.errors.push(format!("[runtime] {}", e.message));
+                                            self.runtime = None;
+                                        }
                                     }
                                 }
+                                if self.paused {
+                                    if ui.button("Step").clicked() {
+                                        self.step_pending = true;
+                                        ctx.request_repaint();
+                                    }
+                                    if ui.button("Play").clicked() {
+                                        self.paused = false;
+                                        self.last_tick = std::time::Instant::now();
+                                        self.timing.clear();
+                                    }
+                                } else if ui.button("Pause").clicked() {
+                                    self.paused = true;
+                                }
                             } else {
                                 if ui.button("Run").clicked() {
                                     self.result = run(&self.source, self.show_builtins);
-                                    self.runtime = compile(&self.source).ok().and_then(|p| Runtime::new(p).ok());
+                                    self.logs.clear();
+                                    self.runtime = match compile(&self.source).map(|p| Runtime::with_iteration_limit(p, LOOP_BUDGET)) {
+                                        Ok(Ok(rt)) => Some(rt),
+                                        Ok(Err(e)) => {
+                                            self.result.errors.push(format!("[runtime] {}", e.message));
+                                            None
+                                        }
+                                        Err(_) => None,
+                                    };
                                     self.last_tick = std::time::Instant::now();
+                                    self.elapsed = 0.0;
+                                    self.frame = 0.0;
+                                    self.timing.clear();
+                                    self.paused = false;
                                 }
                             }
                         });
@@ -406,6 +415,7 @@ impl eframe::App for App {
                         ui.selectable_value(&mut self.tab, Tab::Symbols, "Symbols");
                         ui.selectable_value(&mut self.tab, Tab::Ast, "AST");
                         ui.selectable_value(&mut self.tab, Tab::Output, "Output");
+                        ui.selectable_value(&mut self.tab, Tab::Log, "Log");
                         ui.selectable_value(&mut self.tab, Tab::Canvas, "Canvas");
                     });
 
@@ -418,6 +428,7 @@ impl eframe::App for App {
                             Tab::Symbols => self.show_symbols(ui),
                             Tab::Ast     => self.show_ast(ui),
                             Tab::Output  => self.show_output(ui),
+                            Tab::Log     => self.show_log(ui),
                             Tab::Canvas  => self.show_canvas(ui),
                         }
                     });
@@ -449,6 +460,7 @@ impl App {
                 RenderMode::Fill      => "fill".to_string(),
                 RenderMode::Outline   => "outline".to_string(),
                 RenderMode::Stroke(w) => format!("stroke({w:.3})"),
+                RenderMode::Dashed { width, on, off } => format!("dashed({width:.3}, {on:.3}, {off:.3})"),
             };
 
             let shape_name = match &data.desc {
@@ -456,6 +468,7 @@ impl App {
                 ShapeDesc::Rect { .. }   => "rect",
                 ShapeDesc::Line { .. }   => "line",
                 ShapeDesc::Polygon(_)    => "polygon",
+                ShapeDesc::Wedge { .. }  => "wedge",
             };
 
             // ── Header ───────────────────────────────────────────────────────
@@ -477,7 +490,7 @@ impl App {
                 ShapeDesc::Rect { center, size, origin } => {
                     mono_row(ui, "  center:", &v2(center.0, center.1));
                     mono_row(ui, "  size:  ", &v2(size.0, size.1));
-                    mono_row(ui, "  origin:", fmt_origin(origin));
+                    mono_row(ui, "  origin:", origin.name());
                 }
                 ShapeDesc::Line { from, to } => {
                     mono_row(ui, "  from:", &v2(from.0, from.1));
@@ -487,6 +500,11 @@ impl App {
                     let pts_str: Vec<String> = pts.iter().map(|(x, y)| v2(*x, *y)).collect();
                     mono_row(ui, "  pts:", &pts_str.join(", "));
                 }
+                ShapeDesc::Wedge { center, radius, start_angle, end_angle } => {
+                    mono_row(ui, "  center:", &v2(center.0, center.1));
+                    mono_row(ui, "  radius:", &format!("{:.3}", radius));
+                    mono_row(ui, "  angles:", &format!("{:.1}° → {:.1}°", start_angle.to_degrees(), end_angle.to_degrees()));
+                }
             }
 
             // ── CoordMeta ────────────────────────────────────────────────────
@@ -494,14 +512,14 @@ impl App {
             mono_row(ui, "  meta:", &format!(
                 "{}×{}px  origin={}",
                 m.px_width as u32, m.px_height as u32,
-                fmt_origin(&m.origin)
+                m.origin.name()
             ));
 
             // ── Transforms ───────────────────────────────────────────────────
             for (ti, td) in data.transforms.iter().enumerate() {
                 mono_row(ui, &format!("  tf[{}]:", ti), &format!(
-                    "move=({:.3}, {:.3})  scale=({:.3}, {:.3})  rot={:.1}°",
-                    td.tx, td.ty, td.sx, td.sy, td.angle.to_degrees()
+                    "move=({:.3}, {:.3})  scale=({:.3}, {:.3})  shear=({:.3}, {:.3})  rot={:.1}°",
+                    td.tx, td.ty, td.sx, td.sy, td.shx, td.shy, td.angle.to_degrees()
                 ));
             }
 
@@ -515,14 +533,14 @@ impl App {
             };
             let raw_verts = tessellate_screen_px(data);
             mono_row(ui, "  px: ", &fmt_verts(&raw_verts));
-            let ndc_verts = tessellate(data);
+            let ndc_verts = tessellate_ndc(data);
             mono_row(ui, "  ndc:", &fmt_verts(&ndc_verts));
 
             ui.add_space(8.0);
         }
     }
 
-    fn show_canvas(&self, ui: &mut egui::Ui) {
+    fn show_canvas(&mut self, ui: &mut egui::Ui) {
         if self.result.draw_commands.is_empty() {
             let msg = if self.result.errors.iter().any(|e| !e.starts_with("[warn]")) {
                 "Fix errors to run."
@@ -536,18 +554,41 @@ impl App {
         }
 
         // Determine canvas size from first command's coord_meta, or default 400×400.
-        let (canvas_w, canvas_h) = {
-            let first = match &self.result.draw_commands[0] { DrawCommand::DrawShape(d) => d };
-            let m = &first.coord_meta;
-            if m.px_width > 0.0 && m.px_height > 0.0 {
-                (m.px_width as f32, m.px_height as f32)
-            } else {
-                (400.0_f32, 400.0_f32)
-            }
+        let DrawCommand::DrawShape(first) = &self.result.draw_commands[0];
+        let meta = &first.coord_meta;
+        let (canvas_w, canvas_h) = if meta.px_width > 0.0 && meta.px_height > 0.0 {
+            (meta.px_width as f32, meta.px_height as f32)
+        } else {
+            (400.0_f32, 400.0_f32)
         };
 
-        let desired = egui::vec2(canvas_w, canvas_h);
-        let (canvas_rect, _response) = ui.allocate_exact_size(desired, egui::Sense::hover());
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(format!("{:.0}%", self.zoom * 100.0)).monospace().color(Color32::GRAY));
+            if ui.button("Reset view").clicked() {
+                self.zoom = 1.0;
+                self.pan = egui::Vec2::ZERO;
+            }
+            ui.label(RichText::new("drag to pan, scroll to zoom").color(Color32::GRAY));
+        });
+
+        // The viewport keeps the sketch's aspect ratio but never exceeds the panel width;
+        // whatever doesn't fit is reached by panning and zooming.
+        let view_w = canvas_w.min(ui.available_width());
+        let desired = egui::vec2(view_w, canvas_h * view_w / canvas_w);
+        let (canvas_rect, response) = ui.allocate_exact_size(desired, egui::Sense::drag());
+        self.pan += response.drag_delta();
+        if let Some(pos) = response.hover_pos() {
+            let scroll = ui.input(|i| i.smooth_scroll_delta.y);
+            if scroll != 0.0 {
+                // Zoom about the cursor: the sketch point under it stays put.
+                let new_zoom = (self.zoom * (scroll * 0.002).exp()).clamp(0.05, 50.0);
+                let anchor = (pos - canvas_rect.min - self.pan) / self.zoom;
+                self.pan = pos - canvas_rect.min - anchor * new_zoom;
+                self.zoom = new_zoom;
+            }
+            let local = (pos - canvas_rect.min - self.pan) / self.zoom;
+            self.mouse.set((meta.screen_px_to_x(local.x as f64), meta.screen_px_to_y(local.y as f64)));
+        }
         let painter = ui.painter_at(canvas_rect);
 
         // Background
@@ -560,43 +601,75 @@ impl App {
             let screen_verts = tessellate_screen_px(data);
             if screen_verts.is_empty() { continue; }
 
-            // Offset vertices by canvas top-left so they map onto the allocated rect.
-            let offset = canvas_rect.min;
+            // Apply the view transform, then offset by the canvas top-left.
+            let offset = canvas_rect.min + self.pan;
             let pts: Vec<egui::Pos2> = screen_verts.iter()
-                .map(|(x, y)| egui::pos2(offset.x + *x as f32, offset.y + *y as f32))
+                .map(|(x, y)| offset + egui::vec2(*x as f32, *y as f32) * self.zoom)
                 .collect();
 
-            let fill_color   = Color32::from_rgba_unmultiplied(180, 160, 255, 200);
-            let stroke_color = Color32::from_rgba_unmultiplied(200, 180, 255, 255);
+            let alpha = data.alpha as f32;
+            let fill_color = data.fill.map(to_color32)
+                .unwrap_or(Color32::from_rgba_unmultiplied(180, 160, 255, 200))
+                .gamma_multiply(alpha);
+            let stroke_color = data.stroke_color.map(to_color32)
+                .unwrap_or(Color32::from_rgba_unmultiplied(200, 180, 255, 255))
+                .gamma_multiply(alpha);
             let stroke_width = match &data.render_mode {
-                RenderMode::Stroke(w) => *w as f32,
+                RenderMode::Stroke(w) | RenderMode::Dashed { width: w, .. } => *w as f32 * self.zoom,
                 _ => 1.5_f32,
             };
             let stroke = egui::Stroke::new(stroke_width, stroke_color);
 
             let is_line = matches!(&data.desc, ShapeDesc::Line { .. });
 
-            if is_line {
+            if let RenderMode::Dashed { on, off, .. } = &data.render_mode {
+                let (on, off) = (*on as f32 * self.zoom, *off as f32 * self.zoom);
+                for seg in dash_segments(&pts, !is_line, on, off) {
+                    painter.line_segment(seg, stroke);
+                }
+            } else if is_line {
                 if pts.len() >= 2 {
                     painter.line_segment([pts[0], pts[1]], stroke);
                 }
             } else {
                 match &data.render_mode {
-                    RenderMode::Fill | RenderMode::Sdf => {
+                    RenderMode::Fill | RenderMode::Sdf if is_convex(&screen_verts) => {
                         painter.add(egui::Shape::convex_polygon(
                             pts,
                             fill_color,
                             egui::Stroke::NONE,
                         ));
                     }
+                    RenderMode::Fill | RenderMode::Sdf => {
+                        // Concave outlines (polygons, boolean results) need a real triangulation.
+                        let mut mesh = egui::Mesh::default();
+                        for p in pts {
+                            mesh.colored_vertex(p, fill_color);
+                        }
+                        for [a, b, c] in triangulate(&screen_verts) {
+                            mesh.add_triangle(a as u32, b as u32, c as u32);
+                        }
+                        painter.add(egui::Shape::mesh(mesh));
+                    }
                     RenderMode::Outline | RenderMode::Stroke(_) => {
                         painter.add(egui::Shape::closed_line(pts, stroke));
                     }
+                    RenderMode::Dashed { .. } => unreachable!("dashed shapes drawn above"),
                 }
             }
         }
     }
 
+    fn show_log(&self, ui: &mut egui::Ui) {
+        if self.logs.is_empty() {
+            ui.label(RichText::new("No debug() output.").color(Color32::GRAY));
+            return;
+        }
+        for line in &self.logs {
+            ui.label(RichText::new(line).monospace());
+        }
+    }
+
     fn show_errors(&self, ui: &mut egui::Ui) {
         if self.result.errors.is_empty() {
             ui.label(RichText::new("No errors.").color(Color32::GRAY));
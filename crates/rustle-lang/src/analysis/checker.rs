@@ -43,12 +43,18 @@ impl<'a> TypeResolver<'a> {
     pub fn run(mut self, program: &'a Program) -> (SymbolTable, Vec<Error>) {
         self.program = Some(program);
         self.lookup = LookupContext::new(Some(program), self.lookup.registry);
+        for (name, fields) in &self.table.structs {
+            self.lookup.register_struct(name, fields.clone());
+        }
         if let Some(state) = &program.state {
             self.check_state(state);
         }
         for item in &program.items {
             match item {
                 Item::FnDef(f)  => self.check_fn(f),
+                // Aliases are expanded at parse time, struct fields were
+                // registered above and enums live in the table.
+                Item::TypeAlias(_) | Item::StructDef(_) | Item::EnumDef(_) => {}
                 Item::Stmt(s)   => { self.check_stmt(s); }
             }
         }
@@ -59,7 +65,10 @@ impl<'a> TypeResolver<'a> {
 
     fn check_state(&mut self, state: &StateBlock) {
         for field in &state.fields {
-            let init_ty = self.infer_expr(&field.initializer);
+            let init_ty = match &field.ty {
+                Some(ann) => self.infer_against(&field.initializer, ann),
+                None      => self.infer_expr(&field.initializer),
+            };
             let resolved_ty = match (&field.ty, init_ty) {
                 (Some(ann), Ok(inferred)) => {
                     self.expect_type(&ann.clone(), &inferred, &field.span);
@@ -90,9 +99,19 @@ impl<'a> TypeResolver<'a> {
 
         self.table.push_scope(ScopeKind::Function);
 
-        // Declare params in function scope
+        // Declare params in function scope. Lifecycle hooks have a fixed
+        // signature, so their params are never reported as unused.
+        let is_hook = matches!(f.name.as_str(), "on_init" | "on_update" | "on_exit");
         for param in &f.params {
-            let sym = Symbol::new(param.name.clone(), Some(param.ty.clone()), SymbolKind::Param, param.span.clone());
+            // Defaults see the params declared before them, like the runtime.
+            if let Some(default) = &param.default {
+                match self.infer_expr(default) {
+                    Ok(ty) => self.expect_type(&param.ty, &ty, default.span()),
+                    Err(e) => self.errors.extend(e),
+                }
+            }
+            let mut sym = Symbol::new(param.name.clone(), Some(param.ty.clone()), SymbolKind::Param, param.span.clone());
+            sym.used = is_hook;
             self.table.declare(sym);
         }
 
@@ -110,6 +129,7 @@ impl<'a> TypeResolver<'a> {
     fn check_stmt(&mut self, stmt: &Stmt) {
         match stmt {
             Stmt::VarDecl(v) => self.check_var_decl(v),
+            Stmt::Destructure(d) => self.check_destructure(d),
             Stmt::Assign(a)  => self.check_assign(a),
             Stmt::Out(o)     => self.check_out(o),
             Stmt::If(i)      => self.check_if(i),
@@ -128,7 +148,11 @@ impl<'a> TypeResolver<'a> {
     }
 
     fn check_var_decl(&mut self, v: &VarDecl) {
-        let init_ty = match self.infer_expr(&v.initializer) {
+        let inferred = match &v.ty {
+            Some(ann) => self.infer_against(&v.initializer, ann),
+            None      => self.infer_expr(&v.initializer),
+        };
+        let init_ty = match inferred {
             Ok(t)  => t,
             Err(e) => { self.errors.extend(e); return; }
         };
@@ -156,9 +180,54 @@ impl<'a> TypeResolver<'a> {
         }
     }
 
+    fn check_destructure(&mut self, d: &DestructureDecl) {
+        let init_ty = match self.infer_expr(&d.initializer) {
+            Ok(t)  => t,
+            Err(e) => { self.errors.extend(e); return; }
+        };
+        let tuple_ty = match &d.ty {
+            Some(ann) => { self.expect_type(ann, &init_ty, &d.span); ann.clone() }
+            None      => init_ty,
+        };
+        let elem_tys = match &tuple_ty {
+            Type::Tuple(items) if items.len() == d.names.len() => items.clone(),
+            Type::Tuple(items) => {
+                self.errors.push(Error::new(
+                    ErrorCode::S002, d.span.line, d.span.column,
+                    format!("cannot destructure a {}-element tuple into {} names", items.len(), d.names.len()),
+                ));
+                return;
+            }
+            other => {
+                self.errors.push(Error::new(
+                    ErrorCode::S002, d.span.line, d.span.column,
+                    format!("cannot destructure `{}`, expected a tuple", type_name(other)),
+                ));
+                return;
+            }
+        };
+
+        let kind = if d.is_const { SymbolKind::Const } else { SymbolKind::Variable };
+        for (name, ty) in d.names.iter().zip(elem_tys) {
+            if self.table.current_scope_kind() == &ScopeKind::Global {
+                self.table.update_type(name, ty);
+            } else if !self.table.declare(Symbol::new(name.clone(), Some(ty), kind.clone(), d.span.clone())) {
+                self.errors.push(Error::new(
+                    ErrorCode::S003, d.span.line, d.span.column,
+                    format!("`{name}` already declared in this scope"),
+                ));
+            }
+        }
+    }
+
     fn check_assign(&mut self, a: &Assign) {
         let path = a.target.path();
         let root = &path[0];
+        // Writing through `s.x` or `xs[i]` uses the binding, and so does reading
+        // it for `x += …`; plain `x = …` does not.
+        if path.len() > 1 || matches!(a.target, AssignTarget::Indexed { .. }) || a.op.is_some() {
+            self.table.mark_used(root, self.current_fn_order);
+        }
         let sym = self.lookup_symbol(root, &a.span);
 
         if let Some(sym) = sym {
@@ -173,26 +242,45 @@ impl<'a> TypeResolver<'a> {
             for segment in &path[1..] {
                 ty = ty.and_then(|t| self.lookup.resolve_field(&t, segment));
             }
-            // For indexed target, drill down to element type and check index is float
+            // For indexed target, drill down to element type and check each index
             if let AssignTarget::Indexed { indices, .. } = &a.target {
                 for idx in indices {
                     match self.infer_expr(idx) {
-                        Ok(idx_ty) if idx_ty != Type::Float => {
-                            self.errors.push(Error::new(
-                                ErrorCode::S002, idx.span().line, idx.span().column,
-                                format!("index must be `float`, found `{}`", type_name(&idx_ty)),
-                            ));
+                        Ok(idx_ty) => {
+                            if let Some(coll_ty) = &ty {
+                                self.check_index_type(coll_ty, &idx_ty, idx.span());
+                            }
                         }
                         Err(e) => self.errors.extend(e),
-                        _ => {}
                     }
                     ty = ty.and_then(|t| self.indexed_type(&t));
                 }
             }
-            match self.infer_expr(&a.value) {
+            let val_ty = match (&ty, &a.op) {
+                (Some(target_ty), None) => self.infer_against(&a.value, target_ty),
+                _ => self.infer_expr(&a.value),
+            };
+            match val_ty {
                 Ok(val_ty) => {
                     if let Some(target_ty) = &ty {
-                        self.expect_type(target_ty, &val_ty, &a.span);
+                        // `x op= v` stores the result of `x op v`.
+                        let stored = match &a.op {
+                            Some(op) => match self.check_binop(op, target_ty, &val_ty, &a.span) {
+                                Ok(t) => t,
+                                Err(e) => { self.errors.extend(e); return; }
+                            },
+                            None => {
+                                // The target's type is not known at runtime, so
+                                // record the widening of ints and fresh literals.
+                                let literal = matches!(&a.value, Expr::List(..) | Expr::Map(..) | Expr::Tuple(..))
+                                    || matches!(&a.value, Expr::Call { callee, .. } if callee == "ok");
+                                if val_ty != *target_ty || literal {
+                                    self.table.widened_exprs.insert(a.value.span().clone(), target_ty.clone());
+                                }
+                                val_ty
+                            }
+                        };
+                        self.expect_type(target_ty, &stored, &a.span);
                     }
                 }
                 Err(e) => self.errors.extend(e),
@@ -202,11 +290,25 @@ impl<'a> TypeResolver<'a> {
 
     fn indexed_type(&self, ty: &Type) -> Option<Type> {
         match ty {
-            Type::List(elem) | Type::Array(elem, _) => Some(*elem.clone()),
+            Type::List(elem) | Type::Array(elem, _) | Type::Map(elem) => Some(*elem.clone()),
             _ => None,
         }
     }
 
+    /// Maps are indexed by `string`; lists and arrays by `int` or `float`.
+    fn check_index_type(&mut self, coll_ty: &Type, idx_ty: &Type, span: &Span) {
+        let (ok, expected) = match coll_ty {
+            Type::Map(_) => (*idx_ty == Type::Named("string".into()), "`string`"),
+            _            => (is_numeric(idx_ty), "`int` or `float`"),
+        };
+        if !ok {
+            self.errors.push(Error::new(
+                ErrorCode::S002, span.line, span.column,
+                format!("index must be {expected}, found `{}`", type_name(idx_ty)),
+            ));
+        }
+    }
+
     fn check_out(&mut self, o: &OutStmt) {
         for expr in &o.shapes {
             match self.infer_expr(expr) {
@@ -228,16 +330,16 @@ impl<'a> TypeResolver<'a> {
             Ok(t) => t,
             Err(e) => { self.errors.extend(e); return; }
         };
-        if !is_matchable(&scrut_ty) {
+        if !is_matchable(&scrut_ty) && self.enum_variants(&scrut_ty).is_none() {
             self.errors.push(Error::new(
                 ErrorCode::S008, m.expr.span().line, m.expr.span().column,
-                format!("match scrutinee must be a comparable type (float, bool, string, vec2, vec3, vec4, color), found `{}`", type_name(&scrut_ty)),
+                format!("match scrutinee must be a comparable type (int, float, bool, string, vec2, vec3, vec4, color, enum), found `{}`", type_name(&scrut_ty)),
             ));
         }
         for arm in &m.arms {
             for val in &arm.values {
                 match self.infer_expr(val) {
-                    Ok(val_ty) if !types_compatible(&scrut_ty, &val_ty) => {
+                    Ok(val_ty) if !match_compatible(&scrut_ty, &val_ty) => {
                         self.errors.push(Error::new(
                             ErrorCode::S002, val.span().line, val.span().column,
                             format!("match arm value must match scrutinee type `{}`, found `{}`", type_name(&scrut_ty), type_name(&val_ty)),
@@ -249,6 +351,7 @@ impl<'a> TypeResolver<'a> {
             }
             self.check_block(&arm.body);
         }
+        self.check_enum_exhaustive(&scrut_ty, m.arms.iter().map(|a| a.values.as_slice()), &m.span);
     }
 
     fn check_if(&mut self, i: &IfStmt) {
@@ -303,20 +406,26 @@ impl<'a> TypeResolver<'a> {
         self.table.pop_scope();
     }
 
-    fn check_foreach(&mut self, f: &ForeachStmt) {
-        let elem_ty = match self.infer_expr(&f.iterable) {
+    /// Element type of the list or array `iterable` that `what` (`foreach`, a
+    /// comprehension) walks over. Reports S002 and returns `None` otherwise.
+    fn iterable_elem_type(&mut self, iterable: &Expr, what: &str) -> Option<Type> {
+        match self.infer_expr(iterable) {
             Ok(Type::List(elem))        => Some(*elem),
             Ok(Type::Array(elem, _))    => Some(*elem),
             Ok(other) => {
                 self.errors.push(Error::new(
                     ErrorCode::S002,
-                    f.iterable.span().line, f.iterable.span().column,
-                    format!("foreach expects a list or array, found `{}`", type_name(&other)),
+                    iterable.span().line, iterable.span().column,
+                    format!("{what} expects a list or array, found `{}`", type_name(&other)),
                 ));
                 None
             }
             Err(e) => { self.errors.extend(e); None }
-        };
+        }
+    }
+
+    fn check_foreach(&mut self, f: &ForeachStmt) {
+        let elem_ty = self.iterable_elem_type(&f.iterable, "foreach");
 
         self.table.push_scope(ScopeKind::Block);
 
@@ -327,7 +436,14 @@ impl<'a> TypeResolver<'a> {
             } else {
                 elem_ty
             };
-            let sym = Symbol::new(f.var_name.clone(), Some(var_ty), SymbolKind::Variable, f.span.clone());
+            // Loop bindings aren't `let`s — exempt from unused warnings.
+            let mut sym = Symbol::new(f.var_name.clone(), Some(var_ty), SymbolKind::Variable, f.span.clone());
+            sym.used = true;
+            self.table.declare(sym);
+        }
+        if let Some(index_name) = &f.index_name {
+            let mut sym = Symbol::new(index_name.clone(), Some(Type::Float), SymbolKind::Variable, f.span.clone());
+            sym.used = true;
             self.table.declare(sym);
         }
 
@@ -338,7 +454,7 @@ impl<'a> TypeResolver<'a> {
     fn check_return(&mut self, expr: Option<&Expr>, span: &Span) {
         match (expr, &self.current_fn_return.clone()) {
             (Some(e), Some(expected)) => {
-                match self.infer_expr(e) {
+                match self.infer_against(e, expected) {
                     Ok(actual) => self.expect_type(expected, &actual, span),
                     Err(e) => self.errors.extend(e),
                 }
@@ -397,9 +513,25 @@ impl<'a> TypeResolver<'a> {
 
     pub fn infer_expr(&mut self, expr: &Expr) -> Result<Type, Vec<Error>> {
         match expr {
+            Expr::Int(_, _)       => Ok(Type::Int),
             Expr::Float(_, _)     => Ok(Type::Float),
             Expr::Bool(_, _)      => Ok(Type::Bool),
             Expr::StringLit(_, _) => Ok(Type::Named("string".into())),
+            Expr::Interpolated(parts, _) => {
+                for part in parts {
+                    match self.infer_expr(part) {
+                        Ok(ty) if matches!(ty, Type::Fn(..)) || ty == Type::Named("void".into()) => {
+                            self.errors.push(Error::new(
+                                ErrorCode::S002, part.span().line, part.span().column,
+                                format!("cannot interpolate a value of type `{}`", type_name(&ty)),
+                            ));
+                        }
+                        Err(e) => self.errors.extend(e),
+                        _ => {}
+                    }
+                }
+                Ok(Type::Named("string".into()))
+            }
             Expr::HexColor(_, _)  => Ok(Type::Named("color".into())),
 
             Expr::Ident(name, span) => self.lookup_type(name, span),
@@ -410,6 +542,14 @@ impl<'a> TypeResolver<'a> {
                 self.check_binop(op, &l, &r, span)
             }
 
+            Expr::CompareChain { operands, ops, span } => {
+                let tys = operands.iter().map(|e| self.infer_expr(e)).collect::<Result<Vec<_>, _>>()?;
+                for (op, pair) in ops.iter().zip(tys.windows(2)) {
+                    self.check_binop(op, &pair[0], &pair[1], span)?;
+                }
+                Ok(Type::Bool)
+            }
+
             Expr::UnOp { op, operand, span } => {
                 let ty = self.infer_expr(operand)?;
                 self.check_unop(op, operand, &ty, span)
@@ -425,25 +565,116 @@ impl<'a> TypeResolver<'a> {
                 }
                 let then_ty = self.infer_expr(then_expr)?;
                 let else_ty = self.infer_expr(else_expr)?;
-                if then_ty != else_ty {
+                let mixed = then_ty != else_ty;
+                let ty = unify_branches("ternary", then_ty, &else_ty, span)?;
+                if mixed && ty == Type::Float {
+                    self.table.widened_exprs.insert(span.clone(), Type::Float);
+                }
+                Ok(ty)
+            }
+
+            Expr::Match { expr, arms, span } => {
+                let scrut_ty = self.infer_expr(expr)?;
+                if !is_matchable(&scrut_ty) && self.enum_variants(&scrut_ty).is_none() {
                     return Err(vec![Error::new(
-                        ErrorCode::S002, span.line, span.column,
-                        format!(
-                            "ternary branches have different types: `{}` and `{}`",
-                            type_name(&then_ty), type_name(&else_ty)
-                        ),
+                        ErrorCode::S008, expr.span().line, expr.span().column,
+                        format!("match scrutinee must be a comparable type (int, float, bool, string, vec2, vec3, vec4, color, enum), found `{}`", type_name(&scrut_ty)),
                     )]);
                 }
-                Ok(then_ty)
+                let mut result_ty: Option<Type> = None;
+                let mut mixed = false;
+                for arm in arms {
+                    for val in &arm.values {
+                        let val_ty = self.infer_expr(val)?;
+                        if !match_compatible(&scrut_ty, &val_ty) {
+                            return Err(vec![Error::new(
+                                ErrorCode::S002, val.span().line, val.span().column,
+                                format!("match arm value must match scrutinee type `{}`, found `{}`", type_name(&scrut_ty), type_name(&val_ty)),
+                            )]);
+                        }
+                    }
+                    let arm_ty = self.infer_expr(&arm.result)?;
+                    result_ty = Some(match result_ty {
+                        Some(prev) => {
+                            mixed |= prev != arm_ty;
+                            unify_branches("match", prev, &arm_ty, &arm.span)?
+                        }
+                        None => arm_ty,
+                    });
+                }
+                if mixed && result_ty == Some(Type::Float) {
+                    self.table.widened_exprs.insert(span.clone(), Type::Float);
+                }
+                self.check_enum_exhaustive(&scrut_ty, arms.iter().map(|a| a.values.as_slice()), span);
+                result_ty.ok_or_else(|| vec![Error::new(
+                    ErrorCode::S002, span.line, span.column,
+                    "match expression must have at least one arm",
+                )])
+            }
+
+            Expr::Cast { expr, ty, .. } => {
+                self.infer_expr(expr)?;
+                Ok(ty.clone())
+            }
+
+            Expr::Range { start, end, step, .. } => {
+                for bound in [Some(start), Some(end), step.as_ref()].into_iter().flatten() {
+                    let ty = self.infer_expr(bound)?;
+                    if !is_numeric(&ty) {
+                        let sp = bound.span();
+                        return Err(vec![Error::new(
+                            ErrorCode::S002, sp.line, sp.column,
+                            format!("range bounds must be `int` or `float`, found `{}`", type_name(&ty)),
+                        )]);
+                    }
+                }
+                Ok(Type::List(Box::new(Type::Float)))
             }
 
-            Expr::Cast { ty, .. } => Ok(ty.clone()),
+            Expr::Comprehension { expr, var_name, iterable, filter, span } => {
+                let elem_ty = self.iterable_elem_type(iterable, "comprehension");
+                self.table.push_scope(ScopeKind::Block);
+                // Like a foreach binding, exempt from unused warnings.
+                let mut sym = Symbol::new(var_name.clone(), elem_ty, SymbolKind::Variable, span.clone());
+                sym.used = true;
+                self.table.declare(sym);
+                if let Some(filter) = filter {
+                    match self.infer_expr(filter) {
+                        Ok(ty) if ty != Type::Bool => self.errors.push(Error::new(
+                            ErrorCode::S002, filter.span().line, filter.span().column,
+                            format!("comprehension filter must be `bool`, found `{}`", type_name(&ty)),
+                        )),
+                        Err(e) => self.errors.extend(e),
+                        _ => {}
+                    }
+                }
+                let result = self.infer_expr(expr);
+                self.table.pop_scope();
+                Ok(Type::List(Box::new(result?)))
+            }
 
             Expr::Try { expr, .. } => {
                 let inner = self.infer_expr(expr)?;
                 Ok(Type::Res(Box::new(inner)))
             }
 
+            Expr::Propagate { expr, span } => {
+                let ty = self.infer_expr(expr)?;
+                if !matches!(self.current_fn_return, Some(Type::Res(_))) {
+                    self.errors.push(Error::new(
+                        ErrorCode::S002, span.line, span.column,
+                        "`?` can only be used inside a function returning `res<…>`",
+                    ));
+                }
+                match ty {
+                    Type::Res(inner) => Ok(*inner),
+                    other => Err(vec![Error::new(
+                        ErrorCode::S002, span.line, span.column,
+                        format!("`?` expects a `res<…>`, found `{}`", type_name(&other)),
+                    )]),
+                }
+            }
+
             Expr::Call { callee, args, named_args, span } => {
                 self.check_call(callee, args, named_args, span)
             }
@@ -451,15 +682,11 @@ impl<'a> TypeResolver<'a> {
             Expr::Index { expr, index, span } => {
                 let coll_ty = self.infer_expr(expr)?;
                 let idx_ty  = self.infer_expr(index)?;
-                if idx_ty != Type::Float {
-                    self.errors.push(Error::new(
-                        ErrorCode::S002, span.line, span.column,
-                        format!("index must be `float`, found `{}`", type_name(&idx_ty)),
-                    ));
-                }
+                self.check_index_type(&coll_ty, &idx_ty, span);
                 match coll_ty {
                     Type::List(elem)     => Ok(*elem),
                     Type::Array(elem, _) => Ok(*elem),
+                    Type::Map(elem)      => Ok(*elem),
                     other => Err(vec![Error::new(
                         ErrorCode::S008, span.line, span.column,
                         format!("cannot index into `{}`", type_name(&other)),
@@ -467,6 +694,21 @@ impl<'a> TypeResolver<'a> {
                 }
             }
 
+            // `Mode.Menu` — unless a variable named `Mode` shadows the enum.
+            Expr::Field { expr, field, span }
+                if let Expr::Ident(name, _) = &**expr
+                    && self.table.lookup(name).is_none()
+                    && let Some(variants) = self.table.enums.get(name) =>
+            {
+                if !variants.contains(field) {
+                    return Err(vec![Error::new(
+                        ErrorCode::S009, span.line, span.column,
+                        format!("enum `{name}` has no variant `{field}`"),
+                    )]);
+                }
+                Ok(Type::Named(name.clone()))
+            }
+
             Expr::Field { expr, field, span } => {
                 let obj_ty = self.infer_expr(expr)?;
                 let ty = self.lookup.resolve_field(&obj_ty, field);
@@ -476,8 +718,9 @@ impl<'a> TypeResolver<'a> {
                 )])
             }
 
-            Expr::MethodCall { expr, method, args, named_args: _, span } => {
+            Expr::MethodCall { expr, method, args, named_args, span } => {
                 let obj_ty = self.infer_expr(expr)?;
+                self.visit_named_args(named_args);
                 let ty = self.resolve_method_call(&obj_ty, method, args, span);
                 ty.ok_or_else(|| vec![Error::new(
                     ErrorCode::S009, span.line, span.column,
@@ -513,10 +756,13 @@ impl<'a> TypeResolver<'a> {
                     // The surrounding context (var decl annotation) should provide the type.
                     return Ok(Type::List(Box::new(Type::Float))); // lenient for now
                 }
-                let first_ty = self.infer_expr(&items[0])?;
+                let mut first_ty = self.infer_expr(&items[0])?;
                 for item in items.iter().skip(1) {
                     if let Ok(ty) = self.infer_expr(item) {
-                        if ty != first_ty {
+                        // Mixing `int` and `float` elements widens the list to `list[float]`.
+                        if is_numeric(&ty) && is_numeric(&first_ty) {
+                            if ty == Type::Float { first_ty = Type::Float; }
+                        } else if ty != first_ty {
                             self.errors.push(Error::new(
                                 ErrorCode::S002, span.line, span.column,
                                 format!(
@@ -530,6 +776,90 @@ impl<'a> TypeResolver<'a> {
                 Ok(Type::List(Box::new(first_ty)))
             }
 
+            Expr::StructLit { name, fields, span } => {
+                let Some(decl) = self.table.structs.get(name).cloned() else {
+                    return Err(vec![Error::new(
+                        ErrorCode::S001, span.line, span.column,
+                        format!("undefined struct `{name}`"),
+                    )]);
+                };
+                let mut seen: Vec<&str> = Vec::new();
+                for (field, value) in fields {
+                    if seen.contains(&field.as_str()) {
+                        self.errors.push(Error::new(
+                            ErrorCode::S007, value.span().line, value.span().column,
+                            format!("field `{field}` given twice"),
+                        ));
+                    }
+                    seen.push(field);
+                    match decl.iter().find(|(n, _)| n == field) {
+                        Some((_, ty)) => {
+                            if let Ok(actual) = self.infer_against(value, ty) {
+                                self.expect_type(ty, &actual, value.span());
+                            }
+                        }
+                        None => {
+                            let _ = self.infer_expr(value);
+                            self.errors.push(Error::new(
+                                ErrorCode::S009, value.span().line, value.span().column,
+                                format!("struct `{name}` has no field `{field}`"),
+                            ));
+                        }
+                    }
+                }
+                for (field, _) in &decl {
+                    if !seen.contains(&field.as_str()) {
+                        self.errors.push(Error::new(
+                            ErrorCode::S007, span.line, span.column,
+                            format!("missing field `{field}` in `{name}`"),
+                        ));
+                    }
+                }
+                Ok(Type::Named(name.clone()))
+            }
+
+            Expr::Map(entries, span) => {
+                let mut value_ty: Option<Type> = None;
+                for (key, value) in entries {
+                    self.check_map_key(key);
+                    let Ok(ty) = self.infer_expr(value) else { continue };
+                    value_ty = match value_ty {
+                        None => Some(ty),
+                        // Mixing `int` and `float` values widens the map to `map[float]`.
+                        Some(prev) if is_numeric(&prev) && is_numeric(&ty) => {
+                            Some(if ty == Type::Float { ty } else { prev })
+                        }
+                        Some(prev) => {
+                            if ty != prev {
+                                self.errors.push(Error::new(
+                                    ErrorCode::S002, span.line, span.column,
+                                    format!(
+                                        "map values must all have the same type, found `{}` and `{}`",
+                                        type_name(&prev), type_name(&ty)
+                                    ),
+                                ));
+                            }
+                            Some(prev)
+                        }
+                    };
+                }
+                // Empty map — same placeholder as an empty list; an annotation
+                // supplies the real type through `infer_against`.
+                Ok(Type::Map(Box::new(value_ty.unwrap_or(Type::Float))))
+            }
+
+            Expr::Tuple(items, _) => {
+                let mut tys = Vec::with_capacity(items.len());
+                let mut errs = Vec::new();
+                for item in items {
+                    match self.infer_expr(item) {
+                        Ok(t)  => tys.push(t),
+                        Err(e) => errs.extend(e),
+                    }
+                }
+                if errs.is_empty() { Ok(Type::Tuple(tys)) } else { Err(errs) }
+            }
+
             Expr::Lambda { params, return_ty, body, .. } => {
                 let param_types: Vec<Type> = params.iter().map(|p| p.ty.clone()).collect();
                 let ret = return_ty.clone().map(Box::new);
@@ -550,15 +880,87 @@ impl<'a> TypeResolver<'a> {
         }
     }
 
+    /// Infer `expr` where a value of type `expected` is wanted. An empty `[]`
+    /// or `{}` takes its element type from `expected` instead of defaulting.
+    /// List, map, tuple and `ok(..)` literals whose parts all fit `expected`
+    /// take its type, to any depth — `[[1, 2]]` checked against `list[list[float]]`
+    /// is a `list[list[float]]`, and the interpreter widens its ints.
+    fn infer_against(&mut self, expr: &Expr, expected: &Type) -> Result<Type, Vec<Error>> {
+        match (expr, expected) {
+            (Expr::List(items, _), Type::List(_)) if items.is_empty() => Ok(expected.clone()),
+            (Expr::Map(entries, _), Type::Map(_)) if entries.is_empty() => Ok(expected.clone()),
+            (Expr::List(items, _), Type::List(elem)) => {
+                let tys = self.infer_each_against(items, std::iter::repeat(&**elem))?;
+                Ok(match tys.into_iter().find(|t| !types_compatible(elem, t)) {
+                    Some(other) => Type::List(Box::new(other)),
+                    None => expected.clone(),
+                })
+            }
+            (Expr::Map(entries, _), Type::Map(elem)) => {
+                for (key, _) in entries { self.check_map_key(key); }
+                let values = entries.iter().map(|(_, v)| v);
+                let tys = self.infer_each_against(values, std::iter::repeat(&**elem))?;
+                Ok(match tys.into_iter().find(|t| !types_compatible(elem, t)) {
+                    Some(other) => Type::Map(Box::new(other)),
+                    None => expected.clone(),
+                })
+            }
+            (Expr::Tuple(items, _), Type::Tuple(elems)) if items.len() == elems.len() => {
+                let tys = self.infer_each_against(items, elems.iter())?;
+                let fits = elems.iter().zip(&tys).all(|(e, t)| types_compatible(e, t));
+                Ok(if fits { expected.clone() } else { Type::Tuple(tys) })
+            }
+            (Expr::Call { callee, args, named_args, .. }, Type::Res(inner))
+                if callee == "ok" && args.len() == 1 && named_args.is_empty() =>
+            {
+                let ty = self.infer_against(&args[0], inner)?;
+                Ok(if types_compatible(inner, &ty) { expected.clone() } else { Type::Res(Box::new(ty)) })
+            }
+            _ => self.infer_expr(expr),
+        }
+    }
+
+    fn check_map_key(&mut self, key: &Expr) {
+        if let Ok(key_ty) = self.infer_expr(key)
+            && key_ty != Type::Named("string".into())
+        {
+            self.errors.push(Error::new(
+                ErrorCode::S002, key.span().line, key.span().column,
+                format!("map keys must be `string`, found `{}`", type_name(&key_ty)),
+            ));
+        }
+    }
+
+    /// `infer_against` over literal parts, pairing each with its expected type.
+    fn infer_each_against<'e, 't>(
+        &mut self,
+        items: impl IntoIterator<Item = &'e Expr>,
+        expected: impl Iterator<Item = &'t Type>,
+    ) -> Result<Vec<Type>, Vec<Error>> {
+        let mut tys = Vec::new();
+        let mut errs = Vec::new();
+        for (item, ty) in items.into_iter().zip(expected) {
+            match self.infer_against(item, ty) {
+                Ok(t)  => tys.push(t),
+                Err(e) => errs.extend(e),
+            }
+        }
+        if errs.is_empty() { Ok(tys) } else { Err(errs) }
+    }
+
     // ── Call checking ─────────────────────────────────────────────────────────
 
     fn check_call(
         &mut self,
         callee: &str,
         args: &[Expr],
-        _named_args: &[(String, Expr)],
+        named_args: &[(String, Expr)],
         span: &Span,
     ) -> Result<Type, Vec<Error>> {
+        let user_fn = self.lookup.user_fn(callee);
+        if user_fn.is_none() {
+            self.visit_named_args(named_args);
+        }
         // Special-case generic built-ins before general lookup
         match callee {
             "ok" => {
@@ -570,6 +972,7 @@ impl<'a> TypeResolver<'a> {
                 return Ok(Type::Res(Box::new(inner)));
             }
             "error" => {
+                for arg in args { self.infer_expr(arg).ok(); }
                 return Ok(Type::Res(Box::new(Type::Float))); // placeholder
             }
             "len" => {
@@ -578,6 +981,28 @@ impl<'a> TypeResolver<'a> {
                 }
                 return Ok(Type::Float);
             }
+            // debug accepts a value of any type
+            "debug" => {
+                if args.len() != 1 {
+                    return Err(vec![Error::new(
+                        ErrorCode::S007, span.line, span.column,
+                        format!("`debug` expects 1 argument(s), got {}", args.len()),
+                    )]);
+                }
+                self.infer_expr(&args[0])?;
+                return Ok(Type::Named("void".into()));
+            }
+            // to_string accepts a value of any type
+            "to_string" => {
+                if args.len() != 1 {
+                    return Err(vec![Error::new(
+                        ErrorCode::S007, span.line, span.column,
+                        format!("`to_string` expects 1 argument(s), got {}", args.len()),
+                    )]);
+                }
+                self.infer_expr(&args[0])?;
+                return Ok(Type::Named("string".into()));
+            }
             // color is overloaded (3 or 4 float args)
             "color" => {
                 if args.len() != 3 && args.len() != 4 {
@@ -596,20 +1021,26 @@ impl<'a> TypeResolver<'a> {
 
         match fn_ty {
             Type::Fn(param_types, ret_ty) => {
-                // Check positional arg count
-                if args.len() != param_types.len() {
-                    return Err(vec![Error::new(
-                        ErrorCode::S007, span.line, span.column,
-                        format!(
-                            "`{callee}` expects {} argument(s), got {}",
-                            param_types.len(), args.len()
-                        ),
-                    )]);
-                }
+                // User functions bind named args and defaults; anything else is positional.
+                let bound = match user_fn {
+                    Some(f) => self.bind_user_args(f, args, named_args, span)?,
+                    None => {
+                        if args.len() != param_types.len() {
+                            return Err(vec![Error::new(
+                                ErrorCode::S007, span.line, span.column,
+                                format!(
+                                    "`{callee}` expects {} argument(s), got {}",
+                                    param_types.len(), args.len()
+                                ),
+                            )]);
+                        }
+                        args.iter().zip(param_types.iter().cloned()).collect()
+                    }
+                };
                 // Check each arg type
                 let mut has_arg_error = false;
-                for (arg, expected) in args.iter().zip(param_types.iter()) {
-                    match self.infer_expr(arg) {
+                for (arg, expected) in &bound {
+                    match self.infer_against(arg, expected) {
                         Ok(actual) => self.expect_type(expected, &actual, span),
                         Err(e) => {
                             self.errors.extend(e);
@@ -629,6 +1060,55 @@ impl<'a> TypeResolver<'a> {
         }
     }
 
+    /// Pair each argument of a call to user fn `f` with its parameter type:
+    /// positional args first, then named args by parameter name. Parameters
+    /// left unbound must have a default.
+    fn bind_user_args<'e>(
+        &mut self,
+        f: &FnDef,
+        args: &'e [Expr],
+        named_args: &'e [(String, Expr)],
+        span: &Span,
+    ) -> Result<Vec<(&'e Expr, Type)>, Vec<Error>> {
+        let err = |msg: String| Error::new(ErrorCode::S007, span.line, span.column, msg);
+        let required = f.params.iter().filter(|p| p.default.is_none()).count();
+        if args.len() > f.params.len() || (named_args.is_empty() && args.len() < required) {
+            let expected = if required == f.params.len() {
+                required.to_string()
+            } else {
+                format!("{required} to {}", f.params.len())
+            };
+            return Err(vec![err(format!("`{}` expects {expected} argument(s), got {}", f.name, args.len()))]);
+        }
+
+        let mut bound: Vec<(&Expr, Type)> = args.iter().zip(f.params.iter().map(|p| p.ty.clone())).collect();
+        let mut given = vec![false; f.params.len()];
+        given[..args.len()].fill(true);
+        let mut errors = Vec::new();
+        for (name, value) in named_args {
+            match f.params.iter().position(|p| &p.name == name) {
+                None => errors.push(err(format!("`{}` has no parameter named `{name}`", f.name))),
+                Some(i) if given[i] => errors.push(err(format!("argument `{name}` is given more than once"))),
+                Some(i) => {
+                    given[i] = true;
+                    bound.push((value, f.params[i].ty.clone()));
+                }
+            }
+        }
+        for (p, _) in f.params.iter().zip(&given).filter(|(p, given)| !**given && p.default.is_none()) {
+            errors.push(err(format!("missing argument `{}` in call to `{}`", p.name, f.name)));
+        }
+        if errors.is_empty() { Ok(bound) } else { Err(errors) }
+    }
+
+    /// Named args aren't type-checked, but inferring them records the symbols
+    /// they reference (`render: sdf` uses the imported `sdf`).
+    fn visit_named_args(&mut self, named_args: &[(String, Expr)]) {
+        for (_, value) in named_args {
+            self.infer_expr(value).ok();
+        }
+    }
+
     // ── Operator checking ─────────────────────────────────────────────────────
 
     fn check_binop(&mut self, op: &BinOp, l: &Type, r: &Type, span: &Span) -> Result<Type, Vec<Error>> {
@@ -646,13 +1126,13 @@ impl<'a> TypeResolver<'a> {
     fn check_unop(&mut self, op: &UnOp, operand: &Expr, ty: &Type, span: &Span) -> Result<Type, Vec<Error>> {
         match op {
             UnOp::Neg => {
-                if *ty != Type::Float {
+                if !is_numeric(ty) {
                     Err(vec![Error::new(
                         ErrorCode::S008, span.line, span.column,
-                        format!("unary `-` requires `float`, found `{}`", type_name(ty)),
+                        format!("unary `-` requires `int` or `float`, found `{}`", type_name(ty)),
                     )])
                 } else {
-                    Ok(Type::Float)
+                    Ok(ty.clone())
                 }
             }
             UnOp::Not => {
@@ -666,14 +1146,14 @@ impl<'a> TypeResolver<'a> {
                 }
             }
             UnOp::PrefixInc | UnOp::PrefixDec | UnOp::PostfixInc | UnOp::PostfixDec => {
-                if *ty != Type::Float {
+                if !is_numeric(ty) {
                     return Err(vec![Error::new(
                         ErrorCode::S008, span.line, span.column,
-                        format!("`++`/`--` require `float`, found `{}`", type_name(ty)),
+                        format!("`++`/`--` require `int` or `float`, found `{}`", type_name(ty)),
                     )]);
                 }
                 self.check_assignable_lvalue(operand, span)?;
-                Ok(Type::Float)
+                Ok(ty.clone())
             }
         }
     }
@@ -714,12 +1194,89 @@ impl<'a> TypeResolver<'a> {
         args: &[Expr],
         span: &Span,
     ) -> Option<Type> {
+        // list<T>.map(f): the element type of the result is f's return type.
+        if let (Type::List(elem), "map", [f]) = (obj_ty, method, args) {
+            if let Ok(f_ty) = self.infer_expr(f) {
+                match &f_ty {
+                    Type::Fn(params, Some(ret)) if params.len() == 1 => {
+                        self.expect_type(&params[0], elem, span);
+                        return Some(Type::List(ret.clone()));
+                    }
+                    _ => self.errors.push(Error::new(
+                        ErrorCode::S002, span.line, span.column,
+                        format!(
+                            "`map` expects `fn({}) -> U`, found `{}`",
+                            type_name(elem), type_name(&f_ty)
+                        ),
+                    )),
+                }
+            }
+            return Some(Type::List(elem.clone()));
+        }
+        // res<T>.map(f): the result wraps f's return type.
+        if let (Type::Res(inner), "map", [f]) = (obj_ty, method, args) {
+            if let Ok(f_ty) = self.infer_expr(f) {
+                match &f_ty {
+                    Type::Fn(params, Some(ret)) if params.len() == 1 => {
+                        self.expect_type(&params[0], inner, span);
+                        return Some(Type::Res(ret.clone()));
+                    }
+                    _ => self.errors.push(Error::new(
+                        ErrorCode::S002, span.line, span.column,
+                        format!(
+                            "`map` expects `fn({}) -> U`, found `{}`",
+                            type_name(inner), type_name(&f_ty)
+                        ),
+                    )),
+                }
+            }
+            return Some(Type::Res(inner.clone()));
+        }
+        // list<T>.reduce(init, f): the accumulator type comes from `init`.
+        if let (Type::List(elem), "reduce", [init, f]) = (obj_ty, method, args) {
+            let acc = self.infer_expr(init).ok()?;
+            if let Ok(f_ty) = self.infer_expr(f) {
+                let expected = Type::Fn(vec![acc.clone(), *elem.clone()], Some(Box::new(acc.clone())));
+                self.expect_type(&expected, &f_ty, span);
+            }
+            return Some(acc);
+        }
+
+        // ns.fn(args): namespace functions have a fixed arity, same as a bare
+        // call to an imported member.
+        if let Some(Type::Fn(param_types, ret_ty)) = self.lookup.namespace_member(obj_ty, method) {
+            if args.len() != param_types.len() {
+                self.errors.push(Error::new(
+                    ErrorCode::S007, span.line, span.column,
+                    format!(
+                        "`{}.{method}` expects {} argument(s), got {}",
+                        type_name(obj_ty), param_types.len(), args.len()
+                    ),
+                ));
+            }
+            for (arg, expected) in args.iter().zip(param_types.iter()) {
+                match self.infer_expr(arg) {
+                    Ok(actual) => self.expect_type(expected, &actual, span),
+                    Err(e) => self.errors.extend(e),
+                }
+            }
+            for arg in args.iter().skip(param_types.len()) {
+                self.infer_expr(arg).ok();
+            }
+            return Some(ret_ty.map(|t| *t).unwrap_or(Type::Unit));
+        }
+
         let member_ty = self.lookup.get_method_type(&obj_ty, method)?;
         if let Type::Fn(param_types, ret_ty) = &member_ty {
             if args.len() == param_types.len() {
                 for (arg, expected) in args.iter().zip(param_types.iter()) {
                     match self.infer_expr(arg) {
-                        Ok(actual) => self.expect_type(expected, &actual, span),
+                        Ok(actual) => {
+                            if *expected == Type::Float && actual == Type::Int {
+                                self.table.widened_exprs.insert(arg.span().clone(), Type::Float);
+                            }
+                            self.expect_type(expected, &actual, span)
+                        }
                         Err(e) => self.errors.extend(e),
                     }
                 }
@@ -743,6 +1300,7 @@ impl<'a> TypeResolver<'a> {
     }
 
     fn lookup_type(&mut self, name: &str, span: &Span) -> Result<Type, Vec<Error>> {
+        self.table.mark_used(name, self.current_fn_order);
         let sym = self.lookup_symbol(name, span);
         match sym {
             Some(s) => match &s.ty {
@@ -759,6 +1317,39 @@ impl<'a> TypeResolver<'a> {
         }
     }
 
+    /// Variants of `ty` when it names a user enum.
+    fn enum_variants(&self, ty: &Type) -> Option<&Vec<String>> {
+        let Type::Named(n) = ty else { return None };
+        self.table.enums.get(n)
+    }
+
+    /// W003 when a `match` over an enum has no `else` and leaves variants out.
+    fn check_enum_exhaustive<'v>(
+        &mut self,
+        scrut_ty: &Type,
+        arms: impl Iterator<Item = &'v [Expr]>,
+        span: &Span,
+    ) {
+        let Some(variants) = self.enum_variants(scrut_ty) else { return };
+        let mut covered = Vec::new();
+        for values in arms {
+            if values.is_empty() { return; } // `else`
+            for v in values {
+                if let Expr::Field { field, .. } = v { covered.push(field.as_str()); }
+            }
+        }
+        let missing: Vec<String> = variants.iter()
+            .filter(|v| !covered.contains(&v.as_str()))
+            .map(|v| format!("`{v}`"))
+            .collect();
+        if !missing.is_empty() {
+            self.errors.push(Error::new(
+                ErrorCode::W003, span.line, span.column,
+                format!("match on `{}` does not cover {}", type_name(scrut_ty), missing.join(", ")),
+            ));
+        }
+    }
+
     fn expect_type(&mut self, expected: &Type, actual: &Type, span: &Span) {
         if !types_compatible(expected, actual) {
             self.errors.push(Error::new(
@@ -774,21 +1365,55 @@ impl<'a> TypeResolver<'a> {
 /// True for types that support equality (usable in match).
 pub fn is_matchable(ty: &Type) -> bool {
     match ty {
-        Type::Float | Type::Bool => true,
+        Type::Int | Type::Float | Type::Bool => true,
         Type::Named(n) => matches!(n.as_str(), "string" | "vec2" | "vec3" | "vec4" | "color"),
         _ => false,
     }
 }
 
+/// True for `int` and `float`.
+pub fn is_numeric(ty: &Type) -> bool {
+    matches!(ty, Type::Int | Type::Float)
+}
+
+/// A match arm value may be compared against the scrutinee: the same type, or
+/// `int` against `float` either way round.
+fn match_compatible(scrut: &Type, val: &Type) -> bool {
+    types_compatible(scrut, val) || (is_numeric(scrut) && is_numeric(val))
+}
+
+/// Branches of a value-producing conditional (ternary, match) must agree on one type.
+/// `int` and `float` branches unify to `float`.
+fn unify_branches(what: &str, first: Type, other: &Type, span: &Span) -> Result<Type, Vec<Error>> {
+    if is_numeric(&first) && is_numeric(other) && &first != other {
+        return Ok(Type::Float);
+    }
+    if &first != other {
+        return Err(vec![Error::new(
+            ErrorCode::S002, span.line, span.column,
+            format!(
+                "{} branches have different types: `{}` and `{}`",
+                what, type_name(&first), type_name(other)
+            ),
+        )]);
+    }
+    Ok(first)
+}
+
 /// True for any type that can be pushed to `out <<` or used with `@`.
 pub fn is_drawable(ty: &Type) -> bool {
-    matches!(ty, Type::Named(n) if matches!(n.as_str(), "shape" | "circle" | "rect" | "line" | "polygon"))
+    matches!(ty, Type::Named(n) if matches!(n.as_str(), "shape" | "circle" | "rect" | "line" | "polygon" | "wedge"))
 }
 
 /// True if `actual` is compatible where `expected` is required.
-/// Adds coercion: any concrete shape kind is assignable to `shape`.
+/// Adds coercions: `int` is assignable to `float`, and any concrete shape kind
+/// is assignable to `shape`.
 pub fn types_compatible(expected: &Type, actual: &Type) -> bool {
     if expected == actual { return true; }
+    if expected == &Type::Float && actual == &Type::Int { return true; }
+    if let (Type::Tuple(es), Type::Tuple(acts)) = (expected, actual) {
+        return es.len() == acts.len() && es.iter().zip(acts).all(|(e, a)| types_compatible(e, a));
+    }
     // Concrete shape kind → erased shape
     if expected == &Type::Named("shape".into()) && is_drawable(actual) { return true; }
     false
@@ -798,11 +1423,14 @@ pub fn types_compatible(expected: &Type, actual: &Type) -> bool {
 
 pub fn type_name(ty: &Type) -> String {
     match ty {
+        Type::Int             => "int".into(),
         Type::Float           => "float".into(),
         Type::Bool            => "bool".into(),
         Type::Unit            => "()".into(),
         Type::Array(t, n)     => format!("array[{}, {n}]", type_name(t)),
         Type::List(t)         => format!("list[{}]", type_name(t)),
+        Type::Map(t)          => format!("map[{}]", type_name(t)),
+        Type::Tuple(ts)       => format!("({})", ts.iter().map(type_name).collect::<Vec<_>>().join(", ")),
         Type::Res(t)          => format!("res<{}>", type_name(t)),
         Type::Fn(ps, Some(r)) => format!("fn({}) -> {}", ps.iter().map(type_name).collect::<Vec<_>>().join(", "), type_name(r)),
         Type::Fn(ps, None)    => format!("fn({})", ps.iter().map(type_name).collect::<Vec<_>>().join(", ")),
@@ -6,10 +6,12 @@
 //! - Records function signatures (skips bodies)
 //! - Records top-level variable declarations with declaration order
 //! - Records `state {}` field names
+//! - Records `type Name = T` aliases, `struct` and `enum` declarations
 
 use crate::syntax::ast::*;
 use crate::error::{Error, ErrorCode};
 use crate::namespaces::{NamespaceRegistry, core::core_exports};
+use crate::types::registry::TypeRegistry;
 use super::symbols::{Symbol, SymbolKind, SymbolTable};
 
 pub struct Collector<'a> {
@@ -44,6 +46,9 @@ impl<'a> Collector<'a> {
         for item in &program.items {
             match item {
                 Item::FnDef(f)  => self.collect_fn_sig(f),
+                Item::TypeAlias(a) => self.collect_type_alias(a),
+                Item::StructDef(s) => self.collect_struct(s),
+                Item::EnumDef(e)   => self.collect_enum(e),
                 Item::Stmt(s)   => self.collect_top_stmt(s),
             }
         }
@@ -63,26 +68,29 @@ impl<'a> Collector<'a> {
             return;
         };
 
-        if import.members.is_empty() {
-            // `import render` — add the namespace itself as a named symbol
+        if import.is_whole_namespace() {
+            // `import render` / `import render as r` — add the namespace itself as a named symbol
             let sym = Symbol::new(
-                import.namespace.clone(),
+                import.local_name().to_string(),
                 Some(Type::Named(import.namespace.clone())),
                 SymbolKind::Variable,
                 import.span.clone(),
             );
             self.table.declare_top_level(sym);
         } else {
-            // `import shapes { circle, rect }`
-            for member in &import.members {
-                match ns.get_export(member) {
+            // `import shapes { circle, rect as r }` / `import shapes { * }`
+            for member in &ns.import_members(import) {
+                let local = member.local_name();
+                match ns.get_export(&member.name) {
+                    // Core exports are pre-seeded; importing one under its own name is a no-op.
+                    Some(_) if import.namespace == "core" && member.alias.is_none() => {}
                     Some(export) => {
                         let kind = match export.kind {
                             crate::namespaces::ExportKind::Function => SymbolKind::Function,
                             crate::namespaces::ExportKind::Constant => SymbolKind::Variable,
                         };
                         let sym = Symbol::new(
-                            export.name,
+                            local.to_string(),
                             Some(export.ty),
                             kind,
                             import.span.clone(),
@@ -91,7 +99,7 @@ impl<'a> Collector<'a> {
                             self.errors.push(Error::new(
                                 ErrorCode::S003,
                                 import.span.line, import.span.column,
-                                format!("`{member}` already declared"),
+                                format!("`{local}` already declared"),
                             ));
                         }
                     }
@@ -99,7 +107,7 @@ impl<'a> Collector<'a> {
                         self.errors.push(Error::new(
                             ErrorCode::S006,
                             import.span.line, import.span.column,
-                            format!("`{}` does not export `{member}`", import.namespace),
+                            format!("`{}` does not export `{}`", import.namespace, member.name),
                         ));
                     }
                 }
@@ -138,6 +146,74 @@ impl<'a> Collector<'a> {
         }
     }
 
+    // ── Type aliases ──────────────────────────────────────────────────────────
+
+    fn collect_type_alias(&mut self, a: &TypeAlias) {
+        if let Some(msg) = self.type_name_taken("type alias", &a.name) {
+            self.errors.push(Error::new(ErrorCode::S003, a.span.line, a.span.column, msg));
+            return;
+        }
+        self.table.type_aliases.insert(a.name.clone(), a.ty.clone());
+    }
+
+    // ── Structs ───────────────────────────────────────────────────────────────
+
+    fn collect_struct(&mut self, s: &StructDef) {
+        if let Some(msg) = self.type_name_taken("struct", &s.name) {
+            self.errors.push(Error::new(ErrorCode::S003, s.span.line, s.span.column, msg));
+            return;
+        }
+        let mut fields: Vec<(String, Type)> = Vec::new();
+        for f in &s.fields {
+            if fields.iter().any(|(n, _)| *n == f.name) {
+                self.errors.push(Error::new(
+                    ErrorCode::S003,
+                    f.span.line, f.span.column,
+                    format!("field `{}` already declared in `{}`", f.name, s.name),
+                ));
+                continue;
+            }
+            fields.push((f.name.clone(), f.ty.clone()));
+        }
+        self.table.structs.insert(s.name.clone(), fields);
+    }
+
+    // ── Enums ─────────────────────────────────────────────────────────────────
+
+    fn collect_enum(&mut self, e: &EnumDef) {
+        if let Some(msg) = self.type_name_taken("enum", &e.name) {
+            self.errors.push(Error::new(ErrorCode::S003, e.span.line, e.span.column, msg));
+            return;
+        }
+        let mut variants: Vec<String> = Vec::new();
+        for v in &e.variants {
+            if variants.contains(v) {
+                self.errors.push(Error::new(
+                    ErrorCode::S003,
+                    e.span.line, e.span.column,
+                    format!("variant `{v}` already declared in `{}`", e.name),
+                ));
+                continue;
+            }
+            variants.push(v.clone());
+        }
+        self.table.enums.insert(e.name.clone(), variants);
+    }
+
+    /// Why `name` can't be declared as a new type, if it can't.
+    fn type_name_taken(&self, what: &str, name: &str) -> Option<String> {
+        if matches!(name, "State" | "void") || TypeRegistry::default().contains(name) {
+            Some(format!("{what} `{name}` shadows a built-in type"))
+        } else if self.table.type_aliases.contains_key(name)
+            || self.table.structs.contains_key(name)
+            || self.table.enums.contains_key(name)
+        {
+            Some(format!("{what} `{name}` already declared"))
+        } else {
+            None
+        }
+    }
+
     // ── Top-level statements ──────────────────────────────────────────────────
 
     fn collect_top_stmt(&mut self, stmt: &Stmt) {
@@ -156,6 +232,20 @@ impl<'a> Collector<'a> {
                     ));
                 }
             }
+            Stmt::Destructure(d) => {
+                // Element types are resolved in pass 2, once the initializer is known.
+                let kind = if d.is_const { SymbolKind::Const } else { SymbolKind::Variable };
+                for name in &d.names {
+                    let sym = Symbol::new(name.clone(), None, kind.clone(), d.span.clone());
+                    if !self.table.declare_top_level(sym) {
+                        self.errors.push(Error::new(
+                            ErrorCode::S003,
+                            d.span.line, d.span.column,
+                            format!("`{name}` already declared"),
+                        ));
+                    }
+                }
+            }
             Stmt::FnVar { name, span, .. } => {
                 // `fn f = expr` — type resolved in pass 2
                 let sym = Symbol::new(name.clone(), None, SymbolKind::Function, span.clone());
@@ -179,6 +269,7 @@ impl<'a> Collector<'a> {
 /// inference pass. Returns `None` for complex expressions.
 pub fn infer_literal_type(expr: &Expr) -> Option<Type> {
     match expr {
+        Expr::Int(_, _)       => Some(Type::Int),
         Expr::Float(_, _)     => Some(Type::Float),
         Expr::Bool(_, _)      => Some(Type::Bool),
         Expr::StringLit(_, _) => Some(Type::Named("string".into())),
@@ -189,6 +280,12 @@ pub fn infer_literal_type(expr: &Expr) -> Option<Type> {
                 .and_then(|e| infer_literal_type(e))
                 .map(|elem_ty| Type::List(Box::new(elem_ty)))
         }
+        Expr::StructLit { name, .. } => Some(Type::Named(name.clone())),
+        Expr::Map(entries, _) => {
+            entries.first()
+                .and_then(|(_, v)| infer_literal_type(v))
+                .map(|value_ty| Type::Map(Box::new(value_ty)))
+        }
         _ => None,
     }
 }
@@ -24,15 +24,33 @@ impl<'a> LookupContext<'a> {
         Self { program, registry, type_registry: TypeRegistry::default() }
     }
 
+    /// Make a user `struct` visible to field resolution.
+    pub fn register_struct(&mut self, name: &str, fields: Vec<(String, Type)>) {
+        self.type_registry.register_struct(name, fields);
+    }
+
+    /// Type of `member` when `obj_ty` is an imported namespace — the `Export`
+    /// signature, e.g. `fn(vec2, float) -> circle` for `shapes.circle`.
+    pub fn namespace_member(&self, obj_ty: &Type, member: &str) -> Option<Type> {
+        let Type::Named(n) = obj_ty else { return None };
+        self.registry.get(n)?.get_export(member).map(|e| e.ty)
+    }
+
+    /// The top-level `fn name` definition, if `name` is a user function.
+    pub fn user_fn(&self, name: &str) -> Option<&'a FnDef> {
+        self.program?.items.iter().find_map(|item| match item {
+            Item::FnDef(f) if f.name == name => Some(f),
+            _ => None,
+        })
+    }
+
     /// Resolve the type of `obj.field`.
     pub fn resolve_field(&self, obj_ty: &Type, field: &str) -> Option<Type> {
         // 1. Namespace member lookup.
+        if let Some(ty) = self.namespace_member(obj_ty, field) {
+            return Some(ty);
+        }
         if let Type::Named(n) = obj_ty {
-            if let Some(ns) = self.registry.get(n) {
-                if let Some(export) = ns.get_export(field) {
-                    return Some(export.ty);
-                }
-            }
             // State fields are dynamic — look them up from the parsed program.
             if n == "State" {
                 if let Some(program) = self.program {
@@ -53,12 +71,8 @@ impl<'a> LookupContext<'a> {
     /// Returns `Type::Fn(params, ret)` so the checker can validate arg types.
     pub fn get_method_type(&self, obj_ty: &Type, method: &str) -> Option<Type> {
         // 1. Namespace member lookup.
-        if let Type::Named(n) = obj_ty {
-            if let Some(ns) = self.registry.get(n) {
-                if let Some(export) = ns.get_export(method) {
-                    return Some(export.ty);
-                }
-            }
+        if let Some(ty) = self.namespace_member(obj_ty, method) {
+            return Some(ty);
         }
         // 2. TypeRegistry — handles all built-in types including generics.
         let (params, ret) = self.type_registry.resolve_method_signature(obj_ty, method)?;
@@ -23,11 +23,14 @@ pub struct Symbol {
     /// Used to enforce the strict ordering rule inside function bodies.
     /// 0 for non-top-level symbols (params, local vars).
     pub declaration_order: usize,
+    /// Set by TypeResolver when the symbol is read. Unread `let`s and params
+    /// are reported as W001, unreferenced imports as W002, by the Validator.
+    pub used: bool,
 }
 
 impl Symbol {
     pub fn new(name: impl Into<String>, ty: Option<Type>, kind: SymbolKind, span: Span) -> Self {
-        Self { name: name.into(), ty, kind, span, declaration_order: 0 }
+        Self { name: name.into(), ty, kind, span, declaration_order: 0, used: false }
     }
 }
 
@@ -73,11 +76,26 @@ impl Scope {
 pub struct SymbolTable {
     pub scopes: Vec<Scope>,
     top_level_counter: usize,
+    /// Symbols from scopes already popped — kept for the unused-variable check.
+    retired: Vec<Symbol>,
+    /// `type Name = T` aliases, by name. The parser has already substituted
+    /// them, so this is only consulted for declaration checks.
+    pub type_aliases: HashMap<String, Type>,
+    /// `struct Name { ... }` declarations — field names and types, in order.
+    pub structs: HashMap<String, Vec<(String, Type)>>,
+    /// `enum Name { ... }` declarations — variant names, in order.
+    pub enums: HashMap<String, Vec<String>>,
+    /// Expressions whose value may hold an `int` where the checker settled on
+    /// `float`, with the type to widen it to — method arguments to a `float`
+    /// parameter (`xs.push(1)` on a `list[float]`), ternaries and matches
+    /// mixing `int` and `float` arms, and assigned values (`m["b"] = 4` on a
+    /// `map[float]`). The interpreter widens them.
+    pub widened_exprs: HashMap<Span, Type>,
 }
 
 impl SymbolTable {
     pub fn new() -> Self {
-        Self { scopes: vec![Scope::new(ScopeKind::Global)], top_level_counter: 0 }
+        Self { scopes: vec![Scope::new(ScopeKind::Global)], top_level_counter: 0, retired: Vec::new(), type_aliases: HashMap::new(), structs: HashMap::new(), enums: HashMap::new(), widened_exprs: HashMap::new() }
     }
 
     pub fn push_scope(&mut self, kind: ScopeKind) {
@@ -85,8 +103,10 @@ impl SymbolTable {
     }
 
     pub fn pop_scope(&mut self) {
-        if self.scopes.len() > 1 {
-            self.scopes.pop();
+        if self.scopes.len() > 1
+            && let Some(scope) = self.scopes.pop()
+        {
+            self.retired.extend(scope.symbols.into_values());
         }
     }
 
@@ -110,12 +130,8 @@ impl SymbolTable {
 
     /// Normal lookup: innermost scope to outermost, no ordering constraint.
     pub fn lookup(&self, name: &str) -> Option<&Symbol> {
-        for scope in self.scopes.iter().rev() {
-            if let Some(sym) = scope.get(name) {
-                return Some(sym);
-            }
-        }
-        None
+        let i = self.resolve_scope(name, None)?;
+        self.scopes[i].get(name)
     }
 
     /// Strict lookup used inside function bodies.
@@ -123,24 +139,39 @@ impl SymbolTable {
     /// - Global scope: functions always visible; variables/consts only if
     ///   declared before `fn_order` (strict top-level ordering).
     pub fn lookup_strict(&self, name: &str, fn_order: usize) -> Option<&Symbol> {
-        // Search non-global scopes from innermost outward
-        for scope in self.scopes.iter().rev() {
-            if scope.kind == ScopeKind::Global {
+        let i = self.resolve_scope(name, Some(fn_order))?;
+        self.scopes[i].get(name)
+    }
+
+    /// Mark the symbol `name` resolves to as read — `lookup` rules when
+    /// `fn_order` is `None`, `lookup_strict` rules otherwise.
+    pub fn mark_used(&mut self, name: &str, fn_order: Option<usize>) {
+        if let Some(i) = self.resolve_scope(name, fn_order)
+            && let Some(sym) = self.scopes[i].get_mut(name)
+        {
+            sym.used = true;
+        }
+    }
+
+    /// Index of the scope holding the symbol `name` resolves to.
+    fn resolve_scope(&self, name: &str, fn_order: Option<usize>) -> Option<usize> {
+        // Search from innermost outward — strict lookup stops at the global scope
+        for (i, scope) in self.scopes.iter().enumerate().rev() {
+            if fn_order.is_some() && scope.kind == ScopeKind::Global {
                 break;
             }
-            if let Some(sym) = scope.get(name) {
-                return Some(sym);
+            if scope.get(name).is_some() {
+                return Some(i);
             }
         }
+        let order = fn_order?;
         // Check global scope with ordering constraint
-        if let Some(sym) = self.scopes[0].get(name) {
-            match sym.kind {
-                SymbolKind::Function => return Some(sym), // always visible
-                _ if sym.declaration_order < fn_order => return Some(sym),
-                _ => return None,
-            }
+        let sym = self.scopes[0].get(name)?;
+        match sym.kind {
+            SymbolKind::Function => Some(0), // always visible
+            _ if sym.declaration_order < order => Some(0),
+            _ => None,
         }
-        None
     }
 
     /// Update the resolved type of a symbol anywhere in the table.
@@ -165,4 +196,9 @@ impl SymbolTable {
         syms.sort_by_key(|s| s.declaration_order);
         syms
     }
+
+    /// Every symbol declared so far, including those of popped scopes.
+    pub fn all_symbols(&self) -> impl Iterator<Item = &Symbol> {
+        self.scopes.iter().flat_map(|s| s.symbols.values()).chain(&self.retired)
+    }
 }
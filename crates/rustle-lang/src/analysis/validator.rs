@@ -4,10 +4,12 @@
 //! - `const` never reassigned (cross-check against symbol table)
 //! - `state {}` appears at most once (caught by parser, double-checked here)
 //! - `on_update`, `on_init`, `on_exit` have correct signatures if defined
+//! - unread `let` variables and params (W001 warning, suppressed by a leading `_`)
+//! - imported members and namespaces never referenced (W002 warning)
 
 use crate::syntax::ast::*;
 use crate::error::{Error, ErrorCode};
-use super::symbols::SymbolTable;
+use super::symbols::{SymbolKind, SymbolTable};
 
 pub struct Validator<'a> {
     table: &'a SymbolTable,
@@ -24,6 +26,7 @@ impl<'a> Validator<'a> {
         self.check_on_init_signature(program);
         self.check_on_exit_signature(program);
         self.check_const_reassignment(program);
+        self.check_unused(program);
         self.errors
     }
 
@@ -79,12 +82,51 @@ impl<'a> Validator<'a> {
         }
     }
 
+    // ── unused variables and imports ──────────────────────────────────────────
+
+    fn check_unused(&mut self, program: &Program) {
+        let is_import = |span: &Span| program.imports.iter().any(|i| i.span == *span);
+        let mut unused: Vec<_> = self.table.all_symbols()
+            .filter(|s| matches!(s.kind, SymbolKind::Variable | SymbolKind::Param))
+            .filter(|s| !s.used && !s.name.starts_with('_') && !is_import(&s.span))
+            .map(|s| Error::new(
+                ErrorCode::W001, s.span.line, s.span.column,
+                format!("unused {} `{}` — prefix with `_` to silence",
+                    if s.kind == SymbolKind::Param { "parameter" } else { "variable" }, s.name),
+            ))
+            .collect();
+
+        // `{ * }` imports are exempt; core members re-imported under their own
+        // name aren't declared by the import, so the span check skips them.
+        for import in program.imports.iter().filter(|i| !i.wildcard) {
+            let names: Vec<&str> = if import.is_whole_namespace() {
+                vec![import.local_name()]
+            } else {
+                import.members.iter().map(|m| m.local_name()).collect()
+            };
+            for name in names {
+                if let Some(sym) = self.table.lookup(name)
+                    && sym.span == import.span
+                    && !sym.used
+                {
+                    unused.push(Error::new(
+                        ErrorCode::W002, import.span.line, import.span.column,
+                        format!("unused import `{name}`"),
+                    ));
+                }
+            }
+        }
+        unused.sort_by_key(|e| (e.line, e.column));
+        self.errors.extend(unused);
+    }
+
     // ── const reassignment ────────────────────────────────────────────────────
 
     fn check_const_reassignment(&mut self, program: &Program) {
         for item in &program.items {
             match item {
                 Item::FnDef(f) => self.scan_stmts_for_const_assign(&f.body),
+                Item::TypeAlias(_) | Item::StructDef(_) | Item::EnumDef(_) => {}
                 Item::Stmt(s)  => self.scan_stmt_for_const_assign(s),
             }
         }
@@ -99,7 +141,7 @@ impl<'a> Validator<'a> {
             Stmt::Assign(a) => {
                 let root = &a.target.path()[0];
                 if let Some(sym) = self.table.lookup(root) {
-                    if sym.kind == super::symbols::SymbolKind::Const {
+                    if sym.kind == SymbolKind::Const {
                         self.errors.push(Error::new(
                             ErrorCode::S004,
                             a.span.line, a.span.column,
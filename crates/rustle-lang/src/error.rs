@@ -1,10 +1,12 @@
 /// Error codes prefixed by phase: L = lexer, P = parser, S = semantic.
+/// W codes are warnings — reported, but they don't stop compilation.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ErrorCode {
     // Lexer
     L001, // unexpected character
     L002, // unterminated string literal
     L003, // invalid escape sequence
+    L004, // unterminated block comment
 
     // Parser
     P001, // unexpected token
@@ -23,18 +25,23 @@ pub enum ErrorCode {
     S010, // not callable
     S011, // duplicate state block
     S012, // invalid update function signature
+
+    // Warnings
+    W001, // unused variable or parameter
+    W002, // unused import
+    W003, // match over an enum misses variants
 }
 
 impl ErrorCode {
-    /// All current codes are hard errors (not warnings).
-    /// Extend this when warning codes are added.
-    pub fn is_error(&self) -> bool { true }
+    /// `false` for warning codes (W…).
+    pub fn is_error(&self) -> bool { !matches!(self, Self::W001 | Self::W002 | Self::W003) }
 
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::L001 => "L001",
             Self::L002 => "L002",
             Self::L003 => "L003",
+            Self::L004 => "L004",
             Self::P001 => "P001",
             Self::P002 => "P002",
             Self::S001 => "S001",
@@ -49,6 +56,9 @@ impl ErrorCode {
             Self::S010 => "S010",
             Self::S011 => "S011",
             Self::S012 => "S012",
+            Self::W001 => "W001",
+            Self::W002 => "W002",
+            Self::W003 => "W003",
         }
     }
 }
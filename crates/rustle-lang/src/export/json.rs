@@ -0,0 +1,102 @@
+//! Hand-rolled JSON writer for draw commands — no serde dependency.
+//!
+//! Output shape:
+//! ```text
+//! [{"type":"circle","center":[0,0],"radius":0.5,"render_mode":"sdf",
+//!   "fill":null,"stroke_color":null,"layer":0,"alpha":1,"transforms":[...],
+//!   "coord_meta":{"px_width":0,"px_height":0,"origin":"center","normalized":false,"y_down":false}}]
+//! ```
+
+use std::fmt::Write;
+
+use crate::types::draw::{Color, CoordMeta, DrawCommand, RenderMode, ShapeData, ShapeDesc, TransformData};
+
+/// Serialize a frame's draw commands to a JSON array, one object per shape.
+pub fn to_json(cmds: &[DrawCommand]) -> String {
+    let items: Vec<String> = cmds.iter()
+        .map(|cmd| match cmd {
+            DrawCommand::DrawShape(data) => shape_json(data),
+        })
+        .collect();
+    format!("[{}]", items.join(","))
+}
+
+fn shape_json(data: &ShapeData) -> String {
+    let mut out = String::from("{");
+    match &data.desc {
+        ShapeDesc::Circle { center, radius } => {
+            let _ = write!(out, r#""type":"circle","center":{},"radius":{}"#, point(*center), num(*radius));
+        }
+        ShapeDesc::Rect { center, size, origin } => {
+            let _ = write!(out, r#""type":"rect","center":{},"size":{},"origin":"{}""#,
+                point(*center), point(*size), origin.name());
+        }
+        ShapeDesc::Line { from, to } => {
+            let _ = write!(out, r#""type":"line","from":{},"to":{}"#, point(*from), point(*to));
+        }
+        ShapeDesc::Polygon(pts) => {
+            let pts: Vec<String> = pts.iter().map(|p| point(*p)).collect();
+            let _ = write!(out, r#""type":"polygon","points":[{}]"#, pts.join(","));
+        }
+        ShapeDesc::Wedge { center, radius, start_angle, end_angle } => {
+            let _ = write!(out, r#""type":"wedge","center":{},"radius":{},"start_angle":{},"end_angle":{}"#,
+                point(*center), num(*radius), num(*start_angle), num(*end_angle));
+        }
+    }
+    let transforms: Vec<String> = data.transforms.iter().map(transform_json).collect();
+    let _ = write!(
+        out,
+        r#","render_mode":{},"fill":{},"stroke_color":{},"layer":{},"alpha":{},"transforms":[{}],"coord_meta":{}}}"#,
+        render_mode_json(&data.render_mode),
+        opt_color(data.fill),
+        opt_color(data.stroke_color),
+        num(data.layer),
+        num(data.alpha),
+        transforms.join(","),
+        coord_meta_json(&data.coord_meta),
+    );
+    out
+}
+
+fn transform_json(td: &TransformData) -> String {
+    let pivot = td.pivot.map_or("null".to_string(), point);
+    format!(
+        r#"{{"tx":{},"ty":{},"sx":{},"sy":{},"shx":{},"shy":{},"angle":{},"pivot":{}}}"#,
+        num(td.tx), num(td.ty), num(td.sx), num(td.sy),
+        num(td.shx), num(td.shy), num(td.angle), pivot,
+    )
+}
+
+fn render_mode_json(mode: &RenderMode) -> String {
+    match mode {
+        RenderMode::Sdf       => r#""sdf""#.into(),
+        RenderMode::Fill      => r#""fill""#.into(),
+        RenderMode::Outline   => r#""outline""#.into(),
+        RenderMode::Stroke(w) => format!(r#"{{"stroke":{}}}"#, num(*w)),
+        RenderMode::Dashed { width, on, off } => format!(
+            r#"{{"dashed":{{"width":{},"on":{},"off":{}}}}}"#, num(*width), num(*on), num(*off),
+        ),
+    }
+}
+
+fn coord_meta_json(m: &CoordMeta) -> String {
+    format!(
+        r#"{{"px_width":{},"px_height":{},"origin":"{}","normalized":{},"y_down":{}}}"#,
+        num(m.px_width), num(m.px_height), m.origin.name(), m.normalized, m.is_y_down(),
+    )
+}
+
+fn opt_color(c: Option<Color>) -> String {
+    c.map_or("null".to_string(), |c| {
+        format!("[{},{},{},{}]", num(c.r), num(c.g), num(c.b), num(c.a))
+    })
+}
+
+fn point((x, y): (f64, f64)) -> String {
+    format!("[{},{}]", num(x), num(y))
+}
+
+/// JSON has no NaN or infinity — those become `null`.
+fn num(v: f64) -> String {
+    if v.is_finite() { v.to_string() } else { "null".into() }
+}
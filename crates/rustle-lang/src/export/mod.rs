@@ -0,0 +1,5 @@
+//! Serializers for draw output (JSON, SVG). Pure functions over `DrawCommand`s,
+//! usable from the app or headless.
+
+pub mod json;
+pub mod svg;
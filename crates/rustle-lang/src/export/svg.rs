@@ -0,0 +1,140 @@
+//! SVG export. Each shape becomes one element in a `width × height` document.
+//!
+//! Positions go through `CoordMeta`'s screen-pixel mapping and are scaled to
+//! the requested size; a sketch without `resolution()` is NDC and fills the
+//! page. Untransformed circles and rects keep their native elements; anything
+//! with transforms is written as its transformed outline.
+
+use std::fmt::Write;
+
+use crate::types::draw::{Color, CoordMeta, DrawCommand, RenderMode, ShapeData, ShapeDesc};
+use crate::types::registry::transform_vertices;
+
+/// Stroke width for `outline` and lines without an explicit `stroke(w)`.
+const DEFAULT_STROKE_WIDTH: f64 = 1.5;
+
+const BLACK: Color = Color { r: 0.0, g: 0.0, b: 0.0, a: 1.0 };
+
+/// Serialize a frame's draw commands to an SVG document.
+pub fn export_svg(cmds: &[DrawCommand], width: u32, height: u32) -> String {
+    let mut out = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#,
+    );
+    out.push('\n');
+    let page = Page { w: width as f64, h: height as f64 };
+    for cmd in cmds {
+        let DrawCommand::DrawShape(data) = cmd;
+        if let Some(el) = shape_svg(data, &page) {
+            let _ = writeln!(out, "  {el}");
+        }
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+/// Export document size, in pixels.
+struct Page { w: f64, h: f64 }
+
+impl Page {
+    /// Canvas pixels scale onto the page; a canvas axis with no resolution is
+    /// NDC and spans the whole page.
+    fn point(&self, m: &CoordMeta, (x, y): (f64, f64)) -> (f64, f64) {
+        let px = if m.px_width > 0.0 {
+            m.x_to_screen_px(x) * self.w / m.px_width
+        } else {
+            (x + 1.0) / 2.0 * self.w
+        };
+        let py = if m.px_height > 0.0 {
+            m.y_to_screen_px(y) * self.h / m.px_height
+        } else {
+            (1.0 - y) / 2.0 * self.h
+        };
+        (px, py)
+    }
+
+    fn width(&self, m: &CoordMeta, w: f64) -> f64 {
+        if m.px_width > 0.0 { w * m.unit_px() * self.w / m.px_width } else { w / 2.0 * self.w }
+    }
+
+    fn height(&self, m: &CoordMeta, h: f64) -> f64 {
+        if m.px_height > 0.0 { h * m.unit_px() * self.h / m.px_height } else { h / 2.0 * self.h }
+    }
+}
+
+fn shape_svg(data: &ShapeData, page: &Page) -> Option<String> {
+    let m = &data.coord_meta;
+    let paint = paint_attrs(data);
+
+    if data.transforms.is_empty()
+        && let ShapeDesc::Circle { center, radius } = &data.desc
+    {
+        let (cx, cy) = page.point(m, *center);
+        let (rx, ry) = (page.width(m, *radius), page.height(m, *radius));
+        return Some(if rx == ry {
+            format!(r#"<circle cx="{}" cy="{}" r="{}" {paint}/>"#, num(cx), num(cy), num(rx))
+        } else {
+            format!(r#"<ellipse cx="{}" cy="{}" rx="{}" ry="{}" {paint}/>"#,
+                num(cx), num(cy), num(rx), num(ry))
+        });
+    }
+
+    let (verts, _) = transform_vertices(data);
+    let pts: Vec<(f64, f64)> = verts.into_iter().map(|p| page.point(m, p)).collect();
+    match &data.desc {
+        ShapeDesc::Line { .. } => {
+            let [(x1, y1), (x2, y2)] = pts[..] else { return None };
+            Some(format!(r#"<line x1="{}" y1="{}" x2="{}" y2="{}" {paint}/>"#,
+                num(x1), num(y1), num(x2), num(y2)))
+        }
+        ShapeDesc::Rect { .. } if data.transforms.is_empty() => {
+            let (min_x, max_x) = min_max(pts.iter().map(|p| p.0));
+            let (min_y, max_y) = min_max(pts.iter().map(|p| p.1));
+            Some(format!(r#"<rect x="{}" y="{}" width="{}" height="{}" {paint}/>"#,
+                num(min_x), num(min_y), num(max_x - min_x), num(max_y - min_y)))
+        }
+        _ => {
+            if pts.is_empty() { return None; }
+            let pts: Vec<String> = pts.iter().map(|(x, y)| format!("{},{}", num(*x), num(*y))).collect();
+            Some(format!(r#"<polygon points="{}" {paint}/>"#, pts.join(" ")))
+        }
+    }
+}
+
+/// `fill` / `stroke` attributes. Lines are always stroked; other shapes are
+/// filled under `sdf` and `fill`, stroked under `outline`, `stroke(w)` and
+/// `dashed(w, on, off)`.
+fn paint_attrs(data: &ShapeData) -> String {
+    let is_line = matches!(data.desc, ShapeDesc::Line { .. });
+    let stroke_width = match data.render_mode {
+        RenderMode::Stroke(w) | RenderMode::Dashed { width: w, .. } => w,
+        _ => DEFAULT_STROKE_WIDTH,
+    };
+    let stroked = is_line
+        || matches!(data.render_mode, RenderMode::Outline | RenderMode::Stroke(_) | RenderMode::Dashed { .. });
+    if stroked {
+        let c = data.stroke_color.unwrap_or(BLACK);
+        let dash = match data.render_mode {
+            RenderMode::Dashed { on, off, .. } => format!(r#" stroke-dasharray="{} {}""#, num(on), num(off)),
+            _ => String::new(),
+        };
+        format!(r#"fill="none" stroke="{}" stroke-opacity="{}" stroke-width="{}"{}"#,
+            rgb(c), num(c.a * data.alpha), num(stroke_width), dash)
+    } else {
+        let c = data.fill.unwrap_or(BLACK);
+        format!(r#"fill="{}" fill-opacity="{}""#, rgb(c), num(c.a * data.alpha))
+    }
+}
+
+fn rgb(c: Color) -> String {
+    let ch = |v: f64| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!("#{:02x}{:02x}{:02x}", ch(c.r), ch(c.g), ch(c.b))
+}
+
+fn min_max(it: impl Iterator<Item = f64>) -> (f64, f64) {
+    it.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)))
+}
+
+/// Non-finite coordinates would make the document invalid; write them as 0.
+fn num(v: f64) -> String {
+    if v.is_finite() { v.to_string() } else { "0".into() }
+}
@@ -4,8 +4,11 @@ pub mod runtime;
 pub mod analysis;
 pub mod error;
 pub mod namespaces;
+pub mod export;
+pub mod render;
+pub mod optimize;
 
-pub use types::draw::{CoordMeta, DrawCommand, Origin, RenderMode, ShapeData, ShapeDesc, TransformData, origin_offset};
+pub use types::draw::{Color, CoordMeta, DrawCommand, Origin, RenderMode, ShapeData, ShapeDesc, TransformData, origin_offset};
 pub use error::{Error, ErrorCode, RuntimeError};
 pub use syntax::token::{Token, TokenKind};
 pub use runtime::value::Value;
@@ -26,13 +29,28 @@ pub struct State(pub HashMap<String, Value>);
 /// Per-frame input passed into `update`.
 #[derive(Debug, Clone, Default)]
 pub struct Input {
-    pub dt: f64,
+    /// Seconds since the previous frame.
+    pub dt:    f64,
+    /// Seconds since the sketch started.
+    pub time:  f64,
+    /// Number of frames ticked before this one.
+    pub frame: f64,
+    /// Pointer position in the sketch's coordinate space.
+    pub mouse: (f64, f64),
 }
 
+/// Draw commands per output channel, as returned by `Runtime::tick_channels`.
+/// The default `out` channel is keyed `""`.
+pub type Channels = HashMap<String, Vec<DrawCommand>>;
+
 /// A compiled Rustle program. Produced by `compile`.
 pub struct Program {
     pub(crate) ast: AstProgram,
     pub(crate) registry: NamespaceRegistry,
+    /// Top-level `const` values folded at compile time.
+    pub(crate) consts: HashMap<String, Value>,
+    /// Expressions the checker widens from `int` to `float`.
+    pub(crate) widened_exprs: HashMap<syntax::ast::Span, syntax::ast::Type>,
 }
 
 // ─── Public API ───────────────────────────────────────────────────────────────
@@ -42,8 +60,9 @@ pub fn compile(source: &str) -> Result<Program, Vec<Error>> {
     let tokens = syntax::lexer::Lexer::new(source).tokenize()?;
     let ast = syntax::parser::Parser::new(tokens).parse()?;
     let registry = NamespaceRegistry::standard();
-    resolve(&ast, &registry)?;
-    Ok(Program { ast, registry })
+    let widened_exprs = resolve(&ast, &registry)?.symbol_table.widened_exprs;
+    let consts = optimize::fold_constants(&ast, &registry);
+    Ok(Program { ast, registry, consts, widened_exprs })
 }
 
 // ─── Runtime ──────────────────────────────────────────────────────────────────
@@ -54,18 +73,47 @@ pub fn compile(source: &str) -> Result<Program, Vec<Error>> {
 ///   1. `Runtime::new(program)` — runs top-level config (`resolution`, `origin`),
 ///      evaluates `state {}` field initializers, and calls `init(state)` if present.
 ///   2. `runtime.tick(input)` — runs `update(state, input)` each frame, persisting
-///      both state and coord_meta (resolution/origin) across ticks.
+///      both state and coord_meta (resolution/origin) across ticks. Draw commands
+///      come back ordered by `layer`.
 pub struct Runtime {
     program: Program,
     state: State,
     runtime_state: RuntimeState,
+    iteration_limit: u64,
+    /// Draw commands from the last successful tick, replayed by `try_tick`.
+    last_frame: Vec<DrawCommand>,
 }
 
 impl Runtime {
     pub fn new(program: Program) -> Result<Self, RuntimeError> {
+        Self::with_iteration_limit(program, runtime::interpreter::DEFAULT_ITERATION_LIMIT)
+    }
+
+    /// Like `new`, but caps `while` / `for` iterations per init or tick at `limit`
+    /// (default 10 million). Exceeding it is a runtime error at the loop.
+    pub fn with_iteration_limit(program: Program, limit: u64) -> Result<Self, RuntimeError> {
+        let (state, runtime_state) = Self::initialize(&program, limit)?;
+        Ok(Self { program, state, runtime_state, iteration_limit: limit, last_frame: Vec::new() })
+    }
+
+    /// Restart from a fresh state without recompiling: re-runs top-level config,
+    /// the `state {}` initializers and `init(state)`, discarding the current state
+    /// and coord_meta. On error the runtime is left unchanged.
+    pub fn reset(&mut self) -> Result<(), RuntimeError> {
+        let (state, runtime_state) = Self::initialize(&self.program, self.iteration_limit)?;
+        self.state = state;
+        self.runtime_state = runtime_state;
+        self.last_frame.clear();
+        Ok(())
+    }
+
+    fn initialize(program: &Program, iteration_limit: u64) -> Result<(State, RuntimeState), RuntimeError> {
         use runtime::interpreter::Interpreter;
 
-        let mut interp = Interpreter::new(&program.ast, &program.registry);
+        let mut interp = Interpreter::new(&program.ast, &program.registry)
+            .with_folded_consts(&program.consts)
+            .with_widened_exprs(&program.widened_exprs)
+            .with_iteration_limit(iteration_limit);
 
         // 1. Run top-level stmts — resolution(), origin(), etc. These set
         //    runtime_state.coord_meta which persists for all subsequent ticks.
@@ -76,6 +124,7 @@ impl Runtime {
         if let Some(ref state_block) = program.ast.state {
             for field in &state_block.fields {
                 let val = interp.eval_expr(&field.initializer).unwrap_or(Value::Float(0.0));
+                let val = match &field.ty { Some(ty) => runtime::value::coerce(val, ty), None => val };
                 state.0.insert(field.name.clone(), val);
             }
         }
@@ -83,32 +132,90 @@ impl Runtime {
         // 3. Run init(state) if present — full imperative setup (loops, push, etc.).
         state = interp.run_init(state)?;
 
-        let runtime_state = interp.take_runtime_state();
-
-        Ok(Self { program, state, runtime_state })
+        Ok((state, interp.take_runtime_state()))
     }
 
     /// Execute one frame. Runs `update(state, input)` if present, otherwise
-    /// re-runs top-level draw statements.
+    /// re-runs top-level draw statements. Returns the default `out` channel;
+    /// shapes pushed to named channels are dropped (see `tick_channels`).
     pub fn tick(&mut self, input: &Input) -> Result<Vec<DrawCommand>, RuntimeError> {
+        self.run_frame(input).map(|(output, _)| output)
+    }
+
+    /// Like `tick`, but returns every output channel: the default `out` under
+    /// the key `""`, and each `out name << ...` channel under its name.
+    pub fn tick_channels(&mut self, input: &Input) -> Result<Channels, RuntimeError> {
+        let (output, mut channels) = self.run_frame(input)?;
+        channels.insert(String::new(), output);
+        Ok(channels)
+    }
+
+    /// Shared body of `tick` / `tick_channels`: the default output and the
+    /// named channels, each sorted by layer.
+    fn run_frame(&mut self, input: &Input) -> Result<(Vec<DrawCommand>, Channels), RuntimeError> {
         use runtime::interpreter::Interpreter;
         use syntax::ast::Item;
 
         let mut interp = Interpreter::new(&self.program.ast, &self.program.registry)
-            .with_runtime_state(self.runtime_state.clone());
+            .with_folded_consts(&self.program.consts)
+            .with_widened_exprs(&self.program.widened_exprs)
+            .with_runtime_state(self.runtime_state.for_run())
+            .with_iteration_limit(self.iteration_limit);
 
-        if self.program.ast.items.iter().any(|i| matches!(i, Item::FnDef(f) if f.name == "on_update")) {
-            self.state = interp.run_update(self.state.clone(), input)?;
+        let result = if self.program.ast.items.iter().any(|i| matches!(i, Item::FnDef(f) if f.name == "on_update")) {
+            interp.run_update(self.state.clone(), input).map(|state| self.state = state)
         } else {
-            interp.run_top_level()?;
+            interp.run_top_level()
+        };
+
+        let mut runtime_state = interp.take_runtime_state();
+        if let Err(e) = result {
+            // Keep `debug()` lines from the failed frame — they're usually why you're looking.
+            self.runtime_state.logs = std::mem::take(&mut runtime_state.logs);
+            return Err(e);
+        }
+        self.runtime_state = runtime_state;
+        self.runtime_state.frame += 1;
+        // Stable sort: shapes on the same layer keep their emission order.
+        let by_layer = |a: &DrawCommand, b: &DrawCommand| a.layer().total_cmp(&b.layer());
+        let mut output = interp.take_output();
+        output.sort_by(by_layer);
+        let mut channels = interp.take_channels();
+        for cmds in channels.values_mut() {
+            cmds.sort_by(by_layer);
         }
+        self.last_frame = output.clone();
+        Ok((output, channels))
+    }
 
-        self.runtime_state = interp.take_runtime_state();
-        Ok(interp.take_output())
+    /// Like `tick`, but a failed frame doesn't lose the picture: the error comes
+    /// back alongside the last successful frame's commands, and the state is
+    /// rolled back to before the frame — including lists and maps it mutated in
+    /// place — so the next tick can proceed.
+    pub fn try_tick(&mut self, input: &Input) -> (Vec<DrawCommand>, Option<RuntimeError>) {
+        let mut seen = HashMap::new();
+        let snapshot = self.state.0.iter()
+            .map(|(k, v)| (k.clone(), runtime::value::deep_clone(v, &mut seen)))
+            .collect();
+        match self.tick(input) {
+            Ok(cmds) => (cmds, None),
+            Err(e)   => {
+                self.state = State(snapshot);
+                (self.last_frame.clone(), Some(e))
+            }
+        }
     }
 
     pub fn state(&self) -> &State { &self.state }
 
+    /// Mutable access to the persistent state. Edits are seen by the next `tick`.
+    pub fn state_mut(&mut self) -> &mut State { &mut self.state }
+
+    /// Set (or add) a single state field, e.g. from an editor or debugger.
+    pub fn set_field(&mut self, name: &str, v: Value) {
+        self.state.0.insert(name.to_string(), v);
+    }
+
     /// Run `on_exit(s)` if defined, then drop. Call when the app stops.
     pub fn exit(&mut self) -> Result<(), RuntimeError> {
         use runtime::interpreter::Interpreter;
@@ -116,9 +223,20 @@ impl Runtime {
 
         if self.program.ast.items.iter().any(|i| matches!(i, Item::FnDef(f) if f.name == "on_exit")) {
             let mut interp = Interpreter::new(&self.program.ast, &self.program.registry)
-                .with_runtime_state(self.runtime_state.clone());
-            self.state = interp.run_on_exit(self.state.clone())?;
+                .with_folded_consts(&self.program.consts)
+                .with_widened_exprs(&self.program.widened_exprs)
+                .with_runtime_state(self.runtime_state.for_run())
+                .with_iteration_limit(self.iteration_limit);
+            let result = interp.run_on_exit(self.state.clone());
+            self.runtime_state.logs = interp.take_runtime_state().logs;
+            self.state = result?;
         }
         Ok(())
     }
+
+    /// Drain the lines written by `debug()` since the last call, oldest first.
+    /// Each is prefixed with its source line, e.g. `[12] vec2(1, 2)`.
+    pub fn take_logs(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.runtime_state.logs)
+    }
 }
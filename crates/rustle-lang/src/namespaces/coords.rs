@@ -22,12 +22,14 @@ impl NamespaceInfo for CoordsNamespace {
         vec![
             vfn("resolution", vec![Type::Float, Type::Float]),
             vfn("origin",     vec![named("origin")]),
-            origin_const("center"),
-            origin_const("top_left"), origin_const("top_right"),
-            origin_const("bottom_left"), origin_const("bottom_right"),
-            origin_const("top"), origin_const("bottom"),
-            origin_const("left"), origin_const("right"),
-        ]
+            vfn("normalize_aspect", vec![]),
+            vfn("y_up",       vec![]),
+            vfn("y_down",     vec![]),
+            Export { name: "canvas_size", kind: ExportKind::Function,
+                ty: Type::Fn(vec![], Some(Box::new(named("vec2")))) },
+        ].into_iter()
+            .chain(Origin::ALL.into_iter().map(|o| origin_const(o.name())))
+            .collect()
     }
 }
 
@@ -47,6 +49,20 @@ impl NamespaceProvider for CoordsNamespace {
                 state.coord_meta.px_height = as_float(&args[1], line)?;
                 Ok(Some(Value::Float(0.0)))
             }
+            "canvas_size" => {
+                check_argc(name, args, 0, line)?;
+                Ok(Some(Value::Vec2(state.coord_meta.px_width, state.coord_meta.px_height)))
+            }
+            "normalize_aspect" => {
+                check_argc(name, args, 0, line)?;
+                state.coord_meta.normalized = true;
+                Ok(Some(Value::Float(0.0)))
+            }
+            "y_up" | "y_down" => {
+                check_argc(name, args, 0, line)?;
+                state.coord_meta.y_down = Some(name == "y_down");
+                Ok(Some(Value::Float(0.0)))
+            }
             "origin" => {
                 check_argc(name, args, 1, line)?;
                 let s = match &args[0] {
@@ -55,7 +71,7 @@ impl NamespaceProvider for CoordsNamespace {
                         "`origin` expects an origin constant, got `{:?}`", other
                     ))),
                 };
-                state.coord_meta.origin = parse_origin(&s)
+                state.coord_meta.origin = Origin::from_name(&s)
                     .ok_or_else(|| RuntimeError::new(line, format!("unknown origin: `{s}`")))?;
                 Ok(Some(Value::Float(0.0)))
             }
@@ -64,25 +80,6 @@ impl NamespaceProvider for CoordsNamespace {
     }
 
     fn get_constant(&self, name: &str) -> Option<Value> {
-        match name {
-            "center" | "top_left" | "top_right" | "bottom_left" | "bottom_right"
-            | "top" | "bottom" | "left" | "right" => Some(Value::Str(name.into())),
-            _ => None,
-        }
-    }
-}
-
-fn parse_origin(s: &str) -> Option<Origin> {
-    match s {
-        "center"       => Some(Origin::Center),
-        "top_left"     => Some(Origin::TopLeft),
-        "top_right"    => Some(Origin::TopRight),
-        "bottom_left"  => Some(Origin::BottomLeft),
-        "bottom_right" => Some(Origin::BottomRight),
-        "top"          => Some(Origin::Top),
-        "bottom"       => Some(Origin::Bottom),
-        "left"         => Some(Origin::Left),
-        "right"        => Some(Origin::Right),
-        _              => None,
+        Origin::from_name(name).map(|_| Value::Str(name.into()))
     }
 }
@@ -8,9 +8,15 @@ use crate::types::mat::{
     m4_identity, m4_translate, m4_scale_xyz, m4_rotate_x, m4_rotate_y, m4_rotate_z,
 };
 use crate::error::RuntimeError;
+use crate::runtime::interpreter::MAX_RANGE_LEN;
 use crate::Value;
+use std::cell::RefCell;
 use std::collections::HashMap;
-use super::{Export, ExportKind, NamespaceInfo, NamespaceProvider, RuntimeState, as_float, check_argc, value_type_name};
+use std::rc::Rc;
+use super::{Export, ExportKind, NamespaceInfo, NamespaceProvider, RuntimeState, as_float, as_float_list, as_vec2, as_vec3, check_argc, format_interpolated, format_value, value_type_name};
+
+/// Most places `format_float` will print — past this f64 has no digits left to show.
+const MAX_FORMAT_DECIMALS: f64 = 15.0;
 
 // ─── Type helpers ─────────────────────────────────────────────────────────────
 
@@ -18,6 +24,10 @@ fn f(name: &'static str, params: Vec<Type>, ret: Type) -> Export {
     Export { name, kind: ExportKind::Function, ty: Type::Fn(params, Some(Box::new(ret))) }
 }
 
+fn vfn(name: &'static str, params: Vec<Type>) -> Export {
+    Export { name, kind: ExportKind::Function, ty: Type::Fn(params, None) }
+}
+
 fn c(name: &'static str, ty: Type) -> Export {
     Export { name, kind: ExportKind::Constant, ty }
 }
@@ -38,23 +48,74 @@ pub fn core_exports() -> Vec<Export> {
         f("atan2", vec![Type::Float, Type::Float], Type::Float),
         f("sqrt",  vec![Type::Float], Type::Float),
         f("pow",   vec![Type::Float, Type::Float], Type::Float),
+        f("mod_floor", vec![Type::Float, Type::Float], Type::Float),
         f("abs",   vec![Type::Float], Type::Float),
         f("floor", vec![Type::Float], Type::Float),
         f("ceil",  vec![Type::Float], Type::Float),
         f("round", vec![Type::Float], Type::Float),
         f("sign",  vec![Type::Float], Type::Float),
         f("fract", vec![Type::Float], Type::Float),
+        f("trunc", vec![Type::Float], Type::Float),
+        f("radians", vec![Type::Float], Type::Float),
+        f("degrees", vec![Type::Float], Type::Float),
         f("min",   vec![Type::Float, Type::Float], Type::Float),
         f("max",   vec![Type::Float, Type::Float], Type::Float),
         f("clamp", vec![Type::Float, Type::Float, Type::Float], Type::Float),
+        f("clamp01", vec![Type::Float], Type::Float),
         f("lerp",  vec![Type::Float, Type::Float, Type::Float], Type::Float),
+        f("mix",   vec![Type::Float, Type::Float, Type::Float], Type::Float),
+        f("step",  vec![Type::Float, Type::Float], Type::Float),
+        f("smoothstep", vec![Type::Float, Type::Float, Type::Float], Type::Float),
+        f("remap", vec![Type::Float, Type::Float, Type::Float, Type::Float, Type::Float], Type::Float),
+        // Angles (radians): wrap into (-PI, PI], interpolate along the shorter arc
+        f("wrap_angle", vec![Type::Float], Type::Float),
+        f("lerp_angle", vec![Type::Float, Type::Float, Type::Float], Type::Float),
+
+        // List aggregates
+        f("sum",    vec![Type::List(Box::new(Type::Float))], Type::Float),
+        f("min_of", vec![Type::List(Box::new(Type::Float))], Type::Float),
+        f("max_of", vec![Type::List(Box::new(Type::Float))], Type::Float),
+        f("avg",    vec![Type::List(Box::new(Type::Float))], Type::Float),
+        // Pairing into vec2s: zip stops at the shorter list, enumerate is (index, value)
+        f("zip",       vec![Type::List(Box::new(Type::Float)), Type::List(Box::new(Type::Float))], Type::List(Box::new(named("vec2")))),
+        f("enumerate", vec![Type::List(Box::new(Type::Float))], Type::List(Box::new(named("vec2")))),
+
+        // Random (deterministic per run — see RuntimeState::rng)
+        f("random",       vec![], Type::Float),
+        f("random_range", vec![Type::Float, Type::Float], Type::Float),
+        vfn("seed",       vec![Type::Float]),
+
+        // Frame counter (0 during init and the first tick)
+        f("frame", vec![], Type::Float),
+
+        // Assertions — runtime error with `msg` when `cond` is false
+        vfn("assert", vec![Type::Bool, named("string")]),
+
+        // Debug log — accepts any value; the checker special-cases the argument
+        vfn("debug", vec![Type::Float]),
+
+        // Strings — to_string accepts any value; the checker special-cases the argument
+        f("to_string",    vec![Type::Float], named("string")),
+        f("format_float", vec![Type::Float, Type::Float], named("string")),
+        f("parse_float",  vec![named("string")], Type::Res(Box::new(Type::Float))),
+
+        // Value noise — continuous, deterministic, output in [-1, 1]
+        f("noise",   vec![Type::Float], Type::Float),
+        f("noise2d", vec![named("vec2")], Type::Float),
 
         // Constructors
         f("vec2",      vec![Type::Float, Type::Float], named("vec2")),
         f("vec3",      vec![Type::Float, Type::Float, Type::Float], named("vec3")),
         f("vec4",      vec![Type::Float, Type::Float, Type::Float, Type::Float], named("vec4")),
         f("color",     vec![Type::Float, Type::Float, Type::Float], named("color")),
+        f("hsv",       vec![Type::Float, Type::Float, Type::Float], named("color")),
+        // Polar coordinates (theta in radians): polar(r, theta) ↔ to_polar(p) = (r, theta)
+        f("polar",    vec![Type::Float, Type::Float], named("vec2")),
+        f("to_polar", vec![named("vec2")],            named("vec2")),
+        // Layout: cols × rows positions centered on the origin, row by row
+        f("grid", vec![Type::Float, Type::Float, named("vec2")], Type::List(Box::new(named("vec2")))),
         f("transform", vec![], named("transform")),
+        f("transform_identity", vec![], named("transform")),
         f("mat3",      vec![], named("mat3")),
         f("mat4",      vec![], named("mat4")),
         // Mat3 2D constructors (angle in degrees)
@@ -67,6 +128,13 @@ pub fn core_exports() -> Vec<Export> {
         f("mat4_rotate_x",  vec![Type::Float], named("mat4")),
         f("mat4_rotate_y",  vec![Type::Float], named("mat4")),
         f("mat4_rotate_z",  vec![Type::Float], named("mat4")),
+        // Vector/radian forms: mat3 acts on (x, y, 1), mat4 on (x, y, z, 1)
+        f("mat3_rotation",    vec![Type::Float],   named("mat3")),
+        f("mat3_translation", vec![named("vec2")], named("mat3")),
+        f("mat3_scaling",     vec![named("vec2")], named("mat3")),
+        f("mat4_rotation",    vec![Type::Float],   named("mat4")),
+        f("mat4_translation", vec![named("vec3")], named("mat4")),
+        f("mat4_scaling",     vec![named("vec3")], named("mat4")),
 
         // Result helpers
         f("ok",    vec![Type::Float], Type::Res(Box::new(Type::Float))),
@@ -99,7 +167,7 @@ impl NamespaceProvider for CoreNamespace {
         name: &str,
         args: &[Value],
         _named: &HashMap<String, Value>,
-        _state: &mut RuntimeState,
+        state: &mut RuntimeState,
         line: usize,
     ) -> Result<Option<Value>, RuntimeError> {
         let v = match name {
@@ -115,12 +183,21 @@ impl NamespaceProvider for CoreNamespace {
             "floor" => { check_argc(name, args, 1, line)?; Value::Float(as_float(&args[0], line)?.floor()) }
             "ceil"  => { check_argc(name, args, 1, line)?; Value::Float(as_float(&args[0], line)?.ceil()) }
             "round" => { check_argc(name, args, 1, line)?; Value::Float(as_float(&args[0], line)?.round()) }
-            "sign"  => { check_argc(name, args, 1, line)?; Value::Float(as_float(&args[0], line)?.signum()) }
+            "trunc" => { check_argc(name, args, 1, line)?; Value::Float(as_float(&args[0], line)?.trunc()) }
+            "sign"  => {
+                check_argc(name, args, 1, line)?;
+                // f64::signum maps ±0.0 to ±1.0; sign(0) should be 0
+                let x = as_float(&args[0], line)?;
+                Value::Float(if x == 0.0 { 0.0 } else { x.signum() })
+            }
             "fract" => {
                 check_argc(name, args, 1, line)?;
                 let x = as_float(&args[0], line)?;
                 Value::Float(x - x.floor())
             }
+            "radians" => { check_argc(name, args, 1, line)?; Value::Float(as_float(&args[0], line)?.to_radians()) }
+            "degrees" => { check_argc(name, args, 1, line)?; Value::Float(as_float(&args[0], line)?.to_degrees()) }
+            "clamp01" => { check_argc(name, args, 1, line)?; Value::Float(as_float(&args[0], line)?.clamp(0.0, 1.0)) }
 
             // ── 2-arg math ────────────────────────────────────────────────
             "atan2" => {
@@ -139,6 +216,22 @@ impl NamespaceProvider for CoreNamespace {
                 check_argc(name, args, 2, line)?;
                 Value::Float(as_float(&args[0], line)?.max(as_float(&args[1], line)?))
             }
+            "mod_floor" => {
+                check_argc(name, args, 2, line)?;
+                let (a, b) = (as_float(&args[0], line)?, as_float(&args[1], line)?);
+                if b == 0.0 {
+                    return Err(RuntimeError::new(line, "mod by zero"));
+                }
+                // `%` truncates, so its result takes the sign of `a`; shift it
+                // into the sign of `b` instead.
+                let r = a % b;
+                Value::Float(if r != 0.0 && (r < 0.0) != (b < 0.0) { r + b } else { r })
+            }
+            "step" => {
+                check_argc(name, args, 2, line)?;
+                let (edge, x) = (as_float(&args[0], line)?, as_float(&args[1], line)?);
+                Value::Float(if x < edge { 0.0 } else { 1.0 })
+            }
 
             // ── 3-arg math ────────────────────────────────────────────────
             "clamp" => {
@@ -146,11 +239,160 @@ impl NamespaceProvider for CoreNamespace {
                 let (x, lo, hi) = (as_float(&args[0], line)?, as_float(&args[1], line)?, as_float(&args[2], line)?);
                 Value::Float(x.clamp(lo, hi))
             }
-            "lerp" => {
+            "lerp" | "mix" => {
                 check_argc(name, args, 3, line)?;
                 let (a, b, t) = (as_float(&args[0], line)?, as_float(&args[1], line)?, as_float(&args[2], line)?);
                 Value::Float(a + (b - a) * t)
             }
+            "smoothstep" => {
+                check_argc(name, args, 3, line)?;
+                let (e0, e1, x) = (as_float(&args[0], line)?, as_float(&args[1], line)?, as_float(&args[2], line)?);
+                let t = ((x - e0) / (e1 - e0)).clamp(0.0, 1.0);
+                Value::Float(t * t * (3.0 - 2.0 * t))
+            }
+
+            // ── 5-arg math ────────────────────────────────────────────────
+            "remap" => {
+                check_argc(name, args, 5, line)?;
+                let x = as_float(&args[0], line)?;
+                let (in_lo, in_hi) = (as_float(&args[1], line)?, as_float(&args[2], line)?);
+                let (out_lo, out_hi) = (as_float(&args[3], line)?, as_float(&args[4], line)?);
+                if in_lo == in_hi {
+                    return Err(RuntimeError::new(line, "remap: input range is empty (in_lo == in_hi)"));
+                }
+                Value::Float(out_lo + (x - in_lo) / (in_hi - in_lo) * (out_hi - out_lo))
+            }
+
+            "wrap_angle" => {
+                check_argc(name, args, 1, line)?;
+                Value::Float(wrap_angle(as_float(&args[0], line)?))
+            }
+            "lerp_angle" => {
+                check_argc(name, args, 3, line)?;
+                let (a, b, t) = (as_float(&args[0], line)?, as_float(&args[1], line)?, as_float(&args[2], line)?);
+                Value::Float(wrap_angle(a + wrap_angle(b - a) * t))
+            }
+
+            // ── List aggregates ───────────────────────────────────────────
+            "sum" => {
+                check_argc(name, args, 1, line)?;
+                Value::Float(as_float_list(&args[0], line)?.iter().sum())
+            }
+            "min_of" | "max_of" | "avg" => {
+                check_argc(name, args, 1, line)?;
+                let xs = as_float_list(&args[0], line)?;
+                if xs.is_empty() {
+                    return Err(RuntimeError::new(line, format!("`{name}` of an empty list")));
+                }
+                Value::Float(match name {
+                    "min_of" => xs.iter().copied().fold(f64::INFINITY, f64::min),
+                    "max_of" => xs.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+                    _        => xs.iter().sum::<f64>() / xs.len() as f64,
+                })
+            }
+
+            "zip" => {
+                check_argc(name, args, 2, line)?;
+                let (xs, ys) = (as_float_list(&args[0], line)?, as_float_list(&args[1], line)?);
+                let pairs = xs.into_iter().zip(ys).map(|(x, y)| Value::Vec2(x, y)).collect();
+                Value::List(Rc::new(RefCell::new(pairs)))
+            }
+            "enumerate" => {
+                check_argc(name, args, 1, line)?;
+                let pairs = as_float_list(&args[0], line)?.into_iter().enumerate()
+                    .map(|(i, x)| Value::Vec2(i as f64, x))
+                    .collect();
+                Value::List(Rc::new(RefCell::new(pairs)))
+            }
+
+            // ── Random ────────────────────────────────────────────────────
+            "random" => {
+                check_argc(name, args, 0, line)?;
+                Value::Float(state.next_random())
+            }
+            "random_range" => {
+                check_argc(name, args, 2, line)?;
+                let (lo, hi) = (as_float(&args[0], line)?, as_float(&args[1], line)?);
+                Value::Float(lo + (hi - lo) * state.next_random())
+            }
+            "seed" => {
+                check_argc(name, args, 1, line)?;
+                state.rng = as_float(&args[0], line)?.to_bits();
+                Value::Float(0.0)
+            }
+
+            // ── Frame counter ─────────────────────────────────────────────
+            "frame" => {
+                check_argc(name, args, 0, line)?;
+                Value::Float(state.frame as f64)
+            }
+
+            // ── Assertions ────────────────────────────────────────────────
+            "assert" => {
+                check_argc(name, args, 2, line)?;
+                match (&args[0], &args[1]) {
+                    (Value::Bool(true), Value::Str(_)) => Value::Float(0.0),
+                    (Value::Bool(false), Value::Str(msg)) => {
+                        return Err(RuntimeError::new(line, format!("assertion failed: {msg}")));
+                    }
+                    (cond, msg) => return Err(RuntimeError::new(line, format!(
+                        "`assert` expects (bool, string), got ({}, {})",
+                        value_type_name(cond), value_type_name(msg),
+                    ))),
+                }
+            }
+
+            // ── Debug log ─────────────────────────────────────────────────
+            "debug" => {
+                check_argc(name, args, 1, line)?;
+                state.log(format!("[{line}] {}", format_value(&args[0])));
+                Value::Float(0.0)
+            }
+
+            // ── Strings ───────────────────────────────────────────────────
+            // Same text as `"{x}"` interpolation.
+            "to_string" => {
+                check_argc(name, args, 1, line)?;
+                Value::Str(format_interpolated(&args[0]))
+            }
+            "format_float" => {
+                check_argc(name, args, 2, line)?;
+                let x = as_float(&args[0], line)?;
+                let decimals = as_float(&args[1], line)?.round();
+                if !(0.0..=MAX_FORMAT_DECIMALS).contains(&decimals) {
+                    return Err(RuntimeError::new(line, format!(
+                        "`format_float` needs decimals in 0..={MAX_FORMAT_DECIMALS}, got {decimals}"
+                    )));
+                }
+                // Halves round away from zero, like `round`.
+                let scale = 10f64.powi(decimals as i32);
+                Value::Str(format!("{:.*}", decimals as usize, (x * scale).round() / scale))
+            }
+
+            // Surrounding whitespace is ignored; `inf` / `nan` are rejected.
+            "parse_float" => {
+                check_argc(name, args, 1, line)?;
+                let Value::Str(s) = &args[0] else {
+                    return Err(RuntimeError::new(line, format!(
+                        "`parse_float` expects string, got {}", value_type_name(&args[0])
+                    )));
+                };
+                match s.trim().parse::<f64>() {
+                    Ok(x) if x.is_finite() => Value::ResOk(Box::new(Value::Float(x))),
+                    _ => Value::ResErr(format!("cannot parse `{s}` as float")),
+                }
+            }
+
+            // ── Noise ─────────────────────────────────────────────────────
+            "noise" => {
+                check_argc(name, args, 1, line)?;
+                Value::Float(value_noise2d(as_float(&args[0], line)?, 0.0))
+            }
+            "noise2d" => {
+                check_argc(name, args, 1, line)?;
+                let (x, y) = as_vec2(&args[0], line)?;
+                Value::Float(value_noise2d(x, y))
+            }
 
             // ── Constructors ──────────────────────────────────────────────
             "vec2" => {
@@ -182,7 +424,45 @@ impl NamespaceProvider for CoreNamespace {
                     a,
                 }
             }
-            "transform" => {
+            "hsv" => {
+                check_argc(name, args, 3, line)?;
+                let (h, s, v) = (as_float(&args[0], line)?, as_float(&args[1], line)?, as_float(&args[2], line)?);
+                let (r, g, b) = hsv_to_rgb(h, s, v);
+                Value::Color { r, g, b, a: 1.0 }
+            }
+            "polar" => {
+                check_argc(name, args, 2, line)?;
+                let (r, theta) = (as_float(&args[0], line)?, as_float(&args[1], line)?);
+                Value::Vec2(r * theta.cos(), r * theta.sin())
+            }
+            "to_polar" => {
+                check_argc(name, args, 1, line)?;
+                let (x, y) = as_vec2(&args[0], line)?;
+                Value::Vec2(x.hypot(y), y.atan2(x))
+            }
+            "grid" => {
+                check_argc(name, args, 3, line)?;
+                let (cols, rows) = (as_float(&args[0], line)?.trunc(), as_float(&args[1], line)?.trunc());
+                let (sx, sy) = as_vec2(&args[2], line)?;
+                if !(cols.is_finite() && rows.is_finite() && cols >= 0.0 && rows >= 0.0) {
+                    return Err(RuntimeError::new(line, format!(
+                        "`grid` expects finite non-negative counts, got {cols} × {rows}"
+                    )));
+                }
+                if cols * rows > MAX_RANGE_LEN as f64 {
+                    return Err(RuntimeError::new(line, format!(
+                        "`grid` of {cols} × {rows} has more than the {MAX_RANGE_LEN} points a list can hold"
+                    )));
+                }
+                let (x0, y0) = ((cols - 1.0) / 2.0, (rows - 1.0) / 2.0);
+                let points = (0..rows as usize)
+                    .flat_map(|j| (0..cols as usize).map(move |i| {
+                        Value::Vec2((i as f64 - x0) * sx, (j as f64 - y0) * sy)
+                    }))
+                    .collect();
+                Value::List(Rc::new(RefCell::new(points)))
+            }
+            "transform" | "transform_identity" => {
                 Value::Transform(TransformData::default())
             }
             "mat3" => {
@@ -223,6 +503,34 @@ impl NamespaceProvider for CoreNamespace {
                 check_argc(name, args, 1, line)?;
                 Value::Mat4(Box::new(m4_rotate_z(as_float(&args[0], line)?.to_radians())))
             }
+            "mat3_rotation" => {
+                check_argc(name, args, 1, line)?;
+                Value::Mat3(Box::new(m3_rotate2d(as_float(&args[0], line)?)))
+            }
+            "mat3_translation" => {
+                check_argc(name, args, 1, line)?;
+                let (x, y) = as_vec2(&args[0], line)?;
+                Value::Mat3(Box::new(m3_translate2d(x, y)))
+            }
+            "mat3_scaling" => {
+                check_argc(name, args, 1, line)?;
+                let (x, y) = as_vec2(&args[0], line)?;
+                Value::Mat3(Box::new(m3_scale2d(x, y)))
+            }
+            "mat4_rotation" => {
+                check_argc(name, args, 1, line)?;
+                Value::Mat4(Box::new(m4_rotate_z(as_float(&args[0], line)?)))
+            }
+            "mat4_translation" => {
+                check_argc(name, args, 1, line)?;
+                let (x, y, z) = as_vec3(&args[0], line)?;
+                Value::Mat4(Box::new(m4_translate(x, y, z)))
+            }
+            "mat4_scaling" => {
+                check_argc(name, args, 1, line)?;
+                let (x, y, z) = as_vec3(&args[0], line)?;
+                Value::Mat4(Box::new(m4_scale_xyz(x, y, z)))
+            }
 
             // ── Result helpers ─────────────────────────────────────────────
             "ok" => {
@@ -257,3 +565,52 @@ impl NamespaceProvider for CoreNamespace {
         }
     }
 }
+
+// ─── Angles ───────────────────────────────────────────────────────────────────
+
+/// Wrap an angle in radians into `(-PI, PI]`.
+fn wrap_angle(a: f64) -> f64 {
+    let r = a.rem_euclid(std::f64::consts::TAU);
+    if r > std::f64::consts::PI { r - std::f64::consts::TAU } else { r }
+}
+
+// ─── Color conversion ─────────────────────────────────────────────────────────
+
+/// (hue in degrees, saturation, value) → RGB in 0–1. Hue wraps around 360.
+fn hsv_to_rgb(h: f64, s: f64, v: f64) -> (f64, f64, f64) {
+    let h = h.rem_euclid(360.0) / 60.0;
+    let c = v * s;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = v - c;
+    (r + m, g + m, b + m)
+}
+
+// ─── Value noise ──────────────────────────────────────────────────────────────
+
+/// Hash an integer lattice point to a value in [-1, 1].
+fn lattice_value(ix: i64, iy: i64) -> f64 {
+    let mut h = (ix as u64).wrapping_mul(0x8DA6_B343) ^ (iy as u64).wrapping_mul(0xD816_3841);
+    h = (h ^ (h >> 13)).wrapping_mul(0x5BD1_E995);
+    h ^= h >> 15;
+    (h & 0xFF_FFFF) as f64 / 0xFF_FFFF as f64 * 2.0 - 1.0
+}
+
+/// Bilinear blend of the four surrounding lattice values, eased with the
+/// cubic `t*t*(3-2t)` so the field is continuous with smooth transitions.
+fn value_noise2d(x: f64, y: f64) -> f64 {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (ix, iy) = (x0 as i64, y0 as i64);
+    let ease = |t: f64| t * t * (3.0 - 2.0 * t);
+    let (tx, ty) = (ease(x - x0), ease(y - y0));
+    let top    = lattice_value(ix, iy)     + (lattice_value(ix + 1, iy)     - lattice_value(ix, iy))     * tx;
+    let bottom = lattice_value(ix, iy + 1) + (lattice_value(ix + 1, iy + 1) - lattice_value(ix, iy + 1)) * tx;
+    top + (bottom - top) * ty
+}
@@ -0,0 +1,84 @@
+use crate::syntax::ast::Type;
+use crate::error::RuntimeError;
+use crate::Value;
+use std::collections::HashMap;
+use std::f64::consts::{PI, TAU};
+use super::{Export, ExportKind, NamespaceInfo, NamespaceProvider, RuntimeState, as_float, check_argc};
+
+/// An easing curve and the name it is exported under.
+type Curve = (&'static str, fn(f64) -> f64);
+
+/// Easing curves: `t` in `[0, 1]` (clamped) → eased progress, 0 → 0 and 1 → 1.
+/// Elastic and back curves overshoot in between.
+const CURVES: &[Curve] = &[
+    ("ease_in_quad",      |t| t * t),
+    ("ease_out_quad",     |t| 1.0 - (1.0 - t) * (1.0 - t)),
+    ("ease_in_out_quad",  |t| if t < 0.5 { 2.0 * t * t } else { 1.0 - (-2.0 * t + 2.0).powi(2) / 2.0 }),
+    ("ease_in_cubic",     |t| t * t * t),
+    ("ease_out_cubic",    |t| 1.0 - (1.0 - t).powi(3)),
+    ("ease_in_out_cubic", |t| if t < 0.5 { 4.0 * t * t * t } else { 1.0 - (-2.0 * t + 2.0).powi(3) / 2.0 }),
+    ("ease_in_sine",      |t| 1.0 - (t * PI / 2.0).cos()),
+    ("ease_out_sine",     |t| (t * PI / 2.0).sin()),
+    ("ease_in_out_sine",  |t| -((PI * t).cos() - 1.0) / 2.0),
+    ("ease_in_back",      |t| { let c = 1.70158; (c + 1.0) * t * t * t - c * t * t }),
+    ("ease_out_back",     |t| { let c = 1.70158; 1.0 + (c + 1.0) * (t - 1.0).powi(3) + c * (t - 1.0).powi(2) }),
+    ("ease_out_elastic",  ease_out_elastic),
+    ("ease_out_bounce",   ease_out_bounce),
+];
+
+fn ease_out_elastic(t: f64) -> f64 {
+    if t == 0.0 || t == 1.0 { return t; }
+    2f64.powf(-10.0 * t) * ((t * 10.0 - 0.75) * TAU / 3.0).sin() + 1.0
+}
+
+fn ease_out_bounce(t: f64) -> f64 {
+    let (n, d) = (7.5625, 2.75);
+    if t < 1.0 / d {
+        n * t * t
+    } else if t < 2.0 / d {
+        let t = t - 1.5 / d;
+        n * t * t + 0.75
+    } else if t < 2.5 / d {
+        let t = t - 2.25 / d;
+        n * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / d;
+        n * t * t + 0.984375
+    }
+}
+
+pub struct EasingNamespace;
+
+impl NamespaceInfo for EasingNamespace {
+    fn name(&self) -> &'static str { "easing" }
+
+    fn exports(&self) -> Vec<Export> {
+        CURVES.iter()
+            .map(|&(name, _)| Export {
+                name,
+                kind: ExportKind::Function,
+                ty: Type::Fn(vec![Type::Float], Some(Box::new(Type::Float))),
+            })
+            .collect()
+    }
+}
+
+impl NamespaceProvider for EasingNamespace {
+    fn call(
+        &self,
+        name: &str,
+        args: &[Value],
+        _named: &HashMap<String, Value>,
+        _state: &mut RuntimeState,
+        line: usize,
+    ) -> Result<Option<Value>, RuntimeError> {
+        let Some((_, curve)) = CURVES.iter().find(|(n, _)| *n == name) else {
+            return Ok(None);
+        };
+        check_argc(name, args, 1, line)?;
+        let t = as_float(&args[0], line)?.clamp(0.0, 1.0);
+        Ok(Some(Value::Float(curve(t))))
+    }
+
+    fn get_constant(&self, _name: &str) -> Option<Value> { None }
+}
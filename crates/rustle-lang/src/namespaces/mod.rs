@@ -1,5 +1,5 @@
-use crate::syntax::ast::Type;
-use crate::types::draw::RenderMode;
+use crate::syntax::ast::{ImportDecl, ImportMember, Type};
+use crate::types::draw::{Color, RenderMode};
 use crate::error::RuntimeError;
 use crate::Value;
 use std::collections::HashMap;
@@ -9,14 +9,53 @@ use std::collections::HashMap;
 /// Interpreter-level state passed to every namespace call.
 /// Holds the current coordinate context — updated by `resolution`, `default`,
 /// `normalize`, `origin` and snapshotted into each ShapeData at build time.
+/// Also carries the RNG position so `random()` sequences continue across ticks,
+/// the frame counter read by `frame()`, and the lines written by `debug()`.
 #[derive(Clone)]
 pub struct RuntimeState {
     pub coord_meta: crate::types::draw::CoordMeta,
+    /// SplitMix64 counter — reset by `seed(n)`, advanced by every `random()` call.
+    pub rng: u64,
+    /// Ticks completed so far — advanced by `Runtime::tick` after each frame.
+    pub frame: u64,
+    /// `debug()` output not yet taken by the host (`Runtime::take_logs`).
+    /// Holds at most `MAX_LOGS` lines; older ones are dropped first.
+    pub logs: Vec<String>,
 }
 
+/// Most `debug()` lines kept for a host that never calls `take_logs`.
+pub const MAX_LOGS: usize = 1000;
+
 impl Default for RuntimeState {
     fn default() -> Self {
-        Self { coord_meta: crate::types::draw::CoordMeta::default() }
+        Self { coord_meta: crate::types::draw::CoordMeta::default(), rng: 0, frame: 0, logs: Vec::new() }
+    }
+}
+
+impl RuntimeState {
+    /// Copy handed to an interpreter run. The pending logs move into it rather
+    /// than being cloned; the run hands them back with its final state.
+    pub(crate) fn for_run(&mut self) -> Self {
+        Self { logs: std::mem::take(&mut self.logs), ..self.clone() }
+    }
+
+    /// Append a `debug()` line, dropping the oldest once `MAX_LOGS` are held.
+    pub fn log(&mut self, line: String) {
+        if self.logs.len() >= MAX_LOGS {
+            self.logs.drain(..=self.logs.len() - MAX_LOGS);
+        }
+        self.logs.push(line);
+    }
+
+    /// Next pseudo-random float in `[0, 1)`.
+    pub fn next_random(&mut self) -> f64 {
+        self.rng = self.rng.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.rng;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        // Top 53 bits → uniform f64 mantissa.
+        (z >> 11) as f64 / (1u64 << 53) as f64
     }
 }
 
@@ -24,6 +63,7 @@ pub mod core;
 pub mod shapes;
 pub mod render;
 pub mod coords;
+pub mod easing;
 
 // ─── Export ───────────────────────────────────────────────────────────────────
 
@@ -48,6 +88,18 @@ pub trait NamespaceInfo: Send + Sync {
     fn get_export(&self, name: &str) -> Option<Export> {
         self.exports().into_iter().find(|e| e.name == name)
     }
+
+    /// Members an `import` brings into scope — every export for `{ * }`,
+    /// otherwise the listed ones.
+    fn import_members(&self, import: &ImportDecl) -> Vec<ImportMember> {
+        if import.wildcard {
+            self.exports().into_iter()
+                .map(|e| ImportMember { name: e.name.to_string(), alias: None })
+                .collect()
+        } else {
+            import.members.clone()
+        }
+    }
 }
 
 // ─── Runtime interface ────────────────────────────────────────────────────────
@@ -108,6 +160,7 @@ impl NamespaceRegistry {
         r.register(Box::new(shapes::ShapesNamespace));
         r.register(Box::new(render::RenderNamespace));
         r.register(Box::new(coords::CoordsNamespace));
+        r.register(Box::new(easing::EasingNamespace));
         r
     }
 }
@@ -121,6 +174,7 @@ impl Default for NamespaceRegistry {
 pub(crate) fn as_float(v: &Value, line: usize) -> Result<f64, RuntimeError> {
     match v {
         Value::Float(x) => Ok(*x),
+        Value::Int(n)   => Ok(*n as f64),
         _ => Err(RuntimeError::new(line, format!("expected float, got {}", value_type_name(v)))),
     }
 }
@@ -132,6 +186,13 @@ pub(crate) fn as_vec2(v: &Value, line: usize) -> Result<(f64, f64), RuntimeError
     }
 }
 
+pub(crate) fn as_vec3(v: &Value, line: usize) -> Result<(f64, f64, f64), RuntimeError> {
+    match v {
+        Value::Vec3(x, y, z) => Ok((*x, *y, *z)),
+        _ => Err(RuntimeError::new(line, format!("expected vec3, got {}", value_type_name(v)))),
+    }
+}
+
 pub(crate) fn as_vertices(v: &Value, line: usize) -> Result<Vec<(f64, f64)>, RuntimeError> {
     match v {
         Value::List(items) => items.borrow().iter().map(|i| as_vec2(i, line)).collect(),
@@ -139,6 +200,13 @@ pub(crate) fn as_vertices(v: &Value, line: usize) -> Result<Vec<(f64, f64)>, Run
     }
 }
 
+pub(crate) fn as_float_list(v: &Value, line: usize) -> Result<Vec<f64>, RuntimeError> {
+    match v {
+        Value::List(items) => items.borrow().iter().map(|i| as_float(i, line)).collect(),
+        _ => Err(RuntimeError::new(line, "expected list[float]")),
+    }
+}
+
 pub(crate) fn check_argc(name: &str, args: &[Value], n: usize, line: usize) -> Result<(), RuntimeError> {
     if args.len() != n {
         Err(RuntimeError::new(line, format!("`{name}` expects {n} args, got {}", args.len())))
@@ -155,8 +223,86 @@ pub(crate) fn render_mode_from_named(named: &HashMap<String, Value>, line: usize
     }
 }
 
+/// Optional color named arg, e.g. `fill: #ff0000`.
+pub(crate) fn color_from_named(named: &HashMap<String, Value>, key: &str, line: usize) -> Result<Option<Color>, RuntimeError> {
+    match named.get(key) {
+        Some(Value::Color { r, g, b, a }) => Ok(Some(Color { r: *r, g: *g, b: *b, a: *a })),
+        Some(_) => Err(RuntimeError::new(line, format!("`{key}:` must be a color value"))),
+        None    => Ok(None),
+    }
+}
+
+/// Text spliced into an interpolated string: `format_value`, except that a
+/// float is rounded to 6 decimals so `"{0.1 + 0.2}"` reads `0.3`.
+pub(crate) fn format_interpolated(v: &Value) -> String {
+    match v {
+        Value::Float(x) => {
+            let rounded = (x * 1e6).round() / 1e6;
+            // `+ 0.0` turns a rounded `-0` into `0`.
+            if rounded.is_finite() { (rounded + 0.0).to_string() } else { x.to_string() }
+        }
+        other => format_value(other),
+    }
+}
+
+/// Readable form of a value for `debug()` — literal syntax where the language
+/// has one (`vec2(1, 2)`, `[1, 2]`), the type name otherwise.
+pub(crate) fn format_value(v: &Value) -> String {
+    let floats = |xs: &[f64]| xs.iter().map(f64::to_string).collect::<Vec<_>>().join(", ");
+    match v {
+        Value::Int(n)         => n.to_string(),
+        Value::Float(x)       => x.to_string(),
+        Value::Bool(b)        => b.to_string(),
+        Value::Str(s)         => s.clone(),
+        Value::Vec2(x, y)     => format!("vec2({})", floats(&[*x, *y])),
+        Value::Vec3(x, y, z)  => format!("vec3({})", floats(&[*x, *y, *z])),
+        Value::Vec4(x, y, z, w) => format!("vec4({})", floats(&[*x, *y, *z, *w])),
+        Value::Color { r, g, b, a } => format!("color({})", floats(&[*r, *g, *b, *a])),
+        Value::Mat3(m)        => format!("mat3({})", floats(&m[..])),
+        Value::Mat4(m)        => format!("mat4({})", floats(&m[..])),
+        Value::List(items)    => {
+            let items: Vec<String> = items.borrow().iter().map(format_value).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Value::Map(entries)   => {
+            let entries = entries.borrow();
+            let mut keys: Vec<&String> = entries.keys().collect();
+            keys.sort();
+            let items: Vec<String> = keys.iter()
+                .map(|k| format!("\"{k}\": {}", format_value(&entries[*k])))
+                .collect();
+            format!("{{{}}}", items.join(", "))
+        }
+        Value::Tuple(items)   => {
+            let items: Vec<String> = items.iter().map(format_value).collect();
+            format!("({})", items.join(", "))
+        }
+        Value::Struct { name, fields } => {
+            let parts: Vec<String> = fields.iter()
+                .map(|(n, v)| format!("{n}: {}", format_value(v)))
+                .collect();
+            format!("{name} {{ {} }}", parts.join(", "))
+        }
+        Value::EnumVariant { enum_name, variant } => format!("{enum_name}.{variant}"),
+        Value::ResOk(inner)   => format!("ok({})", format_value(inner)),
+        Value::ResErr(msg)    => format!("error({msg})"),
+        Value::NativeFn(name) => format!("fn {name}"),
+        Value::State(fields)  => {
+            let fields = fields.borrow();
+            let mut names: Vec<&String> = fields.keys().collect();
+            names.sort();
+            let parts: Vec<String> = names.iter()
+                .map(|n| format!("{n}: {}", format_value(&fields[*n])))
+                .collect();
+            format!("State {{ {} }}", parts.join(", "))
+        }
+        other => format!("<{}>", value_type_name(other)),
+    }
+}
+
 pub(crate) fn value_type_name(v: &Value) -> &'static str {
     match v {
+        Value::Int(_)        => "int",
         Value::Float(_)      => "float",
         Value::Bool(_)       => "bool",
         Value::Str(_)        => "string",
@@ -167,6 +313,10 @@ pub(crate) fn value_type_name(v: &Value) -> &'static str {
         Value::Mat3(_)       => "mat3",
         Value::Mat4(_)       => "mat4",
         Value::List(_)       => "list",
+        Value::Map(_)        => "map",
+        Value::Tuple(_)      => "tuple",
+        Value::Struct { .. } => "struct",
+        Value::EnumVariant { .. } => "enum",
         Value::Shape(_)      => "shape",
         Value::Transform(_)  => "transform",
         Value::RenderMode(_) => "render_mode",
@@ -22,6 +22,11 @@ impl NamespaceInfo for RenderNamespace {
                 kind: ExportKind::Function,
                 ty: Type::Fn(vec![Type::Float], Some(Box::new(named("render_mode")))),
             },
+            Export {
+                name: "dashed",
+                kind: ExportKind::Function,
+                ty: Type::Fn(vec![Type::Float, Type::Float, Type::Float], Some(Box::new(named("render_mode")))),
+            },
             Export { name: "gl", kind: ExportKind::Constant, ty: named("gl") },
         ]
     }
@@ -41,6 +46,16 @@ impl NamespaceProvider for RenderNamespace {
                 check_argc(name, args, 1, line)?;
                 Ok(Some(Value::RenderMode(RenderMode::Stroke(as_float(&args[0], line)?))))
             }
+            "dashed" => {
+                check_argc(name, args, 3, line)?;
+                let width = as_float(&args[0], line)?;
+                let on    = as_float(&args[1], line)?;
+                let off   = as_float(&args[2], line)?;
+                if on <= 0.0 || off < 0.0 {
+                    return Err(RuntimeError::new(line, "dashed: `on` must be positive and `off` non-negative"));
+                }
+                Ok(Some(Value::RenderMode(RenderMode::Dashed { width, on, off })))
+            }
             _ => Ok(None),
         }
     }
@@ -5,7 +5,7 @@ use crate::Value;
 use std::collections::HashMap;
 use super::{
     Export, ExportKind, NamespaceInfo, NamespaceProvider, RuntimeState,
-    as_float, as_vec2, as_vertices, check_argc, render_mode_from_named,
+    as_float, as_vec2, as_vertices, check_argc, color_from_named, render_mode_from_named,
 };
 
 fn named(s: &str) -> Type { Type::Named(s.into()) }
@@ -31,13 +31,12 @@ impl NamespaceInfo for ShapesNamespace {
                 ty: Type::Fn(vec![Type::List(Box::new(named("vec2")))], Some(Box::new(named("polygon")))) },
             Export { name: "shape",   kind: ExportKind::Function,
                 ty: Type::Fn(vec![Type::List(Box::new(named("vec2")))], Some(Box::new(named("polygon")))) },
+            Export { name: "wedge",   kind: ExportKind::Function,
+                ty: Type::Fn(vec![named("vec2"), Type::Float, Type::Float, Type::Float], Some(Box::new(named("wedge")))) },
+        ].into_iter()
             // Origin constants
-            origin_const("center"),
-            origin_const("top_left"), origin_const("top_right"),
-            origin_const("bottom_left"), origin_const("bottom_right"),
-            origin_const("top"), origin_const("bottom"),
-            origin_const("left"), origin_const("right"),
-        ]
+            .chain(Origin::ALL.into_iter().map(|o| origin_const(o.name())))
+            .collect()
     }
 }
 
@@ -52,6 +51,16 @@ impl NamespaceProvider for ShapesNamespace {
     ) -> Result<Option<Value>, RuntimeError> {
         let coord_meta  = state.coord_meta.clone();
         let render_mode = render_mode_from_named(named_args, line)?;
+        let fill         = color_from_named(named_args, "fill", line)?;
+        let stroke_color = color_from_named(named_args, "stroke_color", line)?;
+        let layer = match named_args.get("layer") {
+            Some(v) => as_float(v, line)?,
+            None    => 0.0,
+        };
+        let alpha = match named_args.get("alpha") {
+            Some(v) => as_float(v, line)?.clamp(0.0, 1.0),
+            None    => 1.0,
+        };
 
         let desc = match name {
             "circle" => {
@@ -77,34 +86,33 @@ impl NamespaceProvider for ShapesNamespace {
                 check_argc(name, args, 1, line)?;
                 ShapeDesc::Polygon(as_vertices(&args[0], line)?)
             }
+            "wedge" => {
+                check_argc(name, args, 4, line)?;
+                let center      = as_vec2(&args[0], line)?;
+                let radius      = as_float(&args[1], line)?;
+                let start_angle = as_float(&args[2], line)?;
+                let end_angle   = as_float(&args[3], line)?;
+                ShapeDesc::Wedge { center, radius, start_angle, end_angle }
+            }
             _ => return Ok(None),
         };
 
-        Ok(Some(Value::Shape(ShapeData::new(desc, render_mode, coord_meta))))
+        let mut data = ShapeData::new(desc, render_mode, coord_meta);
+        data.fill = fill;
+        data.stroke_color = stroke_color;
+        data.layer = layer;
+        data.alpha = alpha;
+        Ok(Some(Value::Shape(data)))
     }
 
     fn get_constant(&self, name: &str) -> Option<Value> {
-        match name {
-            "center" | "top_left" | "top_right" | "bottom_left" | "bottom_right"
-            | "top" | "bottom" | "left" | "right" => Some(Value::Str(name.into())),
-            _ => None,
-        }
+        Origin::from_name(name).map(|_| Value::Str(name.into()))
     }
 }
 
 fn origin_from_named(named: &HashMap<String, Value>) -> Origin {
     match named.get("origin") {
-        Some(Value::Str(s)) => match s.as_str() {
-            "top_left"     => Origin::TopLeft,
-            "top_right"    => Origin::TopRight,
-            "bottom_left"  => Origin::BottomLeft,
-            "bottom_right" => Origin::BottomRight,
-            "top"          => Origin::Top,
-            "bottom"       => Origin::Bottom,
-            "left"         => Origin::Left,
-            "right"        => Origin::Right,
-            _              => Origin::Center,
-        },
+        Some(Value::Str(s)) => Origin::from_name(s).unwrap_or_default(),
         _ => Origin::Center,
     }
 }
@@ -0,0 +1,79 @@
+//! Constant folding for top-level `const` declarations.
+//!
+//! `const PI2 = PI * 2.0` is evaluated once at compile time instead of every
+//! time the top-level statements run. Only pure expressions fold: literals,
+//! operators, ternaries, core constants and earlier folded consts. Anything
+//! containing a call — or that fails to evaluate, like `1.0 / 0.0` — is left
+//! to the interpreter so errors still surface at runtime.
+
+use crate::syntax::ast::{Expr, Item, Program, Stmt, UnOp};
+use crate::types::binop_registry::BinopRegistry;
+use crate::runtime::interpreter::{eval_binop, eval_unop, parse_hex_color};
+use crate::namespaces::NamespaceRegistry;
+use crate::Value;
+use crate::runtime::value::coerce;
+use std::collections::HashMap;
+
+/// Fold every top-level `const` whose initializer is pure, in declaration order.
+/// Returns the folded values by name.
+pub fn fold_constants(program: &Program, registry: &NamespaceRegistry) -> HashMap<String, Value> {
+    let binops = BinopRegistry::default();
+    let mut folded = HashMap::new();
+    for item in &program.items {
+        if let Item::Stmt(Stmt::VarDecl(v)) = item
+            && v.is_const
+            && let Some(val) = fold_expr(&v.initializer, &folded, registry, &binops)
+        {
+            let val = match &v.ty { Some(ty) => coerce(val, ty), None => val };
+            folded.insert(v.name.clone(), val);
+        }
+    }
+    folded
+}
+
+fn fold_expr(
+    expr: &Expr,
+    folded: &HashMap<String, Value>,
+    registry: &NamespaceRegistry,
+    binops: &BinopRegistry,
+) -> Option<Value> {
+    let fold = |e: &Expr| fold_expr(e, folded, registry, binops);
+    match expr {
+        Expr::Int(v, _)      => Some(Value::Int(*v)),
+        Expr::Float(v, _)    => Some(Value::Float(*v)),
+        Expr::Bool(v, _)     => Some(Value::Bool(*v)),
+        Expr::HexColor(s, _) => parse_hex_color(s).ok(),
+        Expr::Ident(name, _) => folded.get(name).cloned().or_else(|| registry.get_constant(name)),
+        Expr::UnOp { op: op @ (UnOp::Neg | UnOp::Not), operand, span } => {
+            eval_unop(op, fold(operand)?, span.line).ok()
+        }
+        Expr::BinOp { left, op, right, span } => {
+            eval_binop(op, fold(left)?, fold(right)?, span.line, binops).ok()
+        }
+        Expr::CompareChain { operands, ops, span } => {
+            let mut left = fold(&operands[0])?;
+            for (op, operand) in ops.iter().zip(&operands[1..]) {
+                let right = fold(operand)?;
+                match eval_binop(op, left, right.clone(), span.line, binops).ok()? {
+                    Value::Bool(true) => left = right,
+                    other => return Some(other),
+                }
+            }
+            Some(Value::Bool(true))
+        }
+        Expr::Ternary { condition, then_expr, else_expr, .. } => {
+            let (taken, other) = match fold(condition)? {
+                Value::Bool(true)  => (then_expr, else_expr),
+                Value::Bool(false) => (else_expr, then_expr),
+                _ => return None,
+            };
+            // The untaken branch still decides the type: `int` and `float`
+            // branches unify to `float`.
+            match (fold(taken)?, fold(other)?) {
+                (Value::Int(n), Value::Float(_)) => Some(Value::Float(n as f64)),
+                (v, _) => Some(v),
+            }
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,3 @@
+pub mod fold;
+
+pub use fold::fold_constants;
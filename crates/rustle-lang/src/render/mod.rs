@@ -0,0 +1,7 @@
+//! Renderer-independent geometry for draw commands.
+
+pub mod tessellate;
+pub mod triangulate;
+
+pub use tessellate::{CIRCLE_SEGMENTS, centroid, tessellate_ndc, tessellate_screen_px, wedge_points};
+pub use triangulate::{is_convex, triangulate};
@@ -0,0 +1,203 @@
+//! Shape tessellation — turns a `ShapeData` into outline vertices with its
+//! transforms applied, in screen pixels or NDC. Shared by the app's canvas and
+//! any headless consumer.
+
+use crate::types::draw::{Origin, ShapeData, ShapeDesc};
+
+/// Vertices used to approximate a circle outline.
+pub const CIRCLE_SEGMENTS: usize = 64;
+
+/// Average of all vertices — the default transform pivot.
+pub fn centroid(verts: &[(f64, f64)]) -> (f64, f64) {
+    let n = verts.len() as f64;
+    let (sum_x, sum_y) = verts.iter().fold((0.0, 0.0), |(ax, ay), (x, y)| (ax + x, ay + y));
+    (sum_x / n, sum_y / n)
+}
+
+/// Outline of a wedge in user space: the center, then the arc from `start` to
+/// `end`. Arcs get a share of `CIRCLE_SEGMENTS` proportional to their sweep,
+/// and a sweep beyond a full turn is clamped to one.
+pub fn wedge_points(center: (f64, f64), radius: f64, start: f64, end: f64) -> Vec<(f64, f64)> {
+    let tau = std::f64::consts::TAU;
+    let sweep = (end - start).clamp(-tau, tau);
+    let segments = ((sweep.abs() / tau * CIRCLE_SEGMENTS as f64).ceil() as usize).max(1);
+    let mut pts = Vec::with_capacity(segments + 2);
+    pts.push(center);
+    pts.extend((0..=segments).map(|i| {
+        let t = start + sweep * i as f64 / segments as f64;
+        (center.0 + radius * t.cos(), center.1 + radius * t.sin())
+    }));
+    pts
+}
+
+/// Return screen pixel vertices (0,0 = top-left, y-down).
+pub fn tessellate_screen_px(data: &ShapeData) -> Vec<(f64, f64)> {
+    let m = &data.coord_meta;
+    let sx = |x: f64| m.x_to_screen_px(x);
+    let sy = |y: f64| m.y_to_screen_px(y);
+
+    let verts = match &data.desc {
+        ShapeDesc::Circle { center, radius } => {
+            (0..CIRCLE_SEGMENTS).map(|i| {
+                let t = i as f64 / CIRCLE_SEGMENTS as f64 * std::f64::consts::TAU;
+                (sx(center.0 + radius * t.cos()), sy(center.1 + radius * t.sin()))
+            }).collect()
+        }
+        ShapeDesc::Rect { center, size, origin } => {
+            let (w, h) = (size.0 * m.unit_px(), size.1 * m.unit_px());
+            let (ax, ay) = (sx(center.0), sy(center.1));
+            let (min_x, max_x) = match origin {
+                Origin::TopLeft | Origin::BottomLeft | Origin::Left
+                    => (ax, ax + w),
+                Origin::TopRight | Origin::BottomRight | Origin::Right
+                    => (ax - w, ax),
+                Origin::Center | Origin::Top | Origin::Bottom
+                    => (ax - w / 2.0, ax + w / 2.0),
+            };
+            let (min_y, max_y) = match origin {
+                Origin::TopLeft | Origin::TopRight | Origin::Top
+                    => (ay, ay + h),
+                Origin::BottomLeft | Origin::BottomRight | Origin::Bottom
+                    => (ay - h, ay),
+                Origin::Center | Origin::Left | Origin::Right
+                    => (ay - h / 2.0, ay + h / 2.0),
+            };
+            vec![
+                (min_x, min_y), (max_x, min_y),
+                (max_x, max_y), (min_x, max_y),
+            ]
+        }
+        ShapeDesc::Line { from, to } => vec![
+            (sx(from.0), sy(from.1)),
+            (sx(to.0),   sy(to.1)),
+        ],
+        ShapeDesc::Polygon(pts) => pts.iter()
+            .map(|(x, y)| (sx(*x), sy(*y)))
+            .collect(),
+        ShapeDesc::Wedge { center, radius, start_angle, end_angle } => {
+            wedge_points(*center, *radius, *start_angle, *end_angle).into_iter()
+                .map(|(x, y)| (sx(x), sy(y)))
+                .collect()
+        }
+    };
+
+    // Apply transforms in screen pixel space.
+    // td.tx / td.ty are in user-space units — convert to screen px deltas:
+    //   x-right origins flip the x direction, y-up origins flip the y direction.
+    let x_sign: f64 = match m.origin {
+        Origin::TopRight | Origin::BottomRight | Origin::Right => -1.0,
+        _ => 1.0,
+    };
+    let y_sign: f64 = if m.is_y_down() { 1.0 } else { -1.0 };
+    let unit = m.unit_px();
+
+    let mut result = verts;
+    for td in &data.transforms {
+        let tx_px = td.tx * unit * x_sign;
+        let ty_px = td.ty * unit * y_sign;
+        let (pivot_x, pivot_y) = match td.pivot {
+            Some((px, py)) => (sx(px), sy(py)),
+            None => centroid(&result),
+        };
+        // Rotation angle: td.angle is CCW in math (y-up) space.
+        // In screen pixels (y-down) the y axis is flipped, so CCW math = CW visually.
+        // To keep the same visual rotation as NDC, negate the angle.
+        let a = -td.angle;
+        let (cos_a, sin_a) = (a.cos(), a.sin());
+        // Shear is defined in user space; each flipped axis flips its sign in px.
+        let sh_sign = x_sign * y_sign;
+        result = result.into_iter().map(|(x, y)| {
+            let dx = (x - pivot_x) * td.sx;
+            let dy = (y - pivot_y) * td.sy;
+            let (dx, dy) = (dx + td.shx * sh_sign * dy, dy + td.shy * sh_sign * dx);
+            let rx = dx * cos_a - dy * sin_a;
+            let ry = dx * sin_a + dy * cos_a;
+            (pivot_x + rx + tx_px, pivot_y + ry + ty_px)
+        }).collect();
+    }
+    result
+}
+
+/// Convert a ShapeData to NDC vertices (y-up, [-1, 1] across the canvas).
+pub fn tessellate_ndc(data: &ShapeData) -> Vec<(f64, f64)> {
+    let m = &data.coord_meta;
+    let verts = match &data.desc {
+        ShapeDesc::Circle { center, radius } => {
+            let cx = m.x_to_ndc(center.0);
+            let cy = m.y_to_ndc(center.1);
+            // Separate radii per axis so the circle stays round in pixels.
+            let (rx, ry) = (m.w_to_ndc(*radius), m.h_to_ndc(*radius));
+            (0..CIRCLE_SEGMENTS).map(|i| {
+                let t = i as f64 / CIRCLE_SEGMENTS as f64 * std::f64::consts::TAU;
+                (cx + rx * t.cos(), cy + ry * t.sin())
+            }).collect()
+        }
+        ShapeDesc::Rect { center, size, origin } => {
+            let (w, h) = (size.0 * m.unit_px(), size.1 * m.unit_px());
+            let ax = m.x_to_screen_px(center.0);
+            let ay = m.y_to_screen_px(center.1);
+            let (min_x, max_x) = match origin {
+                Origin::TopLeft | Origin::BottomLeft | Origin::Left
+                    => (ax, ax + w),
+                Origin::TopRight | Origin::BottomRight | Origin::Right
+                    => (ax - w, ax),
+                Origin::Center | Origin::Top | Origin::Bottom
+                    => (ax - w / 2.0, ax + w / 2.0),
+            };
+            let (min_y, max_y) = match origin {
+                Origin::TopLeft | Origin::TopRight | Origin::Top
+                    => (ay, ay + h),
+                Origin::BottomLeft | Origin::BottomRight | Origin::Bottom
+                    => (ay - h, ay),
+                Origin::Center | Origin::Left | Origin::Right
+                    => (ay - h / 2.0, ay + h / 2.0),
+            };
+            let snx = |s: f64| if m.px_width  > 0.0 { 2.0 * s / m.px_width  - 1.0 } else { s };
+            let sny = |s: f64| if m.px_height > 0.0 { 1.0 - 2.0 * s / m.px_height } else { s };
+            vec![
+                (snx(min_x), sny(min_y)), (snx(max_x), sny(min_y)),
+                (snx(max_x), sny(max_y)), (snx(min_x), sny(max_y)),
+            ]
+        }
+        ShapeDesc::Line { from, to } => vec![
+            (m.x_to_ndc(from.0), m.y_to_ndc(from.1)),
+            (m.x_to_ndc(to.0),   m.y_to_ndc(to.1)),
+        ],
+        ShapeDesc::Polygon(pts) => pts.iter()
+            .map(|(x, y)| (m.x_to_ndc(*x), m.y_to_ndc(*y)))
+            .collect(),
+        ShapeDesc::Wedge { center, radius, start_angle, end_angle } => {
+            wedge_points(*center, *radius, *start_angle, *end_angle).into_iter()
+                .map(|(x, y)| (m.x_to_ndc(x), m.y_to_ndc(y)))
+                .collect()
+        }
+    };
+
+    // Apply accumulated transforms in NDC space.
+    // Shear is defined in user space; each axis flipped relative to NDC flips its sign.
+    let ndc_x_sign: f64 = match m.origin {
+        Origin::TopRight | Origin::BottomRight | Origin::Right => -1.0,
+        _ => 1.0,
+    };
+    let ndc_y_sign: f64 = if m.is_y_down() { -1.0 } else { 1.0 };
+    let sh_sign = ndc_x_sign * ndc_y_sign;
+    let mut result = verts;
+    for td in &data.transforms {
+        let tx = m.w_to_ndc(td.tx);
+        let ty = m.dy_to_ndc(td.ty);
+        let (cos_a, sin_a) = (td.angle.cos(), td.angle.sin());
+        let (pivot_x, pivot_y) = match td.pivot {
+            Some((px, py)) => (m.x_to_ndc(px), m.y_to_ndc(py)),
+            None => centroid(&result),
+        };
+        result = result.into_iter().map(|(x, y)| {
+            let dx = (x - pivot_x) * td.sx;
+            let dy = (y - pivot_y) * td.sy;
+            let (dx, dy) = (dx + td.shx * sh_sign * dy, dy + td.shy * sh_sign * dx);
+            let rx = dx * cos_a - dy * sin_a;
+            let ry = dx * sin_a + dy * cos_a;
+            (pivot_x + rx + tx, pivot_y + ry + ty)
+        }).collect();
+    }
+    result
+}
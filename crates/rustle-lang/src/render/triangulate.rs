@@ -0,0 +1,91 @@
+//! Fill triangulation for outlines that are not convex — polygons, wedges
+//! wider than a half turn, and boolean results whose holes are joined to the
+//! outline by zero-width seams.
+
+type Pt = (f64, f64);
+
+/// Twice the signed area of `a → b → c`; positive when it turns left.
+fn cross(a: Pt, b: Pt, c: Pt) -> f64 {
+    (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+}
+
+/// Whether every corner of the outline turns the same way.
+pub fn is_convex(pts: &[Pt]) -> bool {
+    let n = pts.len();
+    let (mut left, mut right) = (false, false);
+    for i in 0..n {
+        let turn = cross(pts[i], pts[(i + 1) % n], pts[(i + 2) % n]);
+        left |= turn > 0.0;
+        right |= turn < 0.0;
+    }
+    !(left && right)
+}
+
+/// Ear-clipping triangulation of a closed outline, as index triples into
+/// `pts`. Either winding is accepted. Vertices that coincide — the two ends of
+/// a seam — never block an ear, so seamed holes stay empty. If no ear can be
+/// found (a self-intersecting outline), the rest is filled as a fan.
+pub fn triangulate(pts: &[Pt]) -> Vec<[usize; 3]> {
+    let n = pts.len();
+    if n < 3 {
+        return Vec::new();
+    }
+    let twice_area: f64 = (0..n).map(|i| {
+        let (a, b) = (pts[i], pts[(i + 1) % n]);
+        a.0 * b.1 - b.0 * a.1
+    }).sum();
+    let mut idx: Vec<usize> = (0..n).collect();
+    if twice_area < 0.0 {
+        idx.reverse();
+    }
+
+    let mut tris = Vec::with_capacity(n - 2);
+    while idx.len() > 3 {
+        let m = idx.len();
+        let ear = (0..m).find(|&k| {
+            let (a, b, c) = (pts[idx[(k + m - 1) % m]], pts[idx[k]], pts[idx[(k + 1) % m]]);
+            cross(a, b, c) > 0.0 && !idx.iter().any(|&j| {
+                let p = pts[j];
+                p != a && p != b && p != c
+                    && cross(a, b, p) > 0.0 && cross(b, c, p) > 0.0 && cross(c, a, p) > 0.0
+            })
+        });
+        let Some(k) = ear else {
+            tris.extend((1..m - 1).map(|k| [idx[0], idx[k], idx[k + 1]]));
+            return tris;
+        };
+        tris.push([idx[(k + m - 1) % m], idx[k], idx[(k + 1) % m]]);
+        idx.remove(k);
+    }
+    tris.push([idx[0], idx[1], idx[2]]);
+    tris
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn covered(pts: &[Pt], tris: &[[usize; 3]]) -> f64 {
+        tris.iter().map(|t| cross(pts[t[0]], pts[t[1]], pts[t[2]]).abs() / 2.0).sum()
+    }
+
+    #[test]
+    fn concave_outline_covers_its_area() {
+        // an L shape, clockwise
+        let l = [(0.0, 0.0), (0.0, 2.0), (1.0, 2.0), (1.0, 1.0), (2.0, 1.0), (2.0, 0.0)];
+        assert!(!is_convex(&l));
+        let tris = triangulate(&l);
+        assert_eq!(tris.len(), 4);
+        assert!((covered(&l, &tris) - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn seamed_hole_stays_empty() {
+        // 4×4 square with a 1×1 hole bridged from (0, 0) to (1, 1)
+        let pts = [
+            (0.0, 0.0), (1.0, 1.0), (1.0, 2.0), (2.0, 2.0), (2.0, 1.0), (1.0, 1.0),
+            (0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0),
+        ];
+        assert!((covered(&pts, &triangulate(&pts)) - 15.0).abs() < 1e-9);
+    }
+}
@@ -7,7 +7,9 @@ use crate::types::draw::DrawCommand;
 use crate::types::binop_registry::BinopRegistry;
 use crate::types::registry::TypeRegistry;
 use crate::error::RuntimeError;
-use crate::namespaces::{value_type_name, NamespaceRegistry, RuntimeState};
+use crate::analysis::checker::type_name;
+use crate::namespaces::{format_interpolated, value_type_name, NamespaceRegistry, RuntimeState};
+use crate::runtime::value::{coerce, values_equal};
 use crate::{Input, State, Value};
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -19,11 +21,17 @@ use std::rc::Rc;
 struct Env {
     scopes: Vec<HashMap<String, Value>>,
     output: Rc<RefCell<Vec<DrawCommand>>>,
+    /// Named channels (`out name << ...`), kept apart from the default output.
+    channels: Rc<RefCell<HashMap<String, Vec<DrawCommand>>>>,
 }
 
 impl Env {
     fn new() -> Self {
-        Self { scopes: vec![HashMap::new()], output: Rc::new(RefCell::new(Vec::new())) }
+        Self {
+            scopes: vec![HashMap::new()],
+            output: Rc::new(RefCell::new(Vec::new())),
+            channels: Rc::new(RefCell::new(HashMap::new())),
+        }
     }
 
     fn push_scope(&mut self) { self.scopes.push(HashMap::new()); }
@@ -35,8 +43,8 @@ impl Env {
 
     fn set(&mut self, name: &str, val: Value) -> bool {
         for scope in self.scopes.iter_mut().rev() {
-            if scope.contains_key(name) {
-                scope.insert(name.to_string(), val);
+            if let Some(old) = scope.get_mut(name) {
+                *old = keep_float(old, val);
                 return true;
             }
         }
@@ -50,8 +58,11 @@ impl Env {
         None
     }
 
-    fn emit(&self, cmd: DrawCommand) {
-        self.output.borrow_mut().push(cmd);
+    fn emit(&self, channel: Option<&str>, cmd: DrawCommand) {
+        match channel {
+            Some(name) => self.channels.borrow_mut().entry(name.to_string()).or_default().push(cmd),
+            None => self.output.borrow_mut().push(cmd),
+        }
     }
 }
 
@@ -60,26 +71,101 @@ impl Env {
 pub struct Interpreter<'a> {
     program: &'a ast::Program,
     registry: &'a NamespaceRegistry,
+    /// `struct` declarations by name, so literals don't rescan the items.
+    structs: HashMap<&'a str, &'a ast::StructDef>,
     binops: BinopRegistry,
     types: TypeRegistry,
     env: Env,
     return_value: Option<Value>,
+    /// Set while an `expr?` early return unwinds to the enclosing function call.
+    propagating: bool,
     runtime_state: RuntimeState,
+    /// Loop bodies (`while` / `for`) left before the run is aborted.
+    iterations_left: u64,
+    /// Nested user function calls currently executing.
+    call_depth: usize,
+    /// Top-level `const` values folded at compile time (see `optimize::fold`).
+    folded: Option<&'a HashMap<String, Value>>,
+    /// Expressions to widen to a `float`-holding type (see `SymbolTable::widened_exprs`).
+    widened_exprs: Option<&'a HashMap<Span, ast::Type>>,
 }
 
+/// Maximum nesting of user function calls before the run is aborted.
+pub const MAX_CALL_DEPTH: usize = 2000;
+
+/// Each call level takes roughly 10 KB of native stack in release builds and
+/// 80 KB in debug. Function bodies run on a fresh heap-allocated segment
+/// whenever less than `STACK_RED_ZONE` is left, so `MAX_CALL_DEPTH` — not the
+/// host thread's stack size — is what stops deep recursion.
+const STACK_RED_ZONE: usize = 256 * 1024;
+const STACK_SEGMENT: usize = 4 * 1024 * 1024;
+
+/// Default loop budget per interpreter run — enough for any real sketch, small
+/// enough that `while true {}` fails instead of hanging.
+pub const DEFAULT_ITERATION_LIMIT: u64 = 10_000_000;
+
+/// Longest range that can be materialized as a list value. Ranges walked
+/// directly by `for` / `foreach` / comprehensions are lazy and not capped.
+pub const MAX_RANGE_LEN: usize = 10_000_000;
+
 impl<'a> Interpreter<'a> {
     pub fn new(program: &'a ast::Program, registry: &'a NamespaceRegistry) -> Self {
+        let structs = program.items.iter().filter_map(|i| match i {
+            Item::StructDef(s) => Some((s.name.as_str(), s)),
+            _ => None,
+        }).collect();
         Self {
             program,
             registry,
+            structs,
             binops: BinopRegistry::default(),
             types: TypeRegistry::default(),
             env: Env::new(),
             return_value: None,
+            propagating: false,
             runtime_state: RuntimeState::default(),
+            iterations_left: DEFAULT_ITERATION_LIMIT,
+            call_depth: 0,
+            folded: None,
+            widened_exprs: None,
+        }
+    }
+
+    /// Cap the total number of `while` / `for` body executions for this run.
+    pub fn with_iteration_limit(mut self, limit: u64) -> Self {
+        self.iterations_left = limit;
+        self
+    }
+
+    /// Use compile-time folded `const` values instead of re-evaluating their
+    /// initializers.
+    pub fn with_folded_consts(mut self, folded: &'a HashMap<String, Value>) -> Self {
+        self.folded = Some(folded);
+        self
+    }
+
+    /// Widen the expressions the checker typed as `float` but that may produce
+    /// an `int` — method arguments landing in a `float` parameter, so
+    /// `xs.push(1)` on a `list[float]` stores a float, mixed-arm ternaries and
+    /// matches, and values assigned into `float` slots.
+    pub fn with_widened_exprs(mut self, widened: &'a HashMap<Span, ast::Type>) -> Self {
+        self.widened_exprs = Some(widened);
+        self
+    }
+
+    /// Apply the checker's `int` → `float` widening to the value of the
+    /// expression at `span`, if it recorded one.
+    fn widen(&self, v: Value, span: &Span) -> Value {
+        match self.widened_exprs.and_then(|w| w.get(span)) {
+            Some(ty) => coerce(v, ty),
+            None => v,
         }
     }
 
+    fn folded_const(&self, name: &str) -> Option<Value> {
+        self.folded?.get(name).cloned()
+    }
+
     /// Seed the interpreter with persisted runtime state (coord_meta, etc.) from
     /// a prior init or tick so that resolution/origin survive across frames.
     pub fn with_runtime_state(mut self, rs: RuntimeState) -> Self {
@@ -88,8 +174,17 @@ impl<'a> Interpreter<'a> {
     }
 
     /// Extract the final runtime state after running (captures resolution/origin calls).
-    pub fn take_runtime_state(&self) -> RuntimeState {
-        self.runtime_state.clone()
+    pub fn take_runtime_state(&mut self) -> RuntimeState {
+        std::mem::take(&mut self.runtime_state)
+    }
+
+    /// Charge one loop iteration against the budget.
+    fn count_iteration(&mut self, line: usize) -> Result<(), RuntimeError> {
+        if self.iterations_left == 0 {
+            return Err(self.err(line, "iteration limit exceeded"));
+        }
+        self.iterations_left -= 1;
+        Ok(())
     }
 
     fn err(&self, line: usize, msg: impl Into<String>) -> RuntimeError {
@@ -100,28 +195,31 @@ impl<'a> Interpreter<'a> {
 
     /// Bind all import declarations into the current environment.
     /// - `import shapes`           → `shapes = Namespace("shapes")`
+    /// - `import shapes as s`      → `s = Namespace("shapes")`
     /// - `import shapes { circle }` → `circle = NativeFn("circle")`
+    /// - `import shapes { circle as c }` → `c = NativeFn("circle")`
+    /// - `import shapes { * }`     → every export, as above
     /// - `import render { sdf }`   → `sdf = RenderMode(Sdf)`  (constant)
     pub fn setup_imports(&mut self) {
         let imports = self.program.imports.clone();
         for import in &imports {
-            if import.members.is_empty() {
-                self.env.declare(&import.namespace, Value::Namespace(import.namespace.clone()));
+            if import.is_whole_namespace() {
+                self.env.declare(import.local_name(), Value::Namespace(import.namespace.clone()));
             } else {
                 let exports: Vec<_> = {
                     let Some(ns) = self.registry.get(&import.namespace) else { continue };
-                    import.members.iter()
-                        .filter_map(|m| ns.get_export(m).map(|e| (m.clone(), e)))
+                    ns.import_members(import).into_iter()
+                        .filter_map(|m| ns.get_export(&m.name).map(|e| (m, e)))
                         .collect()
                 };
                 for (member, export) in exports {
                     use crate::namespaces::ExportKind;
                     let val = match export.kind {
-                        ExportKind::Function => Value::NativeFn(member.clone()),
-                        ExportKind::Constant => self.registry.get_constant(&member)
-                            .unwrap_or(Value::NativeFn(member.clone())),
+                        ExportKind::Function => Value::NativeFn(member.name.clone()),
+                        ExportKind::Constant => self.registry.get_constant(&member.name)
+                            .unwrap_or(Value::NativeFn(member.name.clone())),
                     };
-                    self.env.declare(&member, val);
+                    self.env.declare(member.local_name(), val);
                 }
             }
         }
@@ -133,7 +231,17 @@ impl<'a> Interpreter<'a> {
         self.setup_imports();
         let items = self.program.items.clone();
         for item in &items {
-            if let Item::Stmt(s) = item { self.exec_stmt(s)?; }
+            if let Item::Stmt(s) = item {
+                if let Stmt::VarDecl(v) = s
+                    && v.is_const
+                    && let Some(val) = self.folded_const(&v.name)
+                {
+                    // Folded at compile time — skip re-evaluating the initializer.
+                    self.env.declare(&v.name, val);
+                } else {
+                    self.exec_stmt(s)?;
+                }
+            }
         }
         Ok(())
     }
@@ -149,7 +257,9 @@ impl<'a> Interpreter<'a> {
 
         let state_rc = Rc::new(RefCell::new(state.0));
         let state_val = Value::State(state_rc.clone());
-        let input_val = Value::Input { dt: input.dt };
+        let input_val = Value::Input {
+            dt: input.dt, time: input.time, frame: input.frame, mouse: input.mouse,
+        };
 
         self.env.push_scope();
         if let Some(p) = f.params.first()  { self.env.declare(&p.name, state_val); }
@@ -227,23 +337,38 @@ impl<'a> Interpreter<'a> {
         self.env.output.borrow_mut().drain(..).collect()
     }
 
+    /// Drain the named channels, keyed by channel name.
+    pub fn take_channels(&self) -> HashMap<String, Vec<DrawCommand>> {
+        std::mem::take(&mut *self.env.channels.borrow_mut())
+    }
+
     // ─── Expression evaluator ─────────────────────────────────────────────────
 
     pub fn eval_expr(&mut self, expr: &Expr) -> Result<Value, RuntimeError> {
         match expr {
+            Expr::Int(v, _)       => Ok(Value::Int(*v)),
             Expr::Float(v, _)     => Ok(Value::Float(*v)),
             Expr::Bool(v, _)      => Ok(Value::Bool(*v)),
             Expr::StringLit(s, _) => Ok(Value::Str(s.clone())),
+            Expr::Interpolated(parts, _) => {
+                let mut out = String::new();
+                for part in parts {
+                    out.push_str(&format_interpolated(&self.eval_expr(part)?));
+                }
+                Ok(Value::Str(out))
+            }
             Expr::HexColor(s, _)  => parse_hex_color(s),
 
             Expr::Ident(name, span) => {
                 self.env.get(name)
+                    .or_else(|| self.folded_const(name))
                     .or_else(|| self.registry.get_constant(name))
                     .or_else(|| {
                         // User-defined function used as a first-class value.
                         self.program.items.iter().find_map(|i| match i {
                             Item::FnDef(f) if f.name == *name => Some(Value::Closure {
                                 params: f.params.clone(),
+                                return_ty: f.return_ty.clone(),
                                 body: f.body.clone(),
                                 captured: HashMap::new(),
                             }),
@@ -253,12 +378,39 @@ impl<'a> Interpreter<'a> {
                     .ok_or_else(|| self.err(span.line, format!("undefined: `{name}`")))
             }
 
+            Expr::BinOp { left, op: op @ (BinOp::And | BinOp::Or), right, span } => {
+                // Short-circuit: skip the right operand once the left decides the result.
+                // Non-bool operands still reach eval_binop and fail its type check.
+                let l = self.eval_expr(left)?;
+                match (op, &l) {
+                    (BinOp::And, Value::Bool(false)) | (BinOp::Or, Value::Bool(true)) => Ok(l),
+                    _ => {
+                        let r = self.eval_expr(right)?;
+                        eval_binop(op, l, r, span.line, &self.binops)
+                    }
+                }
+            }
+
             Expr::BinOp { left, op, right, span } => {
                 let l = self.eval_expr(left)?;
                 let r = self.eval_expr(right)?;
                 eval_binop(op, l, r, span.line, &self.binops)
             }
 
+            Expr::CompareChain { operands, ops, span } => {
+                // Stops at the first false link, like `and`.
+                let mut left = self.eval_expr(&operands[0])?;
+                for (op, operand) in ops.iter().zip(&operands[1..]) {
+                    let right = self.eval_expr(operand)?;
+                    let holds = eval_binop(op, left, right.clone(), span.line, &self.binops)?;
+                    if !matches!(holds, Value::Bool(true)) {
+                        return Ok(holds);
+                    }
+                    left = right;
+                }
+                Ok(Value::Bool(true))
+            }
+
             Expr::UnOp { op, operand, span } => {
                 match op {
                     UnOp::PrefixInc | UnOp::PrefixDec | UnOp::PostfixInc | UnOp::PostfixDec => {
@@ -272,24 +424,61 @@ impl<'a> Interpreter<'a> {
             }
 
             Expr::Ternary { condition, then_expr, else_expr, span } => {
-                match self.eval_expr(condition)? {
-                    Value::Bool(true)  => self.eval_expr(then_expr),
-                    Value::Bool(false) => self.eval_expr(else_expr),
-                    _ => Err(self.err(span.line, "ternary condition must be bool")),
+                let v = match self.eval_expr(condition)? {
+                    Value::Bool(true)  => self.eval_expr(then_expr)?,
+                    Value::Bool(false) => self.eval_expr(else_expr)?,
+                    _ => return Err(self.err(span.line, "ternary condition must be bool")),
+                };
+                Ok(self.widen(v, span))
+            }
+
+            Expr::Match { expr, arms, span } => {
+                let scrut = self.eval_expr(expr)?;
+                for arm in arms {
+                    // else arm has no values and always matches
+                    let mut matched = arm.values.is_empty();
+                    for val_expr in &arm.values {
+                        if values_equal(&scrut, &self.eval_expr(val_expr)?) {
+                            matched = true;
+                            break;
+                        }
+                    }
+                    if matched {
+                        let v = self.eval_expr(&arm.result)?;
+                        return Ok(self.widen(v, span));
+                    }
                 }
+                Err(self.err(span.line, "no match arm matched and there is no `else`"))
             }
 
-            Expr::Cast { expr, .. } => self.eval_expr(expr),
+            Expr::Cast { expr, ty, span } => {
+                let v = self.eval_expr(expr)?;
+                cast_value(v, ty, span.line)
+            }
 
             Expr::Try { expr, .. } => {
                 // `try expr` wraps the result into res<T>.
                 // Success → ResOk(value), runtime error → ResErr(message).
                 match self.eval_expr(expr) {
                     Ok(v)  => Ok(Value::ResOk(Box::new(v))),
+                    // an `expr?` early return is control flow, not a failure to catch
+                    Err(e) if self.propagating => Err(e),
                     Err(e) => Ok(Value::ResErr(e.message)),
                 }
             }
 
+            Expr::Propagate { expr, span } => {
+                match self.eval_expr(expr)? {
+                    Value::ResOk(v) => Ok(*v),
+                    Value::ResErr(msg) => {
+                        self.return_value = Some(Value::ResErr(msg.clone()));
+                        self.propagating = true;
+                        Err(self.err(span.line, msg))
+                    }
+                    other => Err(self.err(span.line, format!("`?` expects res, got {}", value_type_name(&other)))),
+                }
+            }
+
             Expr::Call { callee, args, named_args, span } => {
                 self.eval_call(callee, args, named_args, span)
             }
@@ -297,18 +486,33 @@ impl<'a> Interpreter<'a> {
             Expr::Index { expr, index, span } => {
                 let coll = self.eval_expr(expr)?;
                 let idx  = self.eval_expr(index)?;
-                let i = as_float(&idx, span.line)? as usize;
-                match coll {
-                    Value::List(items) => items.borrow().get(i).cloned()
-                        .ok_or_else(|| self.err(span.line, "index out of bounds")),
-                    _ => Err(self.err(span.line, format!(
-                        "cannot index `{}`", value_type_name(&coll)
-                    ))),
+                index_value(&coll, &idx, span.line)
+            }
+
+            // `Mode.Menu` — unless a variable named `Mode` shadows the enum.
+            Expr::Field { expr, field, span }
+                if let Expr::Ident(name, _) = &**expr
+                    && self.env.get(name).is_none()
+                    && let Some(e) = self.program.items.iter().find_map(|i| match i {
+                        Item::EnumDef(e) if e.name == *name => Some(e),
+                        _ => None,
+                    }) =>
+            {
+                if !e.variants.contains(field) {
+                    return Err(self.err(span.line, format!("enum `{name}` has no variant `{field}`")));
                 }
+                Ok(Value::EnumVariant { enum_name: name.clone(), variant: field.clone() })
             }
 
             Expr::Field { expr, field, span } => {
                 let obj = self.eval_expr(expr)?;
+                // `render.fill` / `co.top_left` — a constant exported by the namespace.
+                if let Value::Namespace(ns) = &obj
+                    && self.registry.get(ns).is_some_and(|n| n.get_export(field).is_some())
+                    && let Some(v) = self.registry.get_constant(field)
+                {
+                    return Ok(v);
+                }
                 eval_field(&self.types, &obj, field, span.line)
             }
 
@@ -327,16 +531,91 @@ impl<'a> Interpreter<'a> {
             }
 
             Expr::List(items, _) => {
-                let vals: Result<Vec<_>, _> = items.iter().map(|e| self.eval_expr(e)).collect();
-                Ok(Value::List(Rc::new(RefCell::new(vals?))))
+                let mut vals = items.iter().map(|e| self.eval_expr(e)).collect::<Result<Vec<_>, _>>()?;
+                // `[1, 2.5]` is a list[float]: widen the int elements.
+                if vals.iter().any(|v| matches!(v, Value::Float(_))) {
+                    vals = vals.into_iter().map(|v| coerce(v, &ast::Type::Float)).collect();
+                }
+                Ok(Value::List(Rc::new(RefCell::new(vals))))
             }
 
-            Expr::Lambda { params, body, .. } => {
+            Expr::StructLit { name, fields, span } => {
+                let decl = self.structs.get(name.as_str()).copied()
+                    .ok_or_else(|| self.err(span.line, format!("undefined struct `{name}`")))?;
+                let mut given = Vec::with_capacity(fields.len());
+                for (field, value) in fields {
+                    given.push((field, self.eval_expr(value)?));
+                }
+                // Declaration order, each value widened to its field's type.
+                let mut vals = Vec::with_capacity(decl.fields.len());
+                for f in &decl.fields {
+                    let v = given.iter().find(|(n, _)| **n == f.name).map(|(_, v)| v.clone())
+                        .ok_or_else(|| self.err(span.line, format!("missing field `{}` in `{name}`", f.name)))?;
+                    vals.push((f.name.clone(), coerce(v, &f.ty)));
+                }
+                Ok(Value::Struct { name: name.clone(), fields: vals })
+            }
+
+            Expr::Map(entries, span) => {
+                let mut map = HashMap::new();
+                for (k, v) in entries {
+                    let key = as_key(&self.eval_expr(k)?, span.line)?;
+                    map.insert(key, self.eval_expr(v)?);
+                }
+                // `{ "a": 1, "b": 2.5 }` is a map[float]: widen the int values.
+                if map.values().any(|v| matches!(v, Value::Float(_))) {
+                    for v in map.values_mut() {
+                        *v = coerce(v.clone(), &ast::Type::Float);
+                    }
+                }
+                Ok(Value::Map(Rc::new(RefCell::new(map))))
+            }
+
+            Expr::Comprehension { expr, var_name, iterable, filter, span } => {
+                let mut out = Vec::new();
+                for item in self.eval_iterable(iterable, "comprehension", span.line)? {
+                    self.count_iteration(span.line)?;
+                    self.env.push_scope();
+                    self.env.declare(var_name, item);
+                    let keep = match filter {
+                        Some(cond) => match self.eval_expr(cond)? {
+                            Value::Bool(b) => b,
+                            other => return Err(self.err(span.line, format!(
+                                "comprehension filter must be bool, got `{}`", value_type_name(&other)
+                            ))),
+                        },
+                        None => true,
+                    };
+                    if keep {
+                        out.push(self.eval_expr(expr)?);
+                    }
+                    self.env.pop_scope();
+                }
+                Ok(Value::List(Rc::new(RefCell::new(out))))
+            }
+
+            Expr::Tuple(items, _) => {
+                let vals = items.iter().map(|e| self.eval_expr(e)).collect::<Result<Vec<_>, _>>()?;
+                Ok(Value::Tuple(vals))
+            }
+
+            Expr::Lambda { params, return_ty, body, .. } => {
                 let captured = self.env.scopes.iter()
                     .flat_map(|s| s.iter())
                     .map(|(k, v)| (k.clone(), v.clone()))
                     .collect();
-                Ok(Value::Closure { params: params.clone(), body: body.clone(), captured })
+                Ok(Value::Closure { params: params.clone(), return_ty: return_ty.clone(), body: body.clone(), captured })
+            }
+
+            Expr::Range { start, end, step, span } => {
+                let (lo, step, n) = self.eval_range_bounds(start, end, step.as_deref(), span.line)?;
+                if n > MAX_RANGE_LEN {
+                    return Err(self.err(span.line, format!(
+                        "range has {n} elements, more than the {MAX_RANGE_LEN} a list can hold"
+                    )));
+                }
+                let items = (0..n).map(|k| Value::Float(lo + k as f64 * step)).collect();
+                Ok(Value::List(Rc::new(RefCell::new(items))))
             }
         }
     }
@@ -365,8 +644,8 @@ impl<'a> Interpreter<'a> {
                     return self.registry.call_any(&n, &arg_vals, &named, &mut self.runtime_state, span.line)?
                         .ok_or_else(|| self.err(span.line, format!("unknown native fn: `{n}`")));
                 }
-                Value::Closure { params, body, captured } => {
-                    return self.call_closure(&params, &body, &captured, &arg_vals, span.line);
+                Value::Closure { params, return_ty, body, captured } => {
+                    return self.call_closure(&params, return_ty.as_ref(), &body, &captured, &arg_vals, span.line);
                 }
                 _ => {} // fall through — may be a user fn with same name
             }
@@ -383,12 +662,24 @@ impl<'a> Interpreter<'a> {
             _ => None,
         });
         if let Some(f) = f {
-            if f.params.len() != arg_vals.len() {
+            if arg_vals.len() > f.params.len() {
                 return Err(self.err(span.line, format!(
                     "`{}` expects {} args, got {}", f.name, f.params.len(), arg_vals.len()
                 )));
             }
-            return self.call_fn(&f.params, &f.body, &arg_vals, span.line);
+            // Positional args fill the leading params, named args bind by name.
+            let mut slots: Vec<Option<Value>> = arg_vals.into_iter().map(Some).collect();
+            slots.resize(f.params.len(), None);
+            for (name, val) in named {
+                let Some(i) = f.params.iter().position(|p| p.name == name) else {
+                    return Err(self.err(span.line, format!("`{}` has no parameter named `{name}`", f.name)));
+                };
+                if slots[i].is_some() {
+                    return Err(self.err(span.line, format!("argument `{name}` is given more than once")));
+                }
+                slots[i] = Some(val);
+            }
+            return self.call_fn(&f.params, f.return_ty.as_ref(), &f.body, slots, span.line);
         }
 
         Err(self.err(span.line, format!("undefined function: `{callee}`")))
@@ -397,29 +688,35 @@ impl<'a> Interpreter<'a> {
     fn call_fn(
         &mut self,
         params: &[Param],
+        return_ty: Option<&ast::Type>,
         body: &[Stmt],
-        arg_vals: &[Value],
-        _line: usize,
+        args: Vec<Option<Value>>,
+        line: usize,
     ) -> Result<Value, RuntimeError> {
         self.env.push_scope();
-        for (p, v) in params.iter().zip(arg_vals) {
-            self.env.declare(&p.name, v.clone());
+        for (p, arg) in params.iter().zip(args) {
+            // Unbound params fall back to the default, evaluated in the new
+            // scope so it can refer to earlier params.
+            let v = match (arg, &p.default) {
+                (Some(v), _)          => v,
+                (None, Some(default)) => self.eval_expr(default)?,
+                (None, None) => return Err(self.err(line, format!("missing argument `{}`", p.name))),
+            };
+            self.env.declare(&p.name, coerce(v, &p.ty));
         }
         let saved = self.return_value.take();
         let body = body.to_vec();
-        for stmt in &body {
-            self.exec_stmt(stmt)?;
-            if self.return_value.is_some() { break; }
-        }
+        self.exec_fn_body(&body, line)?;
         let result = self.return_value.take().unwrap_or(Value::Float(0.0));
         self.return_value = saved;
         self.env.pop_scope();
-        Ok(result)
+        Ok(match return_ty { Some(ty) => coerce(result, ty), None => result })
     }
 
     fn call_closure(
         &mut self,
         params: &[Param],
+        return_ty: Option<&ast::Type>,
         body: &[Stmt],
         captured: &HashMap<String, Value>,
         arg_vals: &[Value],
@@ -432,17 +729,56 @@ impl<'a> Interpreter<'a> {
         }
         self.env.push_scope();
         for (k, v) in captured { self.env.declare(k, v.clone()); }
-        for (p, v) in params.iter().zip(arg_vals) { self.env.declare(&p.name, v.clone()); }
+        for (p, v) in params.iter().zip(arg_vals) { self.env.declare(&p.name, coerce(v.clone(), &p.ty)); }
         let saved = self.return_value.take();
         let body = body.to_vec();
-        for stmt in &body {
-            self.exec_stmt(stmt)?;
-            if self.return_value.is_some() { break; }
-        }
+        self.exec_fn_body(&body, line)?;
         let result = self.return_value.take().unwrap_or(Value::Float(0.0));
         self.return_value = saved;
         self.env.pop_scope();
-        Ok(result)
+        Ok(match return_ty { Some(ty) => coerce(result, ty), None => result })
+    }
+
+    /// Runs a function body until it returns, absorbing an early return raised by `expr?`.
+    /// `line` is the call site, reported if the call nests too deeply.
+    fn exec_fn_body(&mut self, body: &[Stmt], line: usize) -> Result<(), RuntimeError> {
+        if self.call_depth >= MAX_CALL_DEPTH {
+            return Err(self.err(line, format!(
+                "call depth limit exceeded ({MAX_CALL_DEPTH} nested calls) — unbounded recursion?"
+            )));
+        }
+        self.call_depth += 1;
+        let depth = self.env.scopes.len();
+        let result = stacker::maybe_grow(STACK_RED_ZONE, STACK_SEGMENT, || {
+            for stmt in body {
+                if let Err(e) = self.exec_stmt(stmt) {
+                    if !self.propagating { return Err(e); }
+                    self.propagating = false;
+                    self.env.scopes.truncate(depth);
+                    break;
+                }
+                if self.return_value.is_some() { break; }
+            }
+            Ok(())
+        });
+        self.call_depth -= 1;
+        result
+    }
+
+    /// Invoke a first-class function value (closure or imported native fn).
+    fn call_value(&mut self, f: &Value, args: &[Value], line: usize) -> Result<Value, RuntimeError> {
+        match f {
+            Value::Closure { params, return_ty, body, captured } => {
+                self.call_closure(params, return_ty.as_ref(), body, captured, args, line)
+            }
+            Value::NativeFn(name) => {
+                self.registry.call_any(name, args, &HashMap::new(), &mut self.runtime_state, line)?
+                    .ok_or_else(|| self.err(line, format!("unknown native fn: `{name}`")))
+            }
+            other => Err(self.err(line, format!(
+                "expected a function, got `{}`", value_type_name(other)
+            ))),
+        }
     }
 
     // ─── Method dispatch ──────────────────────────────────────────────────────
@@ -487,54 +823,132 @@ impl<'a> Interpreter<'a> {
 
         // All other types: evaluate args, delegate to TypeRegistry.
         let arg_vals: Vec<Value> = args.iter()
-            .map(|a| self.eval_expr(a))
+            .map(|a| {
+                let v = self.eval_expr(a)?;
+                Ok(self.widen(v, a.span()))
+            })
             .collect::<Result<_, _>>()?;
 
+        // Higher-order list methods call back into the interpreter, which the
+        // TypeRegistry's plain fn pointers cannot do.
+        if let Value::List(items) = &obj
+            && let Some(result) = self.eval_list_method(items, method, &arg_vals, span.line)
+        {
+            return result;
+        }
+        if matches!(obj, Value::ResOk(_) | Value::ResErr(_))
+            && let Some(result) = self.eval_res_method(&obj, method, &arg_vals, span.line)
+        {
+            return result;
+        }
+
         self.types.call_method(&obj, method, &arg_vals, span.line)
             .unwrap_or_else(|| Err(self.err(span.line, format!(
                 "`{}` has no method `{method}`", value_type_name(&obj)
             ))))
     }
 
+    fn eval_list_method(
+        &mut self,
+        items: &Rc<RefCell<Vec<Value>>>,
+        method: &str,
+        args: &[Value],
+        line: usize,
+    ) -> Option<Result<Value, RuntimeError>> {
+        // Snapshot the elements so the callback may freely mutate the list.
+        let elems = items.borrow().clone();
+        let result = match (method, args) {
+            ("map", [f]) => elems.iter()
+                .map(|v| self.call_value(f, std::slice::from_ref(v), line))
+                .collect::<Result<Vec<_>, _>>()
+                .map(|out| Value::List(Rc::new(RefCell::new(out)))),
+            ("filter", [f]) => {
+                let mut out = Vec::new();
+                for v in elems {
+                    match self.call_value(f, std::slice::from_ref(&v), line) {
+                        Ok(Value::Bool(true))  => out.push(v),
+                        Ok(Value::Bool(false)) => {}
+                        Ok(other) => return Some(Err(self.err(line, format!(
+                            "filter predicate must return bool, got `{}`", value_type_name(&other)
+                        )))),
+                        Err(e) => return Some(Err(e)),
+                    }
+                }
+                Ok(Value::List(Rc::new(RefCell::new(out))))
+            }
+            ("reduce", [init, f]) => elems.into_iter()
+                .try_fold(init.clone(), |acc, v| self.call_value(f, &[acc, v], line)),
+            _ => return None,
+        };
+        Some(result)
+    }
+
+    fn eval_res_method(
+        &mut self,
+        res: &Value,
+        method: &str,
+        args: &[Value],
+        line: usize,
+    ) -> Option<Result<Value, RuntimeError>> {
+        let result = match (method, res, args) {
+            ("unwrap_or", Value::ResOk(v), [_]) => Ok(*v.clone()),
+            ("unwrap_or", _, [default])         => Ok(default.clone()),
+            ("map", Value::ResOk(v), [f]) => self.call_value(f, std::slice::from_ref(v), line)
+                .map(|out| Value::ResOk(Box::new(out))),
+            // errors pass through untouched
+            ("map", err, [_]) => Ok(err.clone()),
+            _ => return None,
+        };
+        Some(result)
+    }
+
     // ─── Statement executor ───────────────────────────────────────────────────
 
     pub fn exec_stmt(&mut self, stmt: &Stmt) -> Result<(), RuntimeError> {
         match stmt {
             Stmt::VarDecl(v) => {
                 let val = self.eval_expr(&v.initializer)?;
+                let val = match &v.ty { Some(ty) => coerce(val, ty), None => val };
                 self.env.declare(&v.name, val);
             }
 
-            Stmt::Assign(a) => {
-                let val = self.eval_expr(&a.value)?;
-                match &a.target {
-                    AssignTarget::Path(p) if p.len() == 1 => {
-                        let name = &p[0];
-                        if !self.env.set(name, val) {
-                            return Err(self.err(a.span.line, format!("undefined: `{name}`")));
-                        }
-                    }
-                    AssignTarget::Path(p) => {
-                        let root = &p[0];
-                        let obj = self.env.get(root)
-                            .ok_or_else(|| self.err(a.span.line, format!("undefined: `{root}`")))?;
-                        if let Value::State(rc) = &obj {
-                            assign_state_path(rc, &p[1..], val, a.span.line, &self.types)?;
-                        } else {
-                            let updated = set_field_path(&self.types, obj, &p[1..], val, a.span.line)?;
-                            self.env.set(root, updated);
-                        }
-                    }
-                    AssignTarget::Indexed { path: p, indices } => {
-                        self.exec_indexed_assign(p, indices, val, a.span.line)?;
-                    }
+            Stmt::Destructure(d) => {
+                let val = self.eval_expr(&d.initializer)?;
+                let val = match &d.ty { Some(ty) => coerce(val, ty), None => val };
+                let Value::Tuple(items) = val else {
+                    return Err(self.err(d.span.line, format!(
+                        "cannot destructure `{}`, expected a tuple", value_type_name(&val)
+                    )));
+                };
+                if items.len() != d.names.len() {
+                    return Err(self.err(d.span.line, format!(
+                        "cannot destructure a {}-element tuple into {} names", items.len(), d.names.len()
+                    )));
+                }
+                for (name, item) in d.names.iter().zip(items) {
+                    self.env.declare(name, item);
                 }
             }
 
+            Stmt::Assign(a) => match &a.op {
+                None => {
+                    let val = self.eval_expr(&a.value)?;
+                    let val = self.widen(val, a.value.span());
+                    self.write_assign_target(&a.target, val, a.span.line)?;
+                }
+                Some(op) => {
+                    let target = self.pin_target(&a.target, a.span.line)?;
+                    let old = self.read_assign_target(&target, a.span.line)?;
+                    let rhs = self.eval_expr(&a.value)?;
+                    let val = eval_binop(op, old, rhs, a.span.line, &self.binops)?;
+                    self.write_assign_target(&target, val, a.span.line)?;
+                }
+            },
+
             Stmt::Out(o) => {
                 for expr in &o.shapes {
                     match self.eval_expr(expr)? {
-                        Value::Shape(data) => self.env.emit(DrawCommand::DrawShape(data)),
+                        Value::Shape(data) => self.env.emit(o.channel.as_deref(), DrawCommand::DrawShape(data)),
                         other => return Err(self.err(o.span.line, format!(
                             "out << expects shape, got `{}`", value_type_name(&other)
                         ))),
@@ -594,6 +1008,7 @@ impl<'a> Interpreter<'a> {
                         Value::Bool(true)  => {}
                         _ => return Err(self.err(w.span.line, "while condition must be bool")),
                     }
+                    self.count_iteration(w.span.line)?;
                     self.env.push_scope();
                     for s in &w.body {
                         self.exec_stmt(s)?;
@@ -613,6 +1028,7 @@ impl<'a> Interpreter<'a> {
                         Value::Bool(true)  => {}
                         _ => return Err(self.err(f.span.line, "for condition must be bool")),
                     }
+                    self.count_iteration(f.span.line)?;
                     self.env.push_scope();
                     for s in &f.body {
                         self.exec_stmt(s)?;
@@ -626,14 +1042,13 @@ impl<'a> Interpreter<'a> {
             }
 
             Stmt::Foreach(f) => {
-                let list = match self.eval_expr(&f.iterable)? {
-                    Value::List(items) => items.borrow().clone(),
-                    other => return Err(self.err(f.span.line, format!(
-                        "foreach expects list, got `{}`", value_type_name(&other)
-                    ))),
-                };
-                for item in list {
+                let items = self.eval_iterable(&f.iterable, "foreach", f.span.line)?;
+                for (i, item) in items.enumerate() {
+                    self.count_iteration(f.span.line)?;
                     self.env.push_scope();
+                    if let Some(index_name) = &f.index_name {
+                        self.env.declare(index_name, Value::Float(i as f64));
+                    }
                     self.env.declare(&f.var_name, item);
                     for s in &f.body {
                         self.exec_stmt(s)?;
@@ -662,16 +1077,62 @@ impl<'a> Interpreter<'a> {
         Ok(())
     }
 
+    /// Items `iterable` yields, for `foreach` and comprehensions. A literal range
+    /// is walked lazily so `for i in 0..n` never builds the list; anything else is
+    /// a snapshot of the list it evaluates to.
+    fn eval_iterable(
+        &mut self,
+        iterable: &Expr,
+        what: &str,
+        line: usize,
+    ) -> Result<Box<dyn Iterator<Item = Value>>, RuntimeError> {
+        if let Expr::Range { start, end, step, span } = iterable {
+            let (lo, step, n) = self.eval_range_bounds(start, end, step.as_deref(), span.line)?;
+            return Ok(Box::new((0..n).map(move |k| Value::Float(lo + k as f64 * step))));
+        }
+        match self.eval_expr(iterable)? {
+            Value::List(items) => Ok(Box::new(items.borrow().clone().into_iter())),
+            other => Err(self.err(line, format!(
+                "{what} expects list, got `{}`", value_type_name(&other)
+            ))),
+        }
+    }
+
+    /// `(start, step, len)` of `start..end step step`.
+    fn eval_range_bounds(
+        &mut self,
+        start: &Expr,
+        end: &Expr,
+        step: Option<&Expr>,
+        line: usize,
+    ) -> Result<(f64, f64, usize), RuntimeError> {
+        let lo = as_float(&self.eval_expr(start)?, line)?;
+        let hi = as_float(&self.eval_expr(end)?, line)?;
+        let step = match step {
+            Some(e) => as_float(&self.eval_expr(e)?, line)?,
+            None    => 1.0,
+        };
+        if step <= 0.0 {
+            return Err(self.err(line, format!("range step must be positive, got {step}")));
+        }
+        Ok((lo, step, ((hi - lo) / step).ceil().max(0.0) as usize))
+    }
+
     fn eval_inc_dec(&mut self, op: &UnOp, operand: &Expr, span: &Span) -> Result<Value, RuntimeError> {
         let target = expr_to_assign_target(operand)
             .ok_or_else(|| self.err(span.line, "`++`/`--` require an assignable expression"))?;
+        let target = self.pin_target(&target, span.line)?;
         let old = self.read_assign_target(&target, span.line)?;
-        let x = as_float(&old, span.line)?;
-        let new_val = Value::Float(match op {
-            UnOp::PrefixInc | UnOp::PostfixInc => x + 1.0,
-            UnOp::PrefixDec | UnOp::PostfixDec => x - 1.0,
-            _ => unreachable!(),
-        });
+        let inc = matches!(op, UnOp::PrefixInc | UnOp::PostfixInc);
+        let new_val = match old {
+            Value::Int(n) => if inc { n.checked_add(1) } else { n.checked_sub(1) }
+                .map(Value::Int)
+                .ok_or_else(|| self.err(span.line, "integer overflow"))?,
+            _ => {
+                let x = as_float(&old, span.line)?;
+                Value::Float(if inc { x + 1.0 } else { x - 1.0 })
+            }
+        };
         self.write_assign_target(&target, new_val.clone(), span.line)?;
         Ok(match op {
             UnOp::PrefixInc | UnOp::PrefixDec => new_val,
@@ -680,6 +1141,21 @@ impl<'a> Interpreter<'a> {
         })
     }
 
+    /// Evaluates an indexed target's index expressions once and swaps in their
+    /// values, so a read-modify-write (`xs[next()] += 1`, `xs[next()]++`) runs them once.
+    fn pin_target(&mut self, target: &AssignTarget, line: usize) -> Result<AssignTarget, RuntimeError> {
+        let AssignTarget::Indexed { path, indices } = target else { return Ok(target.clone()); };
+        let mut pinned = Vec::with_capacity(indices.len());
+        for idx_expr in indices {
+            let span = idx_expr.span().clone();
+            pinned.push(match self.eval_expr(idx_expr)? {
+                Value::Str(key) => Expr::StringLit(key, span),
+                idx => Expr::Int(as_index(&idx, line)?, span),
+            });
+        }
+        Ok(AssignTarget::Indexed { path: path.clone(), indices: pinned })
+    }
+
     fn read_assign_target(&mut self, target: &AssignTarget, line: usize) -> Result<Value, RuntimeError> {
         match target {
             AssignTarget::Path(p) if p.len() == 1 => self.env.get(&p[0])
@@ -702,14 +1178,7 @@ impl<'a> Interpreter<'a> {
                 }
                 for idx_expr in indices {
                     let idx = self.eval_expr(idx_expr)?;
-                    let i = as_float(&idx, line)? as usize;
-                    coll = match &coll {
-                        Value::List(items) => items.borrow().get(i).cloned()
-                            .ok_or_else(|| self.err(line, "index out of bounds")),
-                        _ => Err(self.err(line, format!(
-                            "cannot index `{}`", value_type_name(&coll)
-                        ))),
-                    }?;
+                    coll = index_value(&coll, &idx, line)?;
                 }
                 Ok(coll)
             }
@@ -757,25 +1226,25 @@ impl<'a> Interpreter<'a> {
         }
         for idx_expr in &indices[..indices.len().saturating_sub(1)] {
             let idx = self.eval_expr(idx_expr)?;
-            let i = as_float(&idx, line)? as usize;
-            coll = match &coll {
-                Value::List(items) => items.borrow().get(i).cloned()
-                    .ok_or_else(|| self.err(line, "index out of bounds")),
-                _ => Err(self.err(line, format!(
-                    "cannot index `{}`", value_type_name(&coll)
-                ))),
-            }?;
+            coll = index_value(&coll, &idx, line)?;
         }
         let last_idx = indices.last().unwrap();
         let idx = self.eval_expr(last_idx)?;
-        let i = as_float(&idx, line)? as usize;
         match &coll {
             Value::List(items) => {
+                let i = as_index(&idx, line)?;
                 let mut guard = items.borrow_mut();
-                if i >= guard.len() {
+                let Some(i) = resolve_index(i, guard.len()) else {
                     return Err(self.err(line, "index out of bounds"));
-                }
-                guard[i] = val;
+                };
+                guard[i] = keep_float(&guard[i], val);
+            }
+            // Assigning to a missing key inserts it.
+            Value::Map(entries) => {
+                let key = as_key(&idx, line)?;
+                let mut guard = entries.borrow_mut();
+                let val = match guard.get(&key) { Some(old) => keep_float(old, val), None => val };
+                guard.insert(key, val);
             }
             _ => return Err(self.err(line, format!(
                 "cannot assign to index of `{}`", value_type_name(&coll)
@@ -785,6 +1254,41 @@ impl<'a> Interpreter<'a> {
     }
 }
 
+/// `coll[idx]` — a list by position, or a map by key.
+fn index_value(coll: &Value, idx: &Value, line: usize) -> Result<Value, RuntimeError> {
+    match coll {
+        Value::List(items) => {
+            let items = items.borrow();
+            resolve_index(as_index(idx, line)?, items.len()).map(|i| items[i].clone())
+                .ok_or_else(|| RuntimeError::new(line, "index out of bounds"))
+        }
+        Value::Map(entries) => {
+            let key = as_key(idx, line)?;
+            entries.borrow().get(&key).cloned()
+                .ok_or_else(|| RuntimeError::new(line, format!("map has no key \"{key}\"")))
+        }
+        _ => Err(RuntimeError::new(line, format!(
+            "cannot index `{}`", value_type_name(coll)
+        ))),
+    }
+}
+
+fn as_key(v: &Value, line: usize) -> Result<String, RuntimeError> {
+    match v {
+        Value::Str(s) => Ok(s.clone()),
+        _ => Err(RuntimeError::new(line, format!(
+            "map key must be string, got `{}`", value_type_name(v)
+        ))),
+    }
+}
+
+/// Resolve a list index against `len` elements. Negative values count from
+/// the end (`-1` is the last element).
+fn resolve_index(i: i64, len: usize) -> Option<usize> {
+    let i = if i < 0 { i + len as i64 } else { i };
+    (0..len as i64).contains(&i).then_some(i as usize)
+}
+
 /// Convert an assignable Expr (Ident, Field, Index) to AssignTarget.
 fn expr_to_assign_target(expr: &Expr) -> Option<AssignTarget> {
     match expr {
@@ -837,7 +1341,9 @@ fn assign_state_path(
 ) -> Result<(), RuntimeError> {
     let field = &path[0];
     if path.len() == 1 {
-        rc.borrow_mut().insert(field.clone(), val);
+        let mut fields = rc.borrow_mut();
+        let val = match fields.get(field) { Some(old) => keep_float(old, val), None => val };
+        fields.insert(field.clone(), val);
     } else {
         let intermediate = rc.borrow().get(field.as_str()).cloned()
             .ok_or_else(|| RuntimeError::new(line, format!("state has no field `{field}`")))?;
@@ -859,8 +1365,12 @@ fn set_field_path(types: &TypeRegistry, obj: Value, path: &[String], val: Value,
                 "`{}` has no field `{field}`", value_type_name(&obj)
             )))?;
         set_field_path(types, sub, &path[1..], val, line)?
+    } else if let Some(old) = types.get_field(&obj, field).filter(|_| matches!(obj, Value::Struct { .. })) {
+        // Struct fields keep their declared type.
+        keep_float(&old, val)
     } else {
-        val
+        // Built-in fields (`v.x`, `c.r`) are all float.
+        coerce(val, &ast::Type::Float)
     };
     types.set_field(obj, field, new_val)
         .ok_or_else(|| RuntimeError::new(line, format!(
@@ -885,9 +1395,43 @@ fn apply_transform(shape: Value, tf: Value, line: usize) -> Result<Value, Runtim
     Ok(Value::Shape(data))
 }
 
+// ─── Casts ────────────────────────────────────────────────────────────────────
+
+fn cast_value(v: Value, ty: &ast::Type, line: usize) -> Result<Value, RuntimeError> {
+    use ast::Type;
+    match (v, ty) {
+        (Value::Float(x), Type::Bool)  => Ok(Value::Bool(x != 0.0)),
+        (Value::Bool(b),  Type::Float) => Ok(Value::Float(if b { 1.0 } else { 0.0 })),
+        (Value::Int(n),   Type::Float) => Ok(Value::Float(n as f64)),
+        (Value::Int(n),   Type::Bool)  => Ok(Value::Bool(n != 0)),
+        (Value::Bool(b),  Type::Int)   => Ok(Value::Int(b as i64)),
+        // Truncates toward zero; NaN, infinities and out-of-range values are errors.
+        (Value::Float(x), Type::Int) => {
+            let t = x.trunc();
+            if t.is_finite() && t >= i64::MIN as f64 && t < i64::MAX as f64 {
+                Ok(Value::Int(t as i64))
+            } else {
+                Err(RuntimeError::new(line, format!("cannot cast {x} to `int`: out of range")))
+            }
+        }
+        (v @ Value::Float(_), Type::Float) | (v @ Value::Bool(_), Type::Bool)
+        | (v @ Value::Int(_), Type::Int) => Ok(v),
+        (Value::Vec2(x, y), Type::Named(n)) if n == "vec3" => Ok(Value::Vec3(x, y, 0.0)),
+        (Value::Vec3(x, y, _), Type::Named(n)) if n == "vec2" => Ok(Value::Vec2(x, y)),
+        (Value::Color { r, g, b, a }, Type::Named(n)) if n == "vec4" => Ok(Value::Vec4(r, g, b, a)),
+        (Value::Vec4(r, g, b, a), Type::Named(n)) if n == "color" => Ok(Value::Color { r, g, b, a }),
+        (v @ Value::Struct { .. }, Type::Named(n)) if matches!(&v, Value::Struct { name, .. } if name == n) => Ok(v),
+        (v @ Value::EnumVariant { .. }, Type::Named(n)) if matches!(&v, Value::EnumVariant { enum_name, .. } if enum_name == n) => Ok(v),
+        (v, Type::Named(n)) if value_type_name(&v) == n => Ok(v),
+        (v, ty) => Err(RuntimeError::new(line, format!(
+            "cannot cast `{}` to `{}`", value_type_name(&v), type_name(ty)
+        ))),
+    }
+}
+
 // ─── Binary / unary operators ─────────────────────────────────────────────────
 
-fn eval_binop(op: &BinOp, l: Value, r: Value, line: usize, binops: &BinopRegistry) -> Result<Value, RuntimeError> {
+pub(crate) fn eval_binop(op: &BinOp, l: Value, r: Value, line: usize, binops: &BinopRegistry) -> Result<Value, RuntimeError> {
     // Eq / NotEq — generic structural equality, no registry needed
     if let BinOp::Eq   = op { return Ok(Value::Bool(values_equal(&l, &r))); }
     if let BinOp::NotEq = op { return Ok(Value::Bool(!values_equal(&l, &r))); }
@@ -899,6 +1443,7 @@ fn eval_binop(op: &BinOp, l: Value, r: Value, line: usize, binops: &BinopRegistr
             match op {
                 BinOp::Add  => "+",  BinOp::Sub  => "-",
                 BinOp::Mul  => "*",  BinOp::Div  => "/",  BinOp::Mod  => "%",
+                BinOp::Pow  => "**",
                 BinOp::Lt   => "<",  BinOp::LtEq => "<=",
                 BinOp::Gt   => ">",  BinOp::GtEq => ">=",
                 BinOp::And  => "and", BinOp::Or  => "or",
@@ -908,12 +1453,14 @@ fn eval_binop(op: &BinOp, l: Value, r: Value, line: usize, binops: &BinopRegistr
     })
 }
 
-fn eval_unop(op: &UnOp, v: Value, line: usize) -> Result<Value, RuntimeError> {
+pub(crate) fn eval_unop(op: &UnOp, v: Value, line: usize) -> Result<Value, RuntimeError> {
     match op {
         UnOp::PrefixInc | UnOp::PrefixDec | UnOp::PostfixInc | UnOp::PostfixDec => {
             unreachable!("inc/dec handled in eval_expr")
         }
         UnOp::Neg => match v {
+            Value::Int(n)     => n.checked_neg().map(Value::Int)
+                .ok_or_else(|| RuntimeError::new(line, "integer overflow")),
             Value::Float(x)   => Ok(Value::Float(-x)),
             Value::Vec2(x, y) => Ok(Value::Vec2(-x, -y)),
             other => Err(RuntimeError::new(line, format!(
@@ -934,29 +1481,33 @@ fn eval_unop(op: &UnOp, v: Value, line: usize) -> Result<Value, RuntimeError> {
 fn as_float(v: &Value, line: usize) -> Result<f64, RuntimeError> {
     match v {
         Value::Float(x) => Ok(*x),
+        Value::Int(n)   => Ok(*n as f64),
         _ => Err(RuntimeError::new(line, format!(
             "expected float, got `{}`", value_type_name(v)
         ))),
     }
 }
 
-fn values_equal(a: &Value, b: &Value) -> bool {
-    match (a, b) {
-        (Value::Float(x),  Value::Float(y))  => x == y,
-        (Value::Bool(x),   Value::Bool(y))   => x == y,
-        (Value::Str(x),    Value::Str(y))    => x == y,
-        (Value::Vec2(ax, ay), Value::Vec2(bx, by)) => ax == bx && ay == by,
-        (Value::Vec3(ax,ay,az), Value::Vec3(bx,by,bz)) => ax==bx && ay==by && az==bz,
-        (Value::Vec4(ax,ay,az,aw), Value::Vec4(bx,by,bz,bw)) => ax==bx && ay==by && az==bz && aw==bw,
-        (Value::Color { r: ar, g: ag, b: ab, a: aa }, Value::Color { r: br, g: bg, b: bb, a: ba }) => {
-            ar == br && ag == bg && ab == bb && aa == ba
-        }
-        _ => false,
+/// A list index: an `int`, or a `float` truncated toward zero.
+fn as_index(v: &Value, line: usize) -> Result<i64, RuntimeError> {
+    match v {
+        Value::Int(n)   => Ok(*n),
+        Value::Float(x) => Ok(x.trunc() as i64),
+        _ => Err(RuntimeError::new(line, format!(
+            "index must be int or float, got `{}`", value_type_name(v)
+        ))),
     }
 }
 
+/// A slot holding a `float` stays `float` when an `int` is assigned into it.
+fn keep_float(old: &Value, new: Value) -> Value {
+    match old {
+        Value::Float(_) => coerce(new, &ast::Type::Float),
+        _ => new,
+    }
+}
 
-fn parse_hex_color(hex: &str) -> Result<Value, RuntimeError> {
+pub(crate) fn parse_hex_color(hex: &str) -> Result<Value, RuntimeError> {
     let parse = |s: &str| u8::from_str_radix(s, 16)
         .map(|n| n as f64 / 255.0)
         .map_err(|_| RuntimeError::new(0, format!("invalid hex: #{hex}")));
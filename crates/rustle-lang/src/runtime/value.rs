@@ -2,11 +2,12 @@ use std::collections::HashMap;
 use std::rc::Rc;
 use std::cell::RefCell;
 
-use crate::syntax::ast::{Param, Stmt};
+use crate::syntax::ast::{Param, Stmt, Type};
 use crate::types::draw::{RenderMode, ShapeData, TransformData};
 
 #[derive(Debug, Clone)]
 pub enum Value {
+    Int(i64),
     Float(f64),
     Bool(bool),
     Str(String),
@@ -17,6 +18,12 @@ pub enum Value {
     Mat4(Box<[f64; 16]>),  // row-major 4×4
     Color { r: f64, g: f64, b: f64, a: f64 },
     List(Rc<RefCell<Vec<Value>>>),
+    Map(Rc<RefCell<HashMap<String, Value>>>),
+    Tuple(Vec<Value>),
+    /// An instance of a user `struct` — fields in declaration order.
+    Struct { name: String, fields: Vec<(String, Value)> },
+    /// `Mode.Menu` — a variant of a user `enum`.
+    EnumVariant { enum_name: String, variant: String },
     Shape(ShapeData),
     Transform(TransformData),
     RenderMode(RenderMode),
@@ -26,9 +33,121 @@ pub enum Value {
     NativeFn(String),
     Closure {
         params: Vec<Param>,
+        return_ty: Option<Type>,
         body:   Vec<Stmt>,
         captured: HashMap<String, Value>,
     },
     State(Rc<RefCell<HashMap<String, Value>>>),
-    Input { dt: f64 },
+    Input { dt: f64, time: f64, frame: f64, mouse: (f64, f64) },
+}
+
+/// Widen an `int` to `float` when it lands in a slot declared `float`.
+/// A container slot can only receive ints where it wants floats from a fresh
+/// literal like `[[1, 2]]` or `ok(1)`, so list elements and map values are
+/// widened in place.
+pub(crate) fn coerce(v: Value, ty: &Type) -> Value {
+    match (v, ty) {
+        (Value::Int(n), Type::Float) => Value::Float(n as f64),
+        (Value::List(items), Type::List(elem)) if holds_float(elem) => {
+            for item in items.borrow_mut().iter_mut() {
+                *item = coerce(std::mem::replace(item, Value::Int(0)), elem);
+            }
+            Value::List(items)
+        }
+        (Value::Tuple(items), Type::Tuple(tys)) if items.len() == tys.len() => {
+            Value::Tuple(items.into_iter().zip(tys).map(|(v, t)| coerce(v, t)).collect())
+        }
+        (Value::Map(entries), Type::Map(elem)) if holds_float(elem) => {
+            for v in entries.borrow_mut().values_mut() {
+                *v = coerce(std::mem::replace(v, Value::Int(0)), elem);
+            }
+            Value::Map(entries)
+        }
+        (Value::ResOk(v), Type::Res(inner)) => Value::ResOk(Box::new(coerce(*v, inner))),
+        (v, _) => v,
+    }
+}
+
+/// Whether a value of type `ty` contains floats that `coerce` may need to
+/// widen, so lists of anything else are not walked.
+fn holds_float(ty: &Type) -> bool {
+    match ty {
+        Type::Float => true,
+        Type::List(t) | Type::Map(t) | Type::Res(t) => holds_float(t),
+        Type::Tuple(ts) => ts.iter().any(holds_float),
+        _ => false,
+    }
+}
+
+/// `==` on runtime values. Ints and floats compare numerically; maps, tuples
+/// and structs compare by content; lists, shapes and functions never match.
+pub(crate) fn values_equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Float(x),  Value::Float(y))  => x == y,
+        (Value::Int(x),    Value::Int(y))    => x == y,
+        (Value::Int(n),    Value::Float(x))
+        | (Value::Float(x), Value::Int(n))   => *n as f64 == *x,
+        (Value::Bool(x),   Value::Bool(y))   => x == y,
+        (Value::Str(x),    Value::Str(y))    => x == y,
+        (Value::Vec2(ax, ay), Value::Vec2(bx, by)) => ax == bx && ay == by,
+        (Value::Vec3(ax,ay,az), Value::Vec3(bx,by,bz)) => ax==bx && ay==by && az==bz,
+        (Value::Vec4(ax,ay,az,aw), Value::Vec4(bx,by,bz,bw)) => ax==bx && ay==by && az==bz && aw==bw,
+        (Value::Color { r: ar, g: ag, b: ab, a: aa }, Value::Color { r: br, g: bg, b: bb, a: ba }) => {
+            ar == br && ag == bg && ab == bb && aa == ba
+        }
+        (Value::Tuple(xs), Value::Tuple(ys)) => {
+            xs.len() == ys.len() && xs.iter().zip(ys).all(|(x, y)| values_equal(x, y))
+        }
+        (Value::EnumVariant { enum_name: ae, variant: av }, Value::EnumVariant { enum_name: be, variant: bv }) => {
+            ae == be && av == bv
+        }
+        (Value::Struct { name: a, fields: xs }, Value::Struct { name: b, fields: ys }) => {
+            a == b && xs.iter().zip(ys).all(|((_, x), (_, y))| values_equal(x, y))
+        }
+        (Value::Map(xs), Value::Map(ys)) => {
+            let (xs, ys) = (xs.borrow(), ys.borrow());
+            xs.len() == ys.len() && xs.iter().all(|(k, x)| ys.get(k).is_some_and(|y| values_equal(x, y)))
+        }
+        _ => false,
+    }
+}
+
+/// Copy of `v` that shares no list, map or state cell with the original.
+/// Cells shared inside `v` stay shared with each other in the copy — `seen`
+/// maps each original cell to its replacement.
+pub(crate) fn deep_clone(v: &Value, seen: &mut HashMap<usize, Value>) -> Value {
+    match v {
+        Value::List(items) => {
+            let key = Rc::as_ptr(items) as *const () as usize;
+            if let Some(copy) = seen.get(&key) { return copy.clone(); }
+            let cell = Rc::new(RefCell::new(Vec::new()));
+            seen.insert(key, Value::List(cell.clone()));
+            let copied = items.borrow().iter().map(|x| deep_clone(x, seen)).collect();
+            *cell.borrow_mut() = copied;
+            Value::List(cell)
+        }
+        Value::Map(entries) | Value::State(entries) => {
+            let key = Rc::as_ptr(entries) as *const () as usize;
+            if let Some(copy) = seen.get(&key) { return copy.clone(); }
+            let cell = Rc::new(RefCell::new(HashMap::new()));
+            let wrap = |c| if matches!(v, Value::Map(_)) { Value::Map(c) } else { Value::State(c) };
+            seen.insert(key, wrap(cell.clone()));
+            let copied = entries.borrow().iter().map(|(k, x)| (k.clone(), deep_clone(x, seen))).collect();
+            *cell.borrow_mut() = copied;
+            wrap(cell)
+        }
+        Value::Tuple(items) => Value::Tuple(items.iter().map(|x| deep_clone(x, seen)).collect()),
+        Value::Struct { name, fields } => Value::Struct {
+            name: name.clone(),
+            fields: fields.iter().map(|(k, x)| (k.clone(), deep_clone(x, seen))).collect(),
+        },
+        Value::ResOk(inner) => Value::ResOk(Box::new(deep_clone(inner, seen))),
+        Value::Closure { params, return_ty, body, captured } => Value::Closure {
+            params: params.clone(),
+            return_ty: return_ty.clone(),
+            body: body.clone(),
+            captured: captured.iter().map(|(k, x)| (k.clone(), deep_clone(x, seen))).collect(),
+        },
+        other => other.clone(),
+    }
 }
@@ -1,5 +1,5 @@
 /// Source location attached to every node for error reporting.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Span {
     pub line: usize,
     pub column: usize,
@@ -20,14 +20,45 @@ pub struct Program {
     pub items: Vec<Item>,
 }
 
-/// `import shapes { circle, rect }` or `import render`
+/// `import shapes { circle, rect as r }`, `import shapes { * }`, `import render`
+/// or `import render as rn`
 #[derive(Debug, Clone)]
 pub struct ImportDecl {
     pub namespace: String,
-    pub members: Vec<String>, // empty = import whole namespace
+    /// `as` name for a whole-namespace import.
+    pub alias: Option<String>,
+    pub members: Vec<ImportMember>, // empty = import whole namespace
+    /// `{ * }` — bring every export into scope by its own name.
+    pub wildcard: bool,
     pub span: Span,
 }
 
+impl ImportDecl {
+    /// Name the namespace is bound to in the program.
+    pub fn local_name(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.namespace)
+    }
+
+    /// `import ns` / `import ns as n` — binds the namespace itself, no members.
+    pub fn is_whole_namespace(&self) -> bool {
+        self.members.is_empty() && !self.wildcard
+    }
+}
+
+/// One member of a braced import, optionally renamed with `as`.
+#[derive(Debug, Clone)]
+pub struct ImportMember {
+    pub name: String,
+    pub alias: Option<String>,
+}
+
+impl ImportMember {
+    /// Name the member is bound to in the program.
+    pub fn local_name(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.name)
+    }
+}
+
 /// The `state { ... }` block — fields persist between frames.
 #[derive(Debug, Clone)]
 pub struct StateBlock {
@@ -43,13 +74,48 @@ pub struct StateField {
     pub span: Span,
 }
 
-/// A top-level item is either a function definition or a statement.
+/// A top-level item — a function definition, a type declaration, or a statement.
 #[derive(Debug, Clone)]
 pub enum Item {
     FnDef(FnDef),
+    TypeAlias(TypeAlias),
+    StructDef(StructDef),
+    EnumDef(EnumDef),
     Stmt(Stmt),
 }
 
+/// `type Points = list[vec2]` — `ty` is already expanded, so uses of the
+/// alias elsewhere in the tree carry the underlying type, never the name.
+#[derive(Debug, Clone)]
+pub struct TypeAlias {
+    pub name: String,
+    pub ty: Type,
+    pub span: Span,
+}
+
+/// `struct Particle { pos: vec2, vel: vec2 }`
+#[derive(Debug, Clone)]
+pub struct StructDef {
+    pub name: String,
+    pub fields: Vec<StructField>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct StructField {
+    pub name: String,
+    pub ty: Type,
+    pub span: Span,
+}
+
+/// `enum Mode { Menu, Playing, Paused }` — variants are read as `Mode.Menu`.
+#[derive(Debug, Clone)]
+pub struct EnumDef {
+    pub name: String,
+    pub variants: Vec<String>,
+    pub span: Span,
+}
+
 // ─── Functions ───────────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone)]
@@ -65,6 +131,8 @@ pub struct FnDef {
 pub struct Param {
     pub name: String,
     pub ty: Type,
+    /// `size: float = 1.0` — used when the call leaves the argument out.
+    pub default: Option<Expr>,
     pub span: Span,
 }
 
@@ -74,6 +142,8 @@ pub struct Param {
 pub enum Stmt {
     /// `let x = 3.14` or `let x: float = 3.14` or `const PI = 3.14`
     VarDecl(VarDecl),
+    /// `let (r, t) = to_polar(v)`
+    Destructure(DestructureDecl),
     /// `x = 3.14`
     Assign(Assign),
     /// `out << s1 << s2`
@@ -109,6 +179,16 @@ pub struct VarDecl {
     pub span: Span,
 }
 
+/// `let (a, b): (float, float) = pair` — binds each tuple element to a name.
+#[derive(Debug, Clone)]
+pub struct DestructureDecl {
+    pub names: Vec<String>,
+    pub ty: Option<Type>,
+    pub is_const: bool,
+    pub initializer: Expr,
+    pub span: Span,
+}
+
 /// Assignable target: dotted path or indexed (arr[i], s.arr[i]).
 #[derive(Debug, Clone)]
 pub enum AssignTarget {
@@ -133,12 +213,16 @@ impl AssignTarget {
 #[derive(Debug, Clone)]
 pub struct Assign {
     pub target: AssignTarget,
+    /// The operator of a compound assignment (`+=` → `Add`); `None` for plain `=`.
+    pub op: Option<BinOp>,
     pub value: Expr,
     pub span: Span,
 }
 
 #[derive(Debug, Clone)]
 pub struct OutStmt {
+    /// Named output channel (`out background << ...`). `None` = the default `out`.
+    pub channel: Option<String>,
     pub shapes: Vec<Expr>,
     pub span: Span,
 }
@@ -158,6 +242,14 @@ pub struct MatchArm {
     pub span: Span,
 }
 
+#[derive(Debug, Clone)]
+pub struct MatchExprArm {
+    /// Patterns to match (empty for `else`).
+    pub values: Vec<Expr>,
+    pub result: Expr,
+    pub span: Span,
+}
+
 #[derive(Debug, Clone)]
 pub struct IfStmt {
     pub condition: Expr,
@@ -184,6 +276,8 @@ pub struct ForStmt {
 
 #[derive(Debug, Clone)]
 pub struct ForeachStmt {
+    /// `i` in `foreach i, v in xs` — bound to the element index as a float.
+    pub index_name: Option<String>,
     pub var_name: String,
     pub var_ty: Option<Type>,
     pub iterable: Expr,
@@ -195,6 +289,7 @@ pub struct ForeachStmt {
 
 #[derive(Debug, Clone)]
 pub enum Expr {
+    Int(i64, Span),
     Float(f64, Span),
     Bool(bool, Span),
     StringLit(String, Span),
@@ -209,6 +304,14 @@ pub enum Expr {
         span: Span,
     },
 
+    /// `a < b <= c` — `a < b and b <= c` with `b` evaluated once.
+    /// `ops[i]` sits between `operands[i]` and `operands[i + 1]`.
+    CompareChain {
+        operands: Vec<Expr>,
+        ops: Vec<BinOp>,
+        span: Span,
+    },
+
     /// `not x`, `-x`
     UnOp {
         op: UnOp,
@@ -224,6 +327,13 @@ pub enum Expr {
         span: Span,
     },
 
+    /// `match n { 1.0 => a, else => b }` — evaluates to the matched arm's value
+    Match {
+        expr: Box<Expr>,
+        arms: Vec<MatchExprArm>,
+        span: Span,
+    },
+
     /// `expr as float`
     Cast {
         expr: Box<Expr>,
@@ -237,6 +347,12 @@ pub enum Expr {
         span: Span,
     },
 
+    /// `expr?` — unwraps a `res<T>`, returning early from the function on error
+    Propagate {
+        expr: Box<Expr>,
+        span: Span,
+    },
+
     /// `name(args, named: val)`
     Call {
         callee: String,
@@ -275,9 +391,35 @@ pub enum Expr {
         span: Span,
     },
 
+    /// `"t={x}"` — literal parts are `StringLit`s; every part is formatted
+    /// and concatenated at runtime
+    Interpolated(Vec<Expr>, Span),
+
     /// `[1.0, 2.0, 3.0]`
     List(Vec<Expr>, Span),
 
+    /// `Particle { pos: p, vel: v }` — fields in source order
+    StructLit {
+        name: String,
+        fields: Vec<(String, Expr)>,
+        span: Span,
+    },
+
+    /// `[x * 2.0 for x in xs]` or `[x for x in xs if x > 0.0]`
+    Comprehension {
+        expr: Box<Expr>,
+        var_name: String,
+        iterable: Box<Expr>,
+        filter: Option<Box<Expr>>,
+        span: Span,
+    },
+
+    /// `{ "a": 1.0, "b": 2.0 }` — string keys
+    Map(Vec<(Expr, Expr)>, Span),
+
+    /// `(a, b)` — two or more elements; `(a)` is just grouping
+    Tuple(Vec<Expr>, Span),
+
     /// `(a: float, b: float) -> float { return a + b }`
     Lambda {
         params: Vec<Param>,
@@ -285,28 +427,45 @@ pub enum Expr {
         body: Vec<Stmt>,
         span: Span,
     },
+    /// `start..end` or `start..end step n` — half-open float range, evaluates to a `list[float]`
+    Range {
+        start: Box<Expr>,
+        end: Box<Expr>,
+        step: Option<Box<Expr>>,
+        span: Span,
+    },
 }
 
 impl Expr {
     pub fn span(&self) -> &Span {
         match self {
+            Expr::Int(_, s)         => s,
             Expr::Float(_, s)       => s,
             Expr::Bool(_, s)        => s,
             Expr::StringLit(_, s)   => s,
             Expr::HexColor(_, s)    => s,
             Expr::Ident(_, s)       => s,
             Expr::BinOp { span, .. }    => span,
+            Expr::CompareChain { span, .. } => span,
             Expr::UnOp { span, .. }     => span,
             Expr::Ternary { span, .. }  => span,
+            Expr::Match { span, .. }    => span,
             Expr::Cast { span, .. }     => span,
             Expr::Try { span, .. }      => span,
+            Expr::Propagate { span, .. } => span,
             Expr::Call { span, .. }     => span,
             Expr::Index { span, .. }    => span,
             Expr::Field { span, .. }    => span,
             Expr::MethodCall { span, .. } => span,
             Expr::Transform { span, .. } => span,
             Expr::List(_, s)        => s,
+            Expr::Map(_, s)         => s,
+            Expr::StructLit { span, .. } => span,
+            Expr::Interpolated(_, s) => s,
+            Expr::Tuple(_, s)       => s,
             Expr::Lambda { span, .. } => span,
+            Expr::Range { span, .. }  => span,
+            Expr::Comprehension { span, .. } => span,
         }
     }
 }
@@ -315,7 +474,7 @@ impl Expr {
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum BinOp {
-    Add, Sub, Mul, Div, Mod,
+    Add, Sub, Mul, Div, Mod, Pow,
     Eq, NotEq,
     Lt, LtEq, Gt, GtEq,
     And, Or,
@@ -329,6 +488,7 @@ impl std::fmt::Display for BinOp {
             BinOp::Mul   => "*",
             BinOp::Div   => "/",
             BinOp::Mod   => "%",
+            BinOp::Pow   => "**",
             BinOp::Eq    => "==",
             BinOp::NotEq => "!=",
             BinOp::Lt    => "<",
@@ -356,11 +516,16 @@ pub enum UnOp {
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Type {
+    Int,
     Float,
     Bool,
     Unit,
     Array(Box<Type>, usize),
     List(Box<Type>),
+    /// `map[T]` — string keys to `T` values
+    Map(Box<Type>),
+    /// `(float, vec2)` — fixed-size, elements read with `.0`, `.1`, …
+    Tuple(Vec<Type>),
     Res(Box<Type>),
     Fn(Vec<Type>, Option<Box<Type>>),
     /// Any named type — built-in (`vec2`, `color`, `shape`) or user-defined (`State`, `Input`, future structs).
@@ -1,5 +1,5 @@
 use crate::error::{Error, ErrorCode};
-use crate::syntax::token::{Token, TokenKind, keyword_or_ident};
+use crate::syntax::token::{StrPart, Token, TokenKind, keyword_or_ident};
 
 pub struct Lexer<'a> {
     source: &'a [u8],
@@ -48,14 +48,21 @@ impl<'a> Lexer<'a> {
             }
             b'*' => {
                 if self.peek() == b'=' { self.advance(); TokenKind::StarEq }
+                else if self.peek() == b'*' { self.advance(); TokenKind::StarStar }
                 else { TokenKind::Star }
             }
-            b'%' => TokenKind::Percent,
+            b'%' => {
+                if self.peek() == b'=' { self.advance(); TokenKind::PercentEq }
+                else { TokenKind::Percent }
+            }
             b'?' => TokenKind::Question,
             b':' => TokenKind::Colon,
             b',' => TokenKind::Comma,
             b';' => TokenKind::Semicolon,
-            b'.' => TokenKind::Dot,
+            b'.' => {
+                if self.peek() == b'.' { self.advance(); TokenKind::DotDot }
+                else { TokenKind::Dot }
+            }
             b'(' => TokenKind::LParen,
             b')' => TokenKind::RParen,
             b'{' => TokenKind::LBrace,
@@ -72,7 +79,7 @@ impl<'a> Lexer<'a> {
             }
             b'/' => {
                 if self.peek() == b'/' { self.skip_line(); return Ok(None); }
-                else if self.peek() == b'*' { self.skip_block_comment(); return Ok(None); }
+                else if self.peek() == b'*' { self.skip_block_comment(line, col)?; return Ok(None); }
                 else if self.peek() == b'=' { self.advance(); TokenKind::SlashEq }
                 else { TokenKind::Slash }
             }
@@ -102,8 +109,11 @@ impl<'a> Lexer<'a> {
                 if self.is_hex_sequence() { TokenKind::HexColor(self.read_hex_color()) }
                 else { self.skip_line(); return Ok(None); }
             }
-            b'"' => TokenKind::StringLit(self.read_string(line, col)?),
-            b'0'..=b'9' => TokenKind::Float(self.read_number(ch)),
+            b'"' => self.read_string(line, col)?,
+            // `t.0.1` — after a member `.` only the integer is read, so the
+            // next `.` stays a field access instead of becoming a fraction.
+            b'0'..=b'9' if self.after_member_dot() => self.read_tuple_index(ch, line, col)?,
+            b'0'..=b'9' => self.read_number(ch, line, col)?,
             b'a'..=b'z' | b'A'..=b'Z' | b'_' => keyword_or_ident(self.read_ident(ch)),
 
             other => {
@@ -150,16 +160,26 @@ impl<'a> Lexer<'a> {
         while !self.is_at_end() && self.peek() != b'\n' { self.advance(); }
     }
 
-    fn skip_block_comment(&mut self) {
+    /// Skips a `/* ... */` comment. Comments nest, so `/* a /* b */ c */` is
+    /// consumed whole.
+    fn skip_block_comment(&mut self, start_line: usize, start_col: usize) -> Result<(), Error> {
         self.advance(); // consume *
+        let mut depth = 1;
         while !self.is_at_end() {
-            if self.peek() == b'*' && self.peek_next() == b'/' {
+            if self.peek() == b'/' && self.peek_next() == b'*' {
+                self.advance(); // /
+                self.advance(); // *
+                depth += 1;
+            } else if self.peek() == b'*' && self.peek_next() == b'/' {
                 self.advance(); // *
                 self.advance(); // /
-                break;
+                depth -= 1;
+                if depth == 0 { return Ok(()); }
+            } else {
+                self.advance();
             }
-            self.advance();
         }
+        Err(Error::new(ErrorCode::L004, start_line, start_col, "unterminated block comment"))
     }
 
     // ─── Readers ─────────────────────────────────────────────────────────────
@@ -183,8 +203,11 @@ impl<'a> Lexer<'a> {
         s
     }
 
-    fn read_string(&mut self, start_line: usize, start_col: usize) -> Result<String, Error> {
+    /// Reads a string literal. `{expr}` parts make it an `InterpString`, with
+    /// each expression lexed on its own; `\{` and `\}` are literal braces.
+    fn read_string(&mut self, start_line: usize, start_col: usize) -> Result<TokenKind, Error> {
         let mut s = String::new();
+        let mut parts = Vec::new();
         let mut error: Option<Error> = None;
         loop {
             if self.is_at_end() || self.peek() == b'\n' {
@@ -193,6 +216,14 @@ impl<'a> Lexer<'a> {
             }
             let ch = self.advance();
             if ch == b'"' { break; }
+            if ch == b'{' {
+                if !s.is_empty() { parts.push(StrPart::Lit(std::mem::take(&mut s))); }
+                match self.read_interpolation(start_line, start_col)? {
+                    Ok(tokens) => parts.push(StrPart::Expr(tokens)),
+                    Err(e) => if error.is_none() { error = Some(e); },
+                }
+                continue;
+            }
             if ch == b'\\' {
                 let esc_line = self.line;
                 let esc_col  = self.column;
@@ -201,6 +232,8 @@ impl<'a> Lexer<'a> {
                     b't'  => s.push('\t'),
                     b'"'  => s.push('"'),
                     b'\\' => s.push('\\'),
+                    b'{'  => s.push('{'),
+                    b'}'  => s.push('}'),
                     other => {
                         // Record the first escape error but keep consuming so we
                         // don't produce cascading errors from the remainder of the string.
@@ -215,24 +248,107 @@ impl<'a> Lexer<'a> {
             }
         }
         if let Some(e) = error { return Err(e); }
-        Ok(s)
+        if parts.is_empty() { return Ok(TokenKind::StringLit(s)); }
+        if !s.is_empty() { parts.push(StrPart::Lit(s)); }
+        Ok(TokenKind::InterpString(parts))
+    }
+
+    /// Lexes the expression of an `{expr}` string part; the `{` is already
+    /// consumed. The outer error means the string itself is unterminated; the
+    /// inner one is a bad expression, reported once the string is read.
+    fn read_interpolation(&mut self, start_line: usize, start_col: usize)
+        -> Result<Result<Vec<Token>, Error>, Error>
+    {
+        let (line, column, start) = (self.line, self.column, self.pos);
+        let unterminated = || Error::new(ErrorCode::L002, start_line, start_col,
+            "unterminated `{` in string literal");
+        let mut depth = 0;
+        loop {
+            if self.is_at_end() || self.peek() == b'\n' { return Err(unterminated()); }
+            match self.advance() {
+                b'{' => depth += 1,
+                b'}' if depth == 0 => break,
+                b'}' => depth -= 1,
+                // A string inside the expression, like the key in `"{m["k"]}"`:
+                // skip to its closing quote so its braces don't count.
+                b'"' => loop {
+                    if self.is_at_end() || self.peek() == b'\n' { return Err(unterminated()); }
+                    match self.advance() {
+                        b'"' => break,
+                        b'\\' => { self.advance(); }
+                        _ => {}
+                    }
+                },
+                _ => {}
+            }
+        }
+        let inner = &self.source[start..self.pos - 1];
+        if inner.iter().all(u8::is_ascii_whitespace) {
+            return Ok(Err(Error::new(ErrorCode::L001, line, column, "empty `{}` in string literal")));
+        }
+        let sub = Lexer { source: inner, pos: 0, line, column };
+        Ok(sub.tokenize().map_err(|mut errs| errs.remove(0)))
     }
 
-    fn read_number(&mut self, first: u8) -> f64 {
+    /// Reads a number literal: digits with optional `_` separators, an optional
+    /// fraction and an optional `e[+-]digits` exponent. `Int` when it has
+    /// neither a fraction nor an exponent.
+    fn read_number(&mut self, first: u8, start_line: usize, start_col: usize) -> Result<TokenKind, Error> {
         let mut s = String::new();
         s.push(first as char);
-        while !self.is_at_end() && self.peek().is_ascii_digit() {
-            s.push(self.advance() as char);
-        }
+        self.read_digits(&mut s);
+        let mut is_float = false;
         // consume decimal only if followed by at least one digit
         // (avoids treating `.` in `shape.field` as a decimal point)
         if !self.is_at_end() && self.peek() == b'.' && self.peek_next().is_ascii_digit() {
             s.push(self.advance() as char);
-            while !self.is_at_end() && self.peek().is_ascii_digit() {
+            self.read_digits(&mut s);
+            is_float = true;
+        }
+        if matches!(self.peek(), b'e' | b'E') {
+            is_float = true;
+            s.push(self.advance() as char);
+            if matches!(self.peek(), b'+' | b'-') {
                 s.push(self.advance() as char);
             }
+            if !self.peek().is_ascii_digit() {
+                return Err(Error::new(ErrorCode::L001, start_line, start_col,
+                    format!("invalid number literal `{s}`: exponent has no digits")));
+            }
+            self.read_digits(&mut s);
+        }
+        let invalid = || Error::new(ErrorCode::L001, start_line, start_col,
+            format!("invalid number literal `{s}`"));
+        if is_float {
+            s.parse().map(TokenKind::Float).map_err(|_| invalid())
+        } else {
+            s.parse().map(TokenKind::Int).map_err(|_| invalid())
+        }
+    }
+
+    /// True when the digit just consumed directly follows a single `.` (not `..`).
+    fn after_member_dot(&self) -> bool {
+        let at = self.pos - 1;
+        at >= 1 && self.source[at - 1] == b'.' && (at < 2 || self.source[at - 2] != b'.')
+    }
+
+    /// Reads a tuple element index like the `1` in `t.1`.
+    fn read_tuple_index(&mut self, first: u8, start_line: usize, start_col: usize) -> Result<TokenKind, Error> {
+        let mut s = String::new();
+        s.push(first as char);
+        while !self.is_at_end() && self.peek().is_ascii_digit() {
+            s.push(self.advance() as char);
+        }
+        s.parse().map(TokenKind::Int).map_err(|_| Error::new(ErrorCode::L001, start_line, start_col,
+            format!("invalid tuple index `{s}`")))
+    }
+
+    /// Appends a run of digits to `s`, dropping `_` separators.
+    fn read_digits(&mut self, s: &mut String) {
+        while !self.is_at_end() && (self.peek().is_ascii_digit() || self.peek() == b'_') {
+            let ch = self.advance();
+            if ch != b'_' { s.push(ch as char); }
         }
-        s.parse().unwrap_or(0.0)
     }
 
     fn read_ident(&mut self, first: u8) -> String {
@@ -265,8 +381,16 @@ mod tests {
     }
 
     #[test]
-    fn integer_becomes_float() {
-        assert_eq!(lex("42"), vec![TokenKind::Float(42.0), TokenKind::Eof]);
+    fn integer_literal() {
+        assert_eq!(lex("42"),    vec![TokenKind::Int(42),     TokenKind::Eof]);
+        assert_eq!(lex("1_000"), vec![TokenKind::Int(1000),   TokenKind::Eof]);
+        assert_eq!(lex("42.0"),  vec![TokenKind::Float(42.0), TokenKind::Eof]);
+    }
+
+    #[test]
+    fn integer_overflow_error() {
+        let errs = lex_err("x = 99999999999999999999");
+        assert_eq!(errs[0].code, ErrorCode::L001);
     }
 
     #[test]
@@ -274,6 +398,26 @@ mod tests {
         assert_eq!(lex("3.14"), vec![TokenKind::Float(3.14), TokenKind::Eof]);
     }
 
+    #[test]
+    fn scientific_notation() {
+        assert_eq!(lex("1e3"),    vec![TokenKind::Float(1000.0), TokenKind::Eof]);
+        assert_eq!(lex("2.5e-3"), vec![TokenKind::Float(0.0025), TokenKind::Eof]);
+        assert_eq!(lex("1E+2"),   vec![TokenKind::Float(100.0),  TokenKind::Eof]);
+    }
+
+    #[test]
+    fn underscore_separators() {
+        assert_eq!(lex("1_000.5"),   vec![TokenKind::Float(1000.5), TokenKind::Eof]);
+        assert_eq!(lex("1_000e1_0"), vec![TokenKind::Float(1e13),   TokenKind::Eof]);
+    }
+
+    #[test]
+    fn missing_exponent_digits_error() {
+        let errs = lex_err("x = 1e");
+        assert_eq!(errs[0].code, ErrorCode::L001);
+        assert_eq!((errs[0].line, errs[0].column), (1, 5));
+    }
+
     #[test]
     fn dot_not_consumed_by_number() {
         assert_eq!(
@@ -282,6 +426,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn dotdot_range() {
+        assert_eq!(
+            lex("0..10"),
+            vec![TokenKind::Int(0), TokenKind::DotDot, TokenKind::Int(10), TokenKind::Eof]
+        );
+    }
+
     #[test]
     fn keywords() {
         assert_eq!(lex("fn"),      vec![TokenKind::Fn,      TokenKind::Eof]);
@@ -329,22 +481,41 @@ mod tests {
         assert_eq!(lex(">="), vec![TokenKind::GtEq,   TokenKind::Eof]);
         assert_eq!(lex("<<"), vec![TokenKind::LtLt,   TokenKind::Eof]);
         assert_eq!(lex("->"), vec![TokenKind::Arrow,  TokenKind::Eof]);
+        assert_eq!(lex("**"), vec![TokenKind::StarStar, TokenKind::Eof]);
     }
 
     #[test]
     fn line_comment_skipped() {
-        assert_eq!(lex("// comment\n42"), vec![TokenKind::Float(42.0), TokenKind::Eof]);
+        assert_eq!(lex("// comment\n42"), vec![TokenKind::Int(42), TokenKind::Eof]);
     }
 
     #[test]
     fn block_comment_skipped() {
-        assert_eq!(lex("/* comment */42"), vec![TokenKind::Float(42.0), TokenKind::Eof]);
-        assert_eq!(lex("/* a\nb */42"), vec![TokenKind::Float(42.0), TokenKind::Eof]);
+        assert_eq!(lex("/* comment */42"), vec![TokenKind::Int(42), TokenKind::Eof]);
+        assert_eq!(lex("/* a\nb */42"), vec![TokenKind::Int(42), TokenKind::Eof]);
+    }
+
+    #[test]
+    fn nested_block_comment_skipped() {
+        assert_eq!(lex("/* a /* b */ c */42"), vec![TokenKind::Int(42), TokenKind::Eof]);
+    }
+
+    #[test]
+    fn block_comment_keeps_positions() {
+        let toks = Lexer::new("/* a\n /* b\n */ */  x").tokenize().unwrap();
+        assert_eq!((toks[0].line, toks[0].column), (3, 9));
+    }
+
+    #[test]
+    fn unterminated_block_comment_error() {
+        let errs = lex_err("1.0\n  /* a /* b */ c");
+        assert_eq!(errs[0].code, ErrorCode::L004);
+        assert_eq!((errs[0].line, errs[0].column), (2, 3));
     }
 
     #[test]
     fn metadata_comment_skipped() {
-        assert_eq!(lex("# author: name\n42"), vec![TokenKind::Float(42.0), TokenKind::Eof]);
+        assert_eq!(lex("# author: name\n42"), vec![TokenKind::Int(42), TokenKind::Eof]);
     }
 
     #[test]
@@ -362,6 +533,31 @@ mod tests {
         assert_eq!(lex(r#""hello""#), vec![TokenKind::StringLit("hello".into()), TokenKind::Eof]);
     }
 
+    #[test]
+    fn string_interpolation_parts() {
+        let toks = lex(r#""t={x + 1}s""#);
+        let TokenKind::InterpString(parts) = &toks[0] else { panic!("expected InterpString, got {toks:?}") };
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[0], StrPart::Lit("t=".into()));
+        let StrPart::Expr(inner) = &parts[1] else { panic!("expected Expr part") };
+        let kinds: Vec<_> = inner.iter().map(|t| t.kind.clone()).collect();
+        assert_eq!(kinds, vec![TokenKind::Ident("x".into()), TokenKind::Plus, TokenKind::Int(1), TokenKind::Eof]);
+        // Columns point into the original line.
+        assert_eq!(inner[0].column, 5);
+        assert_eq!(parts[2], StrPart::Lit("s".into()));
+    }
+
+    #[test]
+    fn string_escaped_brace_is_literal() {
+        assert_eq!(lex(r#""\{x\}""#), vec![TokenKind::StringLit("{x}".into()), TokenKind::Eof]);
+    }
+
+    #[test]
+    fn string_interpolation_unterminated_error() {
+        let errs = lex_err(r#""t={x""#);
+        assert_eq!(errs[0].code, ErrorCode::L002);
+    }
+
     #[test]
     fn string_escape_newline() {
         assert_eq!(lex(r#""a\nb""#), vec![TokenKind::StringLit("a\nb".into()), TokenKind::Eof]);
@@ -1,15 +1,23 @@
+use std::collections::{HashMap, HashSet};
+
 use crate::syntax::ast::*;
 use crate::error::{Error, ErrorCode};
-use crate::syntax::token::{Token, TokenKind};
+use crate::syntax::token::{StrPart, Token, TokenKind};
 
 pub struct Parser {
     tokens: Vec<Token>,
     pos: usize,
+    /// `type Name = T` declarations, already expanded — `parse_type`
+    /// substitutes these wherever it would produce `Type::Named(Name)`.
+    type_aliases: HashMap<String, Type>,
+    /// Names from `struct Name { ... }` — `Name {` only starts a struct
+    /// literal for these, so `if ready { ... }` keeps parsing as a block.
+    struct_names: HashSet<String>,
 }
 
 impl Parser {
     pub fn new(tokens: Vec<Token>) -> Self {
-        Self { tokens, pos: 0 }
+        Self { tokens, pos: 0, type_aliases: HashMap::new(), struct_names: HashSet::new() }
     }
 
     pub fn parse(mut self) -> Result<Program, Vec<Error>> {
@@ -18,6 +26,10 @@ impl Parser {
         let mut state = None;
         let mut items = Vec::new();
 
+        if let Err(e) = self.collect_type_decls() {
+            errors.push(e);
+        }
+
         while !self.is_at_end() {
             let pos_before = self.pos;
 
@@ -34,6 +46,24 @@ impl Parser {
                     Ok(item) => items.push(item),
                     Err(e) => { errors.push(e); self.recover(); }
                 },
+                TokenKind::Ident(ref n) if n == "type" && self.at_type_alias(self.pos) => {
+                    match self.parse_type_alias() {
+                        Ok(a) => items.push(Item::TypeAlias(a)),
+                        Err(e) => { errors.push(e); self.recover(); }
+                    }
+                }
+                TokenKind::Ident(ref n) if n == "struct" && self.at_decl("struct", self.pos) => {
+                    match self.parse_struct_def() {
+                        Ok(s) => items.push(Item::StructDef(s)),
+                        Err(e) => { errors.push(e); self.recover(); }
+                    }
+                }
+                TokenKind::Ident(ref n) if n == "enum" && self.at_decl("enum", self.pos) => {
+                    match self.parse_enum_def() {
+                        Ok(e) => items.push(Item::EnumDef(e)),
+                        Err(e) => { errors.push(e); self.recover(); }
+                    }
+                }
                 TokenKind::Eof => break,
                 _ => match self.parse_stmt() {
                     Ok(s) => items.push(Item::Stmt(s)),
@@ -61,19 +91,144 @@ impl Parser {
         let span = self.span();
         self.expect(TokenKind::Import)?;
         let namespace = self.expect_ident()?;
+        let mut alias = None;
+        let mut wildcard = false;
         let members = if self.check(TokenKind::LBrace) {
             self.advance();
             let mut members = Vec::new();
+            if self.matches(TokenKind::Star) {
+                wildcard = true;
+                self.expect(TokenKind::RBrace)?;
+                return Ok(ImportDecl { namespace, alias, members, wildcard, span });
+            }
             while !self.check(TokenKind::RBrace) && !self.is_at_end() {
-                members.push(self.expect_ident()?);
+                let name = self.expect_ident()?;
+                let alias = if self.matches(TokenKind::As) { Some(self.expect_ident()?) } else { None };
+                members.push(ImportMember { name, alias });
                 if !self.matches(TokenKind::Comma) { break; }
             }
             self.expect(TokenKind::RBrace)?;
             members
         } else {
+            if self.matches(TokenKind::As) {
+                alias = Some(self.expect_ident()?);
+            }
             Vec::new()
         };
-        Ok(ImportDecl { namespace, members, span })
+        Ok(ImportDecl { namespace, alias, members, wildcard, span })
+    }
+
+    // ─── Type declarations ───────────────────────────────────────────────────
+
+    /// `type` is contextual — only `type Name =` starts an alias.
+    fn at_type_alias(&self, i: usize) -> bool {
+        matches!(&self.tokens[i].kind, TokenKind::Ident(n) if n == "type")
+            && matches!(self.tokens.get(i + 1).map(|t| &t.kind), Some(TokenKind::Ident(_)))
+            && matches!(self.tokens.get(i + 2).map(|t| &t.kind), Some(TokenKind::Eq))
+    }
+
+    /// `struct` and `enum` are contextual too — only `struct Name {` /
+    /// `enum Name {` start a definition.
+    fn at_decl(&self, keyword: &str, i: usize) -> bool {
+        matches!(&self.tokens[i].kind, TokenKind::Ident(n) if n == keyword)
+            && matches!(self.tokens.get(i + 1).map(|t| &t.kind), Some(TokenKind::Ident(_)))
+            && matches!(self.tokens.get(i + 2).map(|t| &t.kind), Some(TokenKind::LBrace))
+    }
+
+    /// Pre-pass over the top level so structs and aliases can be used before
+    /// they are declared. Each alias round expands one more level of
+    /// alias-to-alias references; anything still naming an alias afterwards
+    /// is a cycle.
+    fn collect_type_decls(&mut self) -> Result<(), Error> {
+        let mut decls = Vec::new();
+        let mut depth = 0usize;
+        for i in 0..self.tokens.len() {
+            match &self.tokens[i].kind {
+                TokenKind::LBrace => depth += 1,
+                TokenKind::RBrace => depth = depth.saturating_sub(1),
+                TokenKind::Ident(_) if depth == 0 && self.at_type_alias(i) => decls.push(i),
+                TokenKind::Ident(_) if depth == 0 && self.at_decl("struct", i) => {
+                    if let TokenKind::Ident(name) = &self.tokens[i + 1].kind {
+                        self.struct_names.insert(name.clone());
+                    }
+                }
+                _ => {}
+            }
+        }
+        if decls.is_empty() {
+            return Ok(());
+        }
+
+        for _ in 0..decls.len() {
+            let mut next = HashMap::new();
+            for &i in &decls {
+                self.pos = i + 3;
+                // Malformed types are reported when the main loop reaches them.
+                if let (TokenKind::Ident(name), Ok(ty)) = (self.tokens[i + 1].kind.clone(), self.parse_type()) {
+                    next.insert(name, ty);
+                }
+            }
+            self.type_aliases = next;
+        }
+        self.pos = 0;
+
+        for &i in &decls {
+            if let TokenKind::Ident(name) = &self.tokens[i + 1].kind
+                && let Some(ty) = self.type_aliases.get(name)
+                && self.type_aliases.keys().any(|a| names_type(ty, a))
+            {
+                let tok = &self.tokens[i + 1];
+                let err = Error::new(ErrorCode::P002, tok.line, tok.column,
+                    format!("type alias `{name}` refers to itself"));
+                self.type_aliases.clear();
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    /// `type Points = list[vec2]` — the type comes back already expanded.
+    fn parse_type_alias(&mut self) -> Result<TypeAlias, Error> {
+        let span = self.span();
+        self.advance(); // `type`
+        let name = self.expect_ident()?;
+        self.expect(TokenKind::Eq)?;
+        let ty = self.parse_type()?;
+        Ok(TypeAlias { name, ty, span })
+    }
+
+    /// `struct Particle { pos: vec2, vel: vec2 }` — commas between fields.
+    fn parse_struct_def(&mut self) -> Result<StructDef, Error> {
+        let span = self.span();
+        self.advance(); // `struct`
+        let name = self.expect_ident()?;
+        self.expect(TokenKind::LBrace)?;
+        let mut fields = Vec::new();
+        while !self.check(TokenKind::RBrace) && !self.is_at_end() {
+            let span = self.span();
+            let name = self.expect_ident()?;
+            self.expect(TokenKind::Colon)?;
+            let ty = self.parse_type()?;
+            fields.push(StructField { name, ty, span });
+            if !self.matches(TokenKind::Comma) { break; }
+        }
+        self.expect(TokenKind::RBrace)?;
+        Ok(StructDef { name, fields, span })
+    }
+
+    /// `enum Mode { Menu, Playing, Paused }` — commas between variants.
+    fn parse_enum_def(&mut self) -> Result<EnumDef, Error> {
+        let span = self.span();
+        self.advance(); // `enum`
+        let name = self.expect_ident()?;
+        self.expect(TokenKind::LBrace)?;
+        let mut variants = Vec::new();
+        while !self.check(TokenKind::RBrace) && !self.is_at_end() {
+            variants.push(self.expect_ident()?);
+            if !self.matches(TokenKind::Comma) { break; }
+        }
+        self.expect(TokenKind::RBrace)?;
+        Ok(EnumDef { name, variants, span })
     }
 
     // ─── State block ─────────────────────────────────────────────────────────
@@ -144,7 +299,16 @@ impl Parser {
             let name = self.expect_ident()?;
             self.expect(TokenKind::Colon)?;
             let ty = self.parse_type()?;
-            params.push(Param { name, ty, span });
+            let default = if self.matches(TokenKind::Eq) {
+                Some(self.parse_expr()?)
+            } else {
+                if params.iter().any(|p: &Param| p.default.is_some()) {
+                    return Err(Error::new(ErrorCode::P002, span.line, span.column,
+                        format!("parameter `{name}` needs a default value: it follows one that has a default")));
+                }
+                None
+            };
+            params.push(Param { name, ty, default, span });
             if !self.matches(TokenKind::Comma) { break; }
         }
         Ok(params)
@@ -191,6 +355,9 @@ impl Parser {
     fn parse_var_decl(&mut self, is_const: bool) -> Result<Stmt, Error> {
         let span = self.span();
         self.advance(); // consume `let` or `const`
+        if self.matches(TokenKind::LParen) {
+            return self.parse_destructure(is_const, span);
+        }
         let name = self.expect_ident()?;
         let ty = if self.matches(TokenKind::Colon) {
             Some(self.parse_type()?)
@@ -202,6 +369,27 @@ impl Parser {
         Ok(Stmt::VarDecl(VarDecl { name, ty, is_const, initializer, span }))
     }
 
+    /// `let (a, b) = expr` — the opening `(` is already consumed.
+    fn parse_destructure(&mut self, is_const: bool, span: Span) -> Result<Stmt, Error> {
+        let mut names = vec![self.expect_ident()?];
+        while self.matches(TokenKind::Comma) {
+            names.push(self.expect_ident()?);
+        }
+        self.expect(TokenKind::RParen)?;
+        if names.len() < 2 {
+            return Err(Error::new(ErrorCode::P002, span.line, span.column,
+                "destructuring needs at least two names"));
+        }
+        let ty = if self.matches(TokenKind::Colon) {
+            Some(self.parse_type()?)
+        } else {
+            None
+        };
+        self.expect(TokenKind::Eq)?;
+        let initializer = self.parse_expr()?;
+        Ok(Stmt::Destructure(DestructureDecl { names, ty, is_const, initializer, span }))
+    }
+
     fn parse_assign(&mut self) -> Result<Stmt, Error> {
         let span = self.span();
         let mut path = vec![self.expect_ident()?];
@@ -214,67 +402,39 @@ impl Parser {
             self.expect(TokenKind::RBracket)?;
         }
         let target = if indices.is_empty() {
-            AssignTarget::Path(path.clone())
+            AssignTarget::Path(path)
         } else {
-            AssignTarget::Indexed { path: path.clone(), indices: indices.clone() }
+            AssignTarget::Indexed { path, indices }
         };
-        let value = if self.matches(TokenKind::Eq) {
-            self.parse_expr()?
-        } else {
-            let binop = if self.matches(TokenKind::PlusEq) {
-                BinOp::Add
-            } else if self.matches(TokenKind::MinusEq) {
-                BinOp::Sub
-            } else if self.matches(TokenKind::StarEq) {
-                BinOp::Mul
-            } else if self.matches(TokenKind::SlashEq) {
-                BinOp::Div
-            } else {
-                return Err(Error::new(ErrorCode::P002, self.peek().line, self.peek().column,
-                    "expected `=`, `+=`, `-=`, `*=`, or `/=`"));
-            };
-            let rhs = self.parse_expr()?;
-            let lhs = self.path_to_expr(&path, &indices, span.clone());
-            Expr::BinOp {
-                left: Box::new(lhs),
-                op: binop,
-                right: Box::new(rhs),
-                span: span.clone(),
-            }
+        let op = match self.peek_kind() {
+            TokenKind::Eq        => None,
+            TokenKind::PlusEq    => Some(BinOp::Add),
+            TokenKind::MinusEq   => Some(BinOp::Sub),
+            TokenKind::StarEq    => Some(BinOp::Mul),
+            TokenKind::SlashEq   => Some(BinOp::Div),
+            TokenKind::PercentEq => Some(BinOp::Mod),
+            _ => return Err(Error::new(ErrorCode::P002, self.peek().line, self.peek().column,
+                "expected `=`, `+=`, `-=`, `*=`, `/=`, or `%=`")),
         };
-        Ok(Stmt::Assign(Assign { target, value, span }))
-    }
-
-    /// Build an Expr that reads the value at the given path (e.g. `x`, `s.x`, or `arr[i]`).
-    fn path_to_expr(&self, path: &[String], indices: &[Expr], span: Span) -> Expr {
-        let mut expr = Expr::Ident(path[0].clone(), span.clone());
-        for part in path.iter().skip(1) {
-            expr = Expr::Field {
-                expr: Box::new(expr),
-                field: part.clone(),
-                span: span.clone(),
-            };
-        }
-        for idx in indices {
-            expr = Expr::Index {
-                expr: Box::new(expr),
-                index: Box::new(idx.clone()),
-                span: span.clone(),
-            };
-        }
-        expr
+        self.advance();
+        let value = self.parse_expr()?;
+        Ok(Stmt::Assign(Assign { target, op, value, span }))
     }
 
     fn parse_out(&mut self) -> Result<Stmt, Error> {
         let span = self.span();
         self.expect(TokenKind::Out)?;
+        let channel = match self.peek_kind() {
+            TokenKind::Ident(_) => Some(self.expect_ident()?),
+            _ => None,
+        };
         let mut shapes = Vec::new();
         self.expect(TokenKind::LtLt)?;
         shapes.push(self.parse_expr()?);
         while self.matches(TokenKind::LtLt) {
             shapes.push(self.parse_expr()?);
         }
-        Ok(Stmt::Out(OutStmt { shapes, span }))
+        Ok(Stmt::Out(OutStmt { channel, shapes, span }))
     }
 
     fn parse_if(&mut self) -> Result<Stmt, Error> {
@@ -321,6 +481,31 @@ impl Parser {
         Ok(Stmt::Match(MatchStmt { expr, arms, span }))
     }
 
+    fn parse_match_expr(&mut self) -> Result<Expr, Error> {
+        let span = self.span();
+        self.expect(TokenKind::Match)?;
+        let expr = self.parse_expr()?;
+        self.expect(TokenKind::LBrace)?;
+        let mut arms = Vec::new();
+        while !self.check(TokenKind::RBrace) && !self.is_at_end() {
+            let arm_span = self.span();
+            let mut values = Vec::new();
+            if !self.matches(TokenKind::Else) {
+                values.push(self.parse_expr()?);
+                while self.matches(TokenKind::Comma) {
+                    values.push(self.parse_expr()?);
+                }
+            }
+            self.expect(TokenKind::FatArrow)?;
+            let result = self.parse_expr()?;
+            arms.push(MatchExprArm { values, result, span: arm_span });
+            // arms may be separated by commas
+            self.matches(TokenKind::Comma);
+        }
+        self.expect(TokenKind::RBrace)?;
+        Ok(Expr::Match { expr: Box::new(expr), arms, span })
+    }
+
     fn parse_while(&mut self) -> Result<Stmt, Error> {
         let span = self.span();
         self.expect(TokenKind::While)?;
@@ -332,6 +517,14 @@ impl Parser {
     fn parse_for(&mut self) -> Result<Stmt, Error> {
         let span = self.span();
         self.expect(TokenKind::For)?;
+        // `for i in <iterable> { }` — sugar for `foreach`, usually over a range.
+        if matches!(self.peek_kind(), TokenKind::Ident(_)) && self.peek_next_is(TokenKind::In) {
+            let var_name = self.expect_ident()?;
+            self.expect(TokenKind::In)?;
+            let iterable = self.parse_expr()?;
+            let body = self.parse_block()?;
+            return Ok(Stmt::Foreach(ForeachStmt { index_name: None, var_name, var_ty: None, iterable, body, span }));
+        }
         let init = Box::new(self.parse_var_decl(false)?);
         self.expect(TokenKind::Semicolon)?;
         let condition = self.parse_expr()?;
@@ -344,7 +537,12 @@ impl Parser {
     fn parse_foreach(&mut self) -> Result<Stmt, Error> {
         let span = self.span();
         self.expect(TokenKind::Foreach)?;
-        let var_name = self.expect_ident()?;
+        let first = self.expect_ident()?;
+        let (index_name, var_name) = if self.matches(TokenKind::Comma) {
+            (Some(first), self.expect_ident()?)
+        } else {
+            (None, first)
+        };
         let var_ty = if self.matches(TokenKind::Colon) {
             Some(self.parse_type()?)
         } else {
@@ -353,7 +551,7 @@ impl Parser {
         self.expect(TokenKind::In)?;
         let iterable = self.parse_expr()?;
         let body = self.parse_block()?;
-        Ok(Stmt::Foreach(ForeachStmt { var_name, var_ty, iterable, body, span }))
+        Ok(Stmt::Foreach(ForeachStmt { index_name, var_name, var_ty, iterable, body, span }))
     }
 
     fn parse_return(&mut self) -> Result<Stmt, Error> {
@@ -376,7 +574,20 @@ impl Parser {
             let expr = self.parse_expr()?;
             return Ok(Expr::Try { expr: Box::new(expr), span });
         }
-        self.parse_ternary()
+        let expr = self.parse_ternary()?;
+        if self.matches(TokenKind::DotDot) {
+            let span = expr.span().clone();
+            let end = self.parse_ternary()?;
+            // `step` is contextual — it stays a plain identifier everywhere else.
+            let step = if matches!(self.peek_kind(), TokenKind::Ident(ref s) if s == "step") {
+                self.advance();
+                Some(Box::new(self.parse_ternary()?))
+            } else {
+                None
+            };
+            return Ok(Expr::Range { start: Box::new(expr), end: Box::new(end), step, span });
+        }
+        Ok(expr)
     }
 
     fn parse_ternary(&mut self) -> Result<Expr, Error> {
@@ -434,8 +645,13 @@ impl Parser {
         Ok(left)
     }
 
+    /// `a < b` is a plain `BinOp`; `a < b <= c` becomes a `CompareChain`
+    /// so `b` is evaluated once.
     fn parse_comparison(&mut self) -> Result<Expr, Error> {
-        let mut left = self.parse_addition()?;
+        let first = self.parse_addition()?;
+        let span = first.span().clone();
+        let mut operands = vec![first];
+        let mut ops = Vec::new();
         loop {
             let op = match self.peek_kind() {
                 TokenKind::Lt   => BinOp::Lt,
@@ -444,12 +660,19 @@ impl Parser {
                 TokenKind::GtEq => BinOp::GtEq,
                 _ => break,
             };
-            let span = left.span().clone();
             self.advance();
-            let right = self.parse_addition()?;
-            left = Expr::BinOp { left: Box::new(left), op, right: Box::new(right), span };
+            ops.push(op);
+            operands.push(self.parse_addition()?);
+        }
+        match ops.len() {
+            0 => Ok(operands.pop().unwrap()),
+            1 => {
+                let right = operands.pop().unwrap();
+                let left = operands.pop().unwrap();
+                Ok(Expr::BinOp { left: Box::new(left), op: ops.pop().unwrap(), right: Box::new(right), span })
+            }
+            _ => Ok(Expr::CompareChain { operands, ops, span }),
         }
-        Ok(left)
     }
 
     fn parse_addition(&mut self) -> Result<Expr, Error> {
@@ -503,7 +726,18 @@ impl Parser {
             let operand = self.parse_unary()?;
             return Ok(Expr::UnOp { op: UnOp::PrefixDec, operand: Box::new(operand), span });
         }
-        self.parse_postfix()
+        self.parse_power()
+    }
+
+    /// `a ** b` — binds tighter than unary minus on its left (`-2 ** 2` is `-4`)
+    /// and is right-associative (`2 ** 3 ** 2` is `2 ** 9`).
+    fn parse_power(&mut self) -> Result<Expr, Error> {
+        let left = self.parse_postfix()?;
+        if !self.check(TokenKind::StarStar) { return Ok(left); }
+        let span = left.span().clone();
+        self.advance();
+        let right = self.parse_unary()?;
+        Ok(Expr::BinOp { left: Box::new(left), op: BinOp::Pow, right: Box::new(right), span })
     }
 
     fn parse_postfix(&mut self) -> Result<Expr, Error> {
@@ -515,12 +749,12 @@ impl Parser {
                 TokenKind::Dot => {
                     let span = expr.span().clone();
                     self.advance();
-                    // Allow keyword `in` as a method name (used by shape.in()).
-                    let field = if self.check(TokenKind::In) {
-                        self.advance();
-                        "in".to_string()
-                    } else {
-                        self.expect_ident()?
+                    // Allow keyword `in` as a method name (used by shape.in()),
+                    // and integers for tuple elements (`t.0`).
+                    let field = match self.peek_kind() {
+                        TokenKind::In => { self.advance(); "in".to_string() }
+                        TokenKind::Int(n) => { self.advance(); n.to_string() }
+                        _ => self.expect_ident()?,
                     };
                     if self.check(TokenKind::LParen) {
                         self.advance();
@@ -568,6 +802,13 @@ impl Parser {
                     expr = Expr::Cast { expr: Box::new(expr), ty, span };
                 }
 
+                // error propagation: expr?
+                TokenKind::Question if self.is_propagate_question() => {
+                    let span = expr.span().clone();
+                    self.advance();
+                    expr = Expr::Propagate { expr: Box::new(expr), span };
+                }
+
                 // postfix ++ and --
                 TokenKind::PlusPlus => {
                     let span = expr.span().clone();
@@ -592,35 +833,66 @@ impl Parser {
         let span = Span::new(tok.line, tok.column);
 
         match tok.kind {
+            TokenKind::Int(v)   => { self.advance(); Ok(Expr::Int(v, span)) }
             TokenKind::Float(v) => { self.advance(); Ok(Expr::Float(v, span)) }
             TokenKind::Bool(v)  => { self.advance(); Ok(Expr::Bool(v, span)) }
             TokenKind::StringLit(s) => { self.advance(); Ok(Expr::StringLit(s, span)) }
+            TokenKind::InterpString(parts) => { self.advance(); self.parse_interpolation(parts, span) }
             TokenKind::HexColor(s)  => { self.advance(); Ok(Expr::HexColor(s, span)) }
 
-            // lambda or grouped expression
+            // lambda, tuple or grouped expression
             TokenKind::LParen => {
                 if self.is_lambda_start() {
                     self.parse_lambda()
                 } else {
                     self.advance();
                     let expr = self.parse_expr()?;
+                    if !self.check(TokenKind::Comma) {
+                        self.expect(TokenKind::RParen)?;
+                        return Ok(expr);
+                    }
+                    let mut items = vec![expr];
+                    while self.matches(TokenKind::Comma) {
+                        items.push(self.parse_expr()?);
+                    }
                     self.expect(TokenKind::RParen)?;
-                    Ok(expr)
+                    Ok(Expr::Tuple(items, span))
                 }
             }
 
-            // list literal
+            // match expression: `match n { 1.0 => a, else => b }`
+            TokenKind::Match => self.parse_match_expr(),
+
+            // list literal or comprehension
             TokenKind::LBracket => {
                 self.advance();
                 let mut items = Vec::new();
                 while !self.check(TokenKind::RBracket) && !self.is_at_end() {
-                    items.push(self.parse_expr()?);
+                    let item = self.parse_expr()?;
+                    if items.is_empty() && self.matches(TokenKind::For) {
+                        return self.parse_comprehension(item, span);
+                    }
+                    items.push(item);
                     if !self.matches(TokenKind::Comma) { break; }
                 }
                 self.expect(TokenKind::RBracket)?;
                 Ok(Expr::List(items, span))
             }
 
+            // map literal: { "key": value, ... }
+            TokenKind::LBrace => {
+                self.advance();
+                let mut entries = Vec::new();
+                while !self.check(TokenKind::RBrace) && !self.is_at_end() {
+                    let key = self.parse_expr()?;
+                    self.expect(TokenKind::Colon)?;
+                    entries.push((key, self.parse_expr()?));
+                    if !self.matches(TokenKind::Comma) { break; }
+                }
+                self.expect(TokenKind::RBrace)?;
+                Ok(Expr::Map(entries, span))
+            }
+
             // lambda: (a: float) -> float { ... }
             // only if next token after `(` looks like a param (ident : type)
             // we let parse_call_or_ident handle ident(), so just handle ( here for lambdas
@@ -634,6 +906,41 @@ impl Parser {
         }
     }
 
+    /// `"t={x}"` — each `{expr}` part carries its own tokens and gets its own parser.
+    fn parse_interpolation(&mut self, parts: Vec<StrPart>, span: Span) -> Result<Expr, Error> {
+        let mut exprs = Vec::with_capacity(parts.len());
+        for part in parts {
+            match part {
+                StrPart::Lit(s) => exprs.push(Expr::StringLit(s, span.clone())),
+                StrPart::Expr(tokens) => {
+                    let mut sub = Parser::new(tokens);
+                    sub.type_aliases = self.type_aliases.clone();
+                    sub.struct_names = self.struct_names.clone();
+                    exprs.push(sub.parse_expr()?);
+                    if !sub.is_at_end() {
+                        let tok = sub.peek().clone();
+                        return Err(sub.error_at(&tok, "expected `}` after interpolated expression"));
+                    }
+                }
+            }
+        }
+        Ok(Expr::Interpolated(exprs, span))
+    }
+
+    /// `[expr for x in iterable if filter]` — `expr` and `for` are already consumed.
+    fn parse_comprehension(&mut self, expr: Expr, span: Span) -> Result<Expr, Error> {
+        let var_name = self.expect_ident()?;
+        self.expect(TokenKind::In)?;
+        let iterable = self.parse_expr()?;
+        let filter = if self.matches(TokenKind::If) {
+            Some(Box::new(self.parse_expr()?))
+        } else {
+            None
+        };
+        self.expect(TokenKind::RBracket)?;
+        Ok(Expr::Comprehension { expr: Box::new(expr), var_name, iterable: Box::new(iterable), filter, span })
+    }
+
     fn parse_call_or_ident(&mut self) -> Result<Expr, Error> {
         let tok = self.advance();
         let span = Span::new(tok.line, tok.column);
@@ -647,6 +954,18 @@ impl Parser {
             let (args, named_args) = self.parse_mixed_arg_list()?;
             self.expect(TokenKind::RParen)?;
             Ok(Expr::Call { callee: name, args, named_args, span })
+        } else if self.check(TokenKind::LBrace) && self.struct_names.contains(&name) {
+            // Particle { pos: p, vel: v }
+            self.advance();
+            let mut fields = Vec::new();
+            while !self.check(TokenKind::RBrace) && !self.is_at_end() {
+                let field = self.expect_ident()?;
+                self.expect(TokenKind::Colon)?;
+                fields.push((field, self.parse_expr()?));
+                if !self.matches(TokenKind::Comma) { break; }
+            }
+            self.expect(TokenKind::RBrace)?;
+            Ok(Expr::StructLit { name, fields, span })
         } else {
             Ok(Expr::Ident(name, span))
         }
@@ -693,6 +1012,7 @@ impl Parser {
     fn parse_type(&mut self) -> Result<Type, Error> {
         let tok = self.advance();
         match tok.kind {
+            TokenKind::TInt   => Ok(Type::Int),
             TokenKind::TFloat => Ok(Type::Float),
             TokenKind::TBool  => Ok(Type::Bool),
 
@@ -718,6 +1038,7 @@ impl Parser {
                 let inner = self.parse_type()?;
                 self.expect(TokenKind::Comma)?;
                 let size = match self.advance().kind {
+                    TokenKind::Int(n) if n >= 0 => n as usize,
                     TokenKind::Float(n) => n as usize,
                     _ => return Err(self.error_at(&tok, "expected array size")),
                 };
@@ -742,7 +1063,28 @@ impl Parser {
                 Ok(Type::Fn(params, ret))
             }
 
-            TokenKind::Ident(name) => Ok(Type::Named(name)),
+            // (T, U, ...)
+            TokenKind::LParen => {
+                let mut items = vec![self.parse_type()?];
+                while self.matches(TokenKind::Comma) {
+                    items.push(self.parse_type()?);
+                }
+                self.expect(TokenKind::RParen)?;
+                if items.len() < 2 {
+                    return Err(self.error_at(&tok, "tuple types need at least two elements"));
+                }
+                Ok(Type::Tuple(items))
+            }
+
+            // map[T] — `map` stays an identifier so `.map(f)` keeps working
+            TokenKind::Ident(name) if name == "map" && self.check(TokenKind::LBracket) => {
+                self.advance();
+                let inner = self.parse_type()?;
+                self.expect(TokenKind::RBracket)?;
+                Ok(Type::Map(Box::new(inner)))
+            }
+
+            TokenKind::Ident(name) => Ok(self.type_aliases.get(&name).cloned().unwrap_or(Type::Named(name))),
 
             _ => Err(self.error_at(&tok, "expected type")),
         }
@@ -785,7 +1127,8 @@ impl Parser {
                     i = self.skip_to_matching_bracket(i);
                     if i > self.tokens.len() { return false; }
                 }
-                TokenKind::Eq | TokenKind::PlusEq | TokenKind::MinusEq | TokenKind::StarEq | TokenKind::SlashEq =>
+                TokenKind::Eq | TokenKind::PlusEq | TokenKind::MinusEq | TokenKind::StarEq | TokenKind::SlashEq
+                | TokenKind::PercentEq =>
                     return true,
                 _ => return false,
             }
@@ -868,6 +1211,22 @@ impl Parser {
         Error::new(ErrorCode::P001, tok.line, tok.column, msg)
     }
 
+    /// Returns true when the `?` at the current position is postfix error
+    /// propagation rather than the start of a ternary: nothing that could begin
+    /// a then-branch follows it on the same line.
+    fn is_propagate_question(&self) -> bool {
+        let Some(next) = self.tokens.get(self.pos + 1) else { return true; };
+        if next.line != self.peek().line { return true; }
+        !matches!(
+            next.kind,
+            TokenKind::Int(_) | TokenKind::Float(_) | TokenKind::Bool(_) | TokenKind::Ident(_)
+                | TokenKind::StringLit(_) | TokenKind::InterpString(_) | TokenKind::HexColor(_)
+                | TokenKind::LParen | TokenKind::LBracket
+                | TokenKind::Minus | TokenKind::Not | TokenKind::PlusPlus | TokenKind::MinusMinus
+                | TokenKind::Try | TokenKind::Match
+        )
+    }
+
     /// Returns true if the current `(` starts a lambda expression.
     /// Lambda patterns: `() ->` or `(ident :`
     fn is_lambda_start(&self) -> bool {
@@ -892,6 +1251,10 @@ impl Parser {
         let span = self.span();
         self.expect(TokenKind::LParen)?;
         let params = self.parse_param_list()?;
+        if let Some(p) = params.iter().find(|p| p.default.is_some()) {
+            return Err(Error::new(ErrorCode::P002, p.span.line, p.span.column,
+                "lambda parameters cannot have default values"));
+        }
         self.expect(TokenKind::RParen)?;
         let return_ty = if self.matches(TokenKind::Arrow) { Some(self.parse_type()?) } else { None };
         let body = self.parse_block()?;
@@ -921,6 +1284,19 @@ impl Parser {
     }
 }
 
+/// True when `ty` mentions `Type::Named(name)` anywhere inside it.
+fn names_type(ty: &Type, name: &str) -> bool {
+    match ty {
+        Type::Named(n) => n == name,
+        Type::Array(t, _) | Type::List(t) | Type::Map(t) | Type::Res(t) => names_type(t, name),
+        Type::Tuple(ts) => ts.iter().any(|t| names_type(t, name)),
+        Type::Fn(ps, ret) => {
+            ps.iter().any(|t| names_type(t, name)) || ret.as_deref().is_some_and(|t| names_type(t, name))
+        }
+        Type::Int | Type::Float | Type::Bool | Type::Unit => false,
+    }
+}
+
 // ─── Tests ───────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -944,6 +1320,10 @@ mod tests {
         Parser::new(tokens).parse().expect_err("expected parse error")
     }
 
+    fn member_names(import: &ImportDecl) -> Vec<&str> {
+        import.members.iter().map(|m| m.name.as_str()).collect()
+    }
+
     // ── imports ──────────────────────────────────────────────────────────────
 
     #[test]
@@ -957,7 +1337,34 @@ mod tests {
     #[test]
     fn import_named_members() {
         let p = parse("import shapes { circle, rect }");
-        assert_eq!(p.imports[0].members, vec!["circle", "rect"]);
+        assert_eq!(member_names(&p.imports[0]), vec!["circle", "rect"]);
+    }
+
+    #[test]
+    fn import_wildcard() {
+        let p = parse("import core { * }");
+        assert!(p.imports[0].wildcard);
+        assert!(p.imports[0].members.is_empty());
+        assert!(!p.imports[0].is_whole_namespace());
+        parse_err("import core { *, sin }");
+    }
+
+    #[test]
+    fn import_namespace_alias() {
+        let p = parse("import coords as co");
+        assert_eq!(p.imports[0].namespace, "coords");
+        assert_eq!(p.imports[0].alias.as_deref(), Some("co"));
+        assert_eq!(p.imports[0].local_name(), "co");
+        assert!(p.imports[0].members.is_empty());
+    }
+
+    #[test]
+    fn import_member_alias() {
+        let p = parse("import shapes { circle as c, rect }");
+        let m = &p.imports[0].members;
+        assert_eq!(member_names(&p.imports[0]), vec!["circle", "rect"]);
+        assert_eq!(m[0].local_name(), "c");
+        assert_eq!(m[1].local_name(), "rect");
     }
 
     // ── state block ──────────────────────────────────────────────────────────
@@ -1045,7 +1452,28 @@ mod tests {
         match &p.items[1] {
             Item::Stmt(Stmt::Assign(a)) => {
                 assert_eq!(a.target.path(), &["x"][..]);
-                assert!(matches!(&a.value, Expr::BinOp { op: BinOp::Add, .. }));
+                assert_eq!(a.op, Some(BinOp::Add));
+                assert!(matches!(&a.value, Expr::Float(5.0, _)));
+            }
+            _ => panic!("expected Assign"),
+        }
+    }
+
+    #[test]
+    fn compound_assignment_on_field_and_index() {
+        let p = parse("s.t %= 2.0
+s.xs[i] -= 1.0");
+        match &p.items[0] {
+            Item::Stmt(Stmt::Assign(a)) => {
+                assert_eq!(a.target.path(), &["s", "t"][..]);
+                assert_eq!(a.op, Some(BinOp::Mod));
+            }
+            _ => panic!("expected Assign"),
+        }
+        match &p.items[1] {
+            Item::Stmt(Stmt::Assign(a)) => {
+                assert!(matches!(&a.target, AssignTarget::Indexed { path, indices } if path == &["s", "xs"] && indices.len() == 1));
+                assert_eq!(a.op, Some(BinOp::Sub));
             }
             _ => panic!("expected Assign"),
         }
@@ -1077,6 +1505,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn fn_def_default_param() {
+        let p = parse("fn grow(x: float, by: float = 1.0) -> float { return x + by }");
+        match &p.items[0] {
+            Item::FnDef(f) => {
+                assert!(f.params[0].default.is_none());
+                assert!(matches!(f.params[1].default, Some(Expr::Float(v, _)) if v == 1.0));
+            }
+            _ => panic!("expected FnDef"),
+        }
+    }
+
+    #[test]
+    fn default_param_must_be_trailing() {
+        let errs = parse_err("fn f(a: float = 1.0, b: float) { }");
+        assert!(errs.iter().any(|e| e.message.contains("`b` needs a default")));
+    }
+
     #[test]
     fn fn_def_void() {
         let p = parse("fn draw() { }");
@@ -1097,6 +1543,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn out_named_channel() {
+        let p = parse("out background << bg << s1");
+        match &p.items[0] {
+            Item::Stmt(Stmt::Out(o)) => {
+                assert_eq!(o.channel.as_deref(), Some("background"));
+                assert_eq!(o.shapes.len(), 2);
+            }
+            _ => panic!("expected Out"),
+        }
+    }
+
     #[test]
     fn out_chained() {
         let p = parse("out << bg << s1 << s2");
@@ -1181,6 +1639,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn for_in_range_with_step() {
+        let p = parse("for i in 0..10 step 2 { }");
+        match &p.items[0] {
+            Item::Stmt(Stmt::Foreach(f)) => {
+                assert_eq!(f.var_name, "i");
+                assert!(matches!(&f.iterable, Expr::Range { step: Some(_), .. }));
+            }
+            _ => panic!("expected Foreach"),
+        }
+    }
+
+    #[test]
+    fn foreach_loop_with_index() {
+        let p = parse("foreach i, v in values { out << v }");
+        match &p.items[0] {
+            Item::Stmt(Stmt::Foreach(f)) => {
+                assert_eq!(f.index_name.as_deref(), Some("i"));
+                assert_eq!(f.var_name, "v");
+            }
+            _ => panic!("expected Foreach"),
+        }
+    }
+
     #[test]
     fn foreach_loop_explicit_type() {
         let p = parse("foreach v: float in values { out << v }");
@@ -1252,6 +1734,77 @@ mod tests {
         }
     }
 
+    #[test]
+    fn interpolated_string() {
+        match parse_expr_src(r#""v={a.x}!""#) {
+            Expr::Interpolated(parts, _) => {
+                assert_eq!(parts.len(), 3);
+                assert!(matches!(&parts[0], Expr::StringLit(s, _) if s == "v="));
+                assert!(matches!(parts[1], Expr::Field { .. }));
+            }
+            _ => panic!("expected Interpolated"),
+        }
+    }
+
+    #[test]
+    fn interpolated_string_trailing_tokens_error() {
+        let errs = parse_err(r#"let s = "{a b}""#);
+        assert!(errs.iter().any(|e| e.message.contains("after interpolated expression")));
+    }
+
+    #[test]
+    fn list_comprehension_with_filter() {
+        match parse_expr_src("[x * 2.0 for x in xs if x > 1.0]") {
+            Expr::Comprehension { var_name, filter, .. } => {
+                assert_eq!(var_name, "x");
+                assert!(filter.is_some());
+            }
+            _ => panic!("expected Comprehension"),
+        }
+    }
+
+    #[test]
+    fn map_literal() {
+        let expr = parse_expr_src(r#"{ "a": 1.0, "b": 2.0 }"#);
+        match expr {
+            Expr::Map(entries, _) => {
+                assert_eq!(entries.len(), 2);
+                assert!(matches!(&entries[0].0, Expr::StringLit(k, _) if k == "a"));
+            }
+            _ => panic!("expected Map"),
+        }
+    }
+
+    #[test]
+    fn tuple_literal_vs_grouping() {
+        assert!(matches!(parse_expr_src("(1.0, 2.0)"), Expr::Tuple(ref items, _) if items.len() == 2));
+        assert!(matches!(parse_expr_src("(1.0)"), Expr::Float(..)));
+    }
+
+    #[test]
+    fn tuple_field_chain() {
+        // `t.0.1` must lex as two field accesses, not `t` `.` `0.1`
+        match parse_expr_src("t.0.1") {
+            Expr::Field { expr, field, .. } => {
+                assert_eq!(field, "1");
+                assert!(matches!(*expr, Expr::Field { ref field, .. } if field == "0"));
+            }
+            _ => panic!("expected Field"),
+        }
+    }
+
+    #[test]
+    fn destructure_decl() {
+        let p = parse("let (r, t): (float, float) = pair");
+        match &p.items[0] {
+            Item::Stmt(Stmt::Destructure(d)) => {
+                assert_eq!(d.names, vec!["r", "t"]);
+                assert_eq!(d.ty, Some(Type::Tuple(vec![Type::Float, Type::Float])));
+            }
+            _ => panic!("expected Destructure"),
+        }
+    }
+
     #[test]
     fn type_res() {
         let p = parse("let x: res<float> = try 1.0");
@@ -1274,6 +1827,92 @@ mod tests {
         }
     }
 
+    #[test]
+    fn type_map() {
+        let p = parse("let m: map[color] = {}");
+        match &p.items[0] {
+            Item::Stmt(Stmt::VarDecl(v)) => {
+                assert_eq!(v.ty, Some(Type::Map(Box::new(Type::Named("color".into())))));
+            }
+            _ => panic!("expected VarDecl"),
+        }
+    }
+
+    #[test]
+    fn type_alias_expands_at_use_sites() {
+        let p = parse("fn f(ps: Points) -> Points { return ps }\ntype Points = list[vec2]");
+        let points = Type::List(Box::new(Type::Named("vec2".into())));
+        match &p.items[0] {
+            Item::FnDef(f) => {
+                assert_eq!(f.params[0].ty, points);
+                assert_eq!(f.return_ty, Some(points.clone()));
+            }
+            _ => panic!("expected FnDef"),
+        }
+        match &p.items[1] {
+            Item::TypeAlias(a) => assert_eq!((a.name.as_str(), &a.ty), ("Points", &points)),
+            _ => panic!("expected TypeAlias"),
+        }
+    }
+
+    #[test]
+    fn type_alias_cycle_is_an_error() {
+        let errs = parse_err("type A = list[B]\ntype B = A");
+        assert!(errs.iter().any(|e| e.code == ErrorCode::P002 && e.message.contains("refers to itself")));
+    }
+
+    #[test]
+    fn type_stays_usable_as_identifier() {
+        let p = parse("let type = 1.0\nout << type");
+        assert_eq!(p.items.len(), 2);
+    }
+
+    #[test]
+    fn struct_def_and_literal() {
+        let p = parse("let p = P { a: 1.0, b: vec2(0.0, 0.0) }\nstruct P { a: float, b: vec2 }");
+        match &p.items[1] {
+            Item::StructDef(s) => {
+                let fields: Vec<(&str, &Type)> = s.fields.iter().map(|f| (f.name.as_str(), &f.ty)).collect();
+                assert_eq!(fields, [("a", &Type::Float), ("b", &Type::Named("vec2".into()))]);
+            }
+            _ => panic!("expected StructDef"),
+        }
+        match &p.items[0] {
+            Item::Stmt(Stmt::VarDecl(v)) => match &v.initializer {
+                Expr::StructLit { name, fields, .. } => {
+                    assert_eq!(name, "P");
+                    assert_eq!(fields.iter().map(|(n, _)| n.as_str()).collect::<Vec<_>>(), ["a", "b"]);
+                }
+                other => panic!("expected StructLit, got {other:?}"),
+            },
+            _ => panic!("expected VarDecl"),
+        }
+    }
+
+    #[test]
+    fn enum_def_and_variant_access() {
+        let p = parse("enum Mode { Menu, Playing, Paused, }\nlet m = Mode.Menu");
+        match &p.items[0] {
+            Item::EnumDef(e) => {
+                assert_eq!(e.name, "Mode");
+                assert_eq!(e.variants, ["Menu", "Playing", "Paused"]);
+            }
+            _ => panic!("expected EnumDef"),
+        }
+        match &p.items[1] {
+            Item::Stmt(Stmt::VarDecl(v)) => {
+                assert!(matches!(&v.initializer, Expr::Field { field, .. } if field == "Menu"));
+            }
+            _ => panic!("expected VarDecl"),
+        }
+    }
+
+    #[test]
+    fn brace_after_non_struct_ident_is_a_block() {
+        let p = parse("struct P { a: float }\nlet ready = true\nif ready { out << 1.0 }");
+        assert!(matches!(&p.items[2], Item::Stmt(Stmt::If(_))));
+    }
+
     #[test]
     fn named_type_in_fn() {
         let p = parse("fn on_update(s: State, i: Input) -> State { return s }");
@@ -1375,6 +2014,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn propagate_vs_ternary() {
+        // `?` followed by an operator is propagation; followed by an operand it starts a ternary
+        let expr = parse_expr_src("f(x)? + 1.0");
+        match expr {
+            Expr::BinOp { left, .. } => assert!(matches!(*left, Expr::Propagate { .. })),
+            _ => panic!("expected BinOp with Propagate on the left"),
+        }
+        assert!(matches!(parse_expr_src("r ? a : b"), Expr::Ternary { .. }));
+        // at the end of a line it is always propagation
+        let p = parse("fn g() -> res<float> {\nlet v = f()?\nx = v\nreturn ok(v)\n}");
+        match &p.items[0] {
+            Item::FnDef(f) => assert_eq!(f.body.len(), 3),
+            _ => panic!("expected FnDef"),
+        }
+    }
+
     // ── error recovery ────────────────────────────────────────────────────────
 
     #[test]
@@ -1398,6 +2054,12 @@ mod tests {
         assert!(matches!(expr, Expr::Float(42.0, _)));
     }
 
+    #[test]
+    fn int_literal_expr() {
+        let expr = parse_expr_src("42");
+        assert!(matches!(expr, Expr::Int(42, _)));
+    }
+
     #[test]
     fn bool_literal_true() {
         let expr = parse_expr_src("true");
@@ -1463,6 +2125,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn prefix_inc_dec_on_field() {
+        match parse_expr_src("++s.x") {
+            Expr::UnOp { op: UnOp::PrefixInc, operand, .. } => {
+                assert!(matches!(*operand, Expr::Field { .. }));
+            }
+            _ => panic!("expected PrefixInc"),
+        }
+        assert!(matches!(parse_expr_src("--x"), Expr::UnOp { op: UnOp::PrefixDec, .. }));
+    }
+
+    #[test]
+    fn postfix_inc_dec_on_index() {
+        match parse_expr_src("xs[i]++") {
+            Expr::UnOp { op: UnOp::PostfixInc, operand, .. } => {
+                assert!(matches!(*operand, Expr::Index { .. }));
+            }
+            _ => panic!("expected PostfixInc"),
+        }
+        assert!(matches!(parse_expr_src("s.x--"), Expr::UnOp { op: UnOp::PostfixDec, .. }));
+    }
+
     // ── simple: binary operators ──────────────────────────────────────────────
 
     #[test]
@@ -1472,6 +2156,7 @@ mod tests {
         assert!(matches!(parse_expr_src("a * b"), Expr::BinOp { op: BinOp::Mul, .. }));
         assert!(matches!(parse_expr_src("a / b"), Expr::BinOp { op: BinOp::Div, .. }));
         assert!(matches!(parse_expr_src("a % b"), Expr::BinOp { op: BinOp::Mod, .. }));
+        assert!(matches!(parse_expr_src("a ** b"), Expr::BinOp { op: BinOp::Pow, .. }));
     }
 
     #[test]
@@ -1484,6 +2169,20 @@ mod tests {
         assert!(matches!(parse_expr_src("a >= b"),  Expr::BinOp { op: BinOp::GtEq, .. }));
     }
 
+    #[test]
+    fn chained_comparison() {
+        match parse_expr_src("a < b + 1.0 <= c") {
+            Expr::CompareChain { operands, ops, .. } => {
+                assert_eq!(ops, [BinOp::Lt, BinOp::LtEq]);
+                assert_eq!(operands.len(), 3);
+                assert!(matches!(operands[1], Expr::BinOp { op: BinOp::Add, .. }));
+            }
+            other => panic!("expected CompareChain, got {other:?}"),
+        }
+        // equality binds looser, so it compares the whole chain
+        assert!(matches!(parse_expr_src("a < b < c == d"), Expr::BinOp { op: BinOp::Eq, .. }));
+    }
+
     #[test]
     fn logical_and_or() {
         assert!(matches!(parse_expr_src("a and b"), Expr::BinOp { op: BinOp::And, .. }));
@@ -1502,6 +2201,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn power_binds_tighter_and_right_assoc() {
+        // a * b ** c ** d → Mul(a, Pow(b, Pow(c, d)))
+        let expr = parse_expr_src("a * b ** c ** d");
+        let Expr::BinOp { op: BinOp::Mul, right, .. } = expr else { panic!("expected Mul at top") };
+        let Expr::BinOp { op: BinOp::Pow, right, .. } = *right else { panic!("expected Pow") };
+        assert!(matches!(*right, Expr::BinOp { op: BinOp::Pow, .. }));
+        // -a ** b → Neg(Pow(a, b))
+        let expr = parse_expr_src("-a ** b");
+        let Expr::UnOp { op: UnOp::Neg, operand, .. } = expr else { panic!("expected Neg at top") };
+        assert!(matches!(*operand, Expr::BinOp { op: BinOp::Pow, .. }));
+    }
+
     #[test]
     fn operator_precedence_compare_over_logical() {
         // a and b > c → And(a, Gt(b, c))
@@ -1521,7 +2233,7 @@ mod tests {
         let expr = parse_expr_src("xs[2]");
         match expr {
             Expr::Index { index, .. } => {
-                assert!(matches!(*index, Expr::Float(2.0, _)));
+                assert!(matches!(*index, Expr::Int(2, _)));
             }
             _ => panic!("expected Index"),
         }
@@ -1710,13 +2422,13 @@ mod tests {
         assert_eq!(p.imports.len(), 2);
         assert_eq!(p.imports[0].namespace, "shapes");
         assert_eq!(p.imports[1].namespace, "render");
-        assert_eq!(p.imports[1].members, vec!["sdf", "fill"]);
+        assert_eq!(member_names(&p.imports[1]), vec!["sdf", "fill"]);
     }
 
     #[test]
     fn import_single_member() {
         let p = parse("import coords { px }");
-        assert_eq!(p.imports[0].members, vec!["px"]);
+        assert_eq!(member_names(&p.imports[0]), vec!["px"]);
     }
 
     // ── complex: expressions ──────────────────────────────────────────────────
@@ -1779,7 +2491,7 @@ mod tests {
         match expr {
             Expr::Index { expr, index, .. } => {
                 assert!(matches!(*expr, Expr::Call { .. }));
-                assert!(matches!(*index, Expr::Float(0.0, _)));
+                assert!(matches!(*index, Expr::Int(0, _)));
             }
             _ => panic!("expected Index on Call"),
         }
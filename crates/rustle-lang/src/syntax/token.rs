@@ -1,10 +1,13 @@
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenKind {
     // Literals
+    Int(i64),
     Float(f64),
     Bool(bool),
     Ident(String),
     StringLit(String),
+    /// `"t={x}"` — a string literal with at least one `{expr}` part
+    InterpString(Vec<StrPart>),
     HexColor(String), // digits only — "ff0000" or "ff0000ff"
 
     // Keywords
@@ -29,6 +32,7 @@ pub enum TokenKind {
     As,
 
     // Type keywords — only true primitives and parameterised collection types
+    TInt,
     TFloat,
     TBool,
     TArray,
@@ -39,6 +43,7 @@ pub enum TokenKind {
     Plus,       // +
     Minus,      // -
     Star,       // *
+    StarStar,   // **
     Slash,      // /
     Percent,    // %
     PlusEq,     // +=
@@ -47,6 +52,7 @@ pub enum TokenKind {
     MinusMinus, // --
     StarEq,     // *=
     SlashEq,    // /=
+    PercentEq,  // %=
     Eq,         // =
     EqEq,       // ==
     BangEq,     // !=
@@ -65,6 +71,7 @@ pub enum TokenKind {
     Comma,      // ,
     Semicolon,  // ;
     Dot,        // .
+    DotDot,     // ..
     LParen,     // (
     RParen,     // )
     LBrace,     // {
@@ -77,11 +84,11 @@ pub enum TokenKind {
 
 impl TokenKind {
     pub fn is_literal(&self) -> bool {
-        matches!(self, Self::Float(_) | Self::Bool(_) | Self::StringLit(_) | Self::HexColor(_))
+        matches!(self, Self::Int(_) | Self::Float(_) | Self::Bool(_) | Self::StringLit(_) | Self::InterpString(_) | Self::HexColor(_))
     }
 
     pub fn is_arithmetic(&self) -> bool {
-        matches!(self, Self::Plus | Self::Minus | Self::Star | Self::Slash | Self::Percent)
+        matches!(self, Self::Plus | Self::Minus | Self::Star | Self::StarStar | Self::Slash | Self::Percent)
     }
 
     pub fn is_comparison(&self) -> bool {
@@ -93,7 +100,7 @@ impl TokenKind {
     }
 
     pub fn is_type_keyword(&self) -> bool {
-        matches!(self, Self::TFloat | Self::TBool | Self::TArray | Self::TList | Self::TRes)
+        matches!(self, Self::TInt | Self::TFloat | Self::TBool | Self::TArray | Self::TList | Self::TRes)
     }
 
     pub fn is_keyword(&self) -> bool {
@@ -130,6 +137,7 @@ pub fn keyword_or_ident(s: String) -> TokenKind {
         "match"     => TokenKind::Match,
         "true"      => TokenKind::Bool(true),
         "false"     => TokenKind::Bool(false),
+        "int"       => TokenKind::TInt,
         "float"     => TokenKind::TFloat,
         "bool"      => TokenKind::TBool,
         "array"     => TokenKind::TArray,
@@ -141,7 +149,15 @@ pub fn keyword_or_ident(s: String) -> TokenKind {
 
 // ─────────────────────────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone)]
+/// One piece of an interpolated string literal.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StrPart {
+    Lit(String),
+    /// Tokens of an `{expr}` part, ending in `Eof`.
+    Expr(Vec<Token>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Token {
     pub kind: TokenKind,
     pub line: usize,
@@ -32,7 +32,9 @@ impl BinopRegistry {
     /// Return the result type key for `lhs op rhs`, or `None` if not registered.
     /// Used by the type checker at compile time.
     pub fn result_type(&self, op: &BinOp, lhs: &'static str, rhs: &'static str) -> Option<&'static str> {
-        self.ops.get(&(op.clone(), lhs, rhs)).map(|(ret, _)| *ret)
+        self.ops.get(&(op.clone(), lhs, rhs))
+            .or_else(|| self.ops.get(&(op.clone(), promote_key(lhs), promote_key(rhs))))
+            .map(|(ret, _)| *ret)
     }
 
     /// Evaluate `l op r`. Returns `None` if no handler is registered for this
@@ -46,7 +48,24 @@ impl BinopRegistry {
     ) -> Option<Result<Value, RuntimeError>> {
         let lkey = value_type_key(&l);
         let rkey = value_type_key(&r);
-        self.ops.get(&(op.clone(), lkey, rkey)).map(|(_, f)| f(l, r, line))
+        if let Some((_, f)) = self.ops.get(&(op.clone(), lkey, rkey)) {
+            return Some(f(l, r, line));
+        }
+        // No exact handler: retry with `int` operands promoted to `float`.
+        let (_, f) = self.ops.get(&(op.clone(), promote_key(lkey), promote_key(rkey)))?;
+        Some(f(promote(l), promote(r), line))
+    }
+}
+
+/// `int` operands fall back to the `float` handler when no `int` one exists.
+fn promote_key(key: &'static str) -> &'static str {
+    if key == "int" { "float" } else { key }
+}
+
+fn promote(v: Value) -> Value {
+    match v {
+        Value::Int(n) => Value::Float(n as f64),
+        other => other,
     }
 }
 
@@ -57,9 +76,11 @@ use crate::syntax::ast::Type;
 /// Map a `Type` to its BinopRegistry key. Returns `None` for generic/compound types.
 pub fn type_to_key(ty: &Type) -> Option<&'static str> {
     match ty {
+        Type::Int                            => Some("int"),
         Type::Float                          => Some("float"),
         Type::Bool                           => Some("bool"),
         Type::Named(n) => match n.as_str() {
+            "string"    => Some("string"),
             "vec2"      => Some("vec2"),
             "vec3"      => Some("vec3"),
             "vec4"      => Some("vec4"),
@@ -77,6 +98,7 @@ pub fn type_to_key(ty: &Type) -> Option<&'static str> {
 /// Map a BinopRegistry return-type key back to a `Type`.
 pub fn key_to_type(key: &str) -> Type {
     match key {
+        "int"   => Type::Int,
         "float" => Type::Float,
         "bool"  => Type::Bool,
         n       => Type::Named(n.to_string()),
@@ -86,18 +108,60 @@ pub fn key_to_type(key: &str) -> Type {
 impl Default for BinopRegistry {
     fn default() -> Self {
         let mut r = Self::new();
+        register_int(&mut r);
         register_float(&mut r);
         register_vec2(&mut r);
         register_vec3(&mut r);
         register_vec4(&mut r);
         register_color(&mut r);
         register_bool(&mut r);
+        register_string(&mut r);
         register_mat3(&mut r);
         register_mat4(&mut r);
         r
     }
 }
 
+// ─── int ──────────────────────────────────────────────────────────────────────
+
+/// `+`, `-`, `*` and `%` stay integral (overflow is a runtime error);
+/// `/` and `**` produce a float, as they do for mixed operands.
+fn register_int(r: &mut BinopRegistry) {
+    use BinOp::*;
+    r.register(Add, "int", "int", "int", |l, r, line| {
+        let (Value::Int(a), Value::Int(b)) = (l, r) else { unreachable!() };
+        a.checked_add(b).map(Value::Int).ok_or_else(|| RuntimeError::new(line, "integer overflow"))
+    });
+    r.register(Sub, "int", "int", "int", |l, r, line| {
+        let (Value::Int(a), Value::Int(b)) = (l, r) else { unreachable!() };
+        a.checked_sub(b).map(Value::Int).ok_or_else(|| RuntimeError::new(line, "integer overflow"))
+    });
+    r.register(Mul, "int", "int", "int", |l, r, line| {
+        let (Value::Int(a), Value::Int(b)) = (l, r) else { unreachable!() };
+        a.checked_mul(b).map(Value::Int).ok_or_else(|| RuntimeError::new(line, "integer overflow"))
+    });
+    r.register(Div, "int", "int", "float", |l, r, line| {
+        let (Value::Int(a), Value::Int(b)) = (l, r) else { unreachable!() };
+        if b == 0 { Err(RuntimeError::new(line, "division by zero")) }
+        else { Ok(Value::Float(a as f64 / b as f64)) }
+    });
+    r.register(Mod, "int", "int", "int", |l, r, line| {
+        let (Value::Int(a), Value::Int(b)) = (l, r) else { unreachable!() };
+        if b == 0 { return Err(RuntimeError::new(line, "mod by zero")); }
+        a.checked_rem(b).map(Value::Int).ok_or_else(|| RuntimeError::new(line, "integer overflow"))
+    });
+    r.register(Pow, "int", "int", "float", |l, r, _| {
+        let (Value::Int(a), Value::Int(b)) = (l, r) else { unreachable!() };
+        Ok(Value::Float((a as f64).powf(b as f64)))
+    });
+    r.register(Lt,   "int", "int", "bool", |l, r, _| { let (Value::Int(a), Value::Int(b)) = (l, r) else { unreachable!() }; Ok(Value::Bool(a <  b)) });
+    r.register(LtEq, "int", "int", "bool", |l, r, _| { let (Value::Int(a), Value::Int(b)) = (l, r) else { unreachable!() }; Ok(Value::Bool(a <= b)) });
+    r.register(Gt,   "int", "int", "bool", |l, r, _| { let (Value::Int(a), Value::Int(b)) = (l, r) else { unreachable!() }; Ok(Value::Bool(a >  b)) });
+    r.register(GtEq, "int", "int", "bool", |l, r, _| { let (Value::Int(a), Value::Int(b)) = (l, r) else { unreachable!() }; Ok(Value::Bool(a >= b)) });
+    r.register(Eq,   "int", "int", "bool", |l, r, _| { let (Value::Int(a), Value::Int(b)) = (l, r) else { unreachable!() }; Ok(Value::Bool(a == b)) });
+    r.register(NotEq,"int", "int", "bool", |l, r, _| { let (Value::Int(a), Value::Int(b)) = (l, r) else { unreachable!() }; Ok(Value::Bool(a != b)) });
+}
+
 // ─── float ────────────────────────────────────────────────────────────────────
 
 fn register_float(r: &mut BinopRegistry) {
@@ -124,6 +188,10 @@ fn register_float(r: &mut BinopRegistry) {
         if b == 0.0 { Err(RuntimeError::new(line, "mod by zero")) }
         else { Ok(Value::Float(a % b)) }
     });
+    r.register(Pow, "float", "float", "float", |l, r, _| {
+        let (Value::Float(a), Value::Float(b)) = (l, r) else { unreachable!() };
+        Ok(Value::Float(a.powf(b)))
+    });
     r.register(Lt,   "float", "float", "bool", |l, r, _| { let (Value::Float(a), Value::Float(b)) = (l, r) else { unreachable!() }; Ok(Value::Bool(a <  b)) });
     r.register(LtEq, "float", "float", "bool", |l, r, _| { let (Value::Float(a), Value::Float(b)) = (l, r) else { unreachable!() }; Ok(Value::Bool(a <= b)) });
     r.register(Gt,   "float", "float", "bool", |l, r, _| { let (Value::Float(a), Value::Float(b)) = (l, r) else { unreachable!() }; Ok(Value::Bool(a >  b)) });
@@ -257,6 +325,26 @@ fn register_bool(r: &mut BinopRegistry) {
     r.register(NotEq, "bool", "bool", "bool", |l, r, _| { let (Value::Bool(a), Value::Bool(b)) = (l, r) else { unreachable!() }; Ok(Value::Bool(a != b)) });
 }
 
+// ─── string ───────────────────────────────────────────────────────────────────
+
+fn register_string(r: &mut BinopRegistry) {
+    use BinOp::*;
+    r.register(Add, "string", "string", "string", |l, r, _| {
+        let (Value::Str(a), Value::Str(b)) = (l, r) else { unreachable!() };
+        Ok(Value::Str(a + &b))
+    });
+    // Floats use Rust's shortest round-trip formatting: 3.0 → "3", 0.5 → "0.5".
+    r.register(Add, "string", "float", "string", |l, r, _| {
+        let (Value::Str(a), Value::Float(b)) = (l, r) else { unreachable!() };
+        Ok(Value::Str(format!("{a}{b}")))
+    });
+    // Lexicographic by byte, like Rust's `str` ordering.
+    r.register(Lt,   "string", "string", "bool", |l, r, _| { let (Value::Str(a), Value::Str(b)) = (l, r) else { unreachable!() }; Ok(Value::Bool(a <  b)) });
+    r.register(LtEq, "string", "string", "bool", |l, r, _| { let (Value::Str(a), Value::Str(b)) = (l, r) else { unreachable!() }; Ok(Value::Bool(a <= b)) });
+    r.register(Gt,   "string", "string", "bool", |l, r, _| { let (Value::Str(a), Value::Str(b)) = (l, r) else { unreachable!() }; Ok(Value::Bool(a >  b)) });
+    r.register(GtEq, "string", "string", "bool", |l, r, _| { let (Value::Str(a), Value::Str(b)) = (l, r) else { unreachable!() }; Ok(Value::Bool(a >= b)) });
+}
+
 // ─── mat3 ─────────────────────────────────────────────────────────────────────
 
 fn register_mat3(r: &mut BinopRegistry) {
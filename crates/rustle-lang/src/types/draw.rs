@@ -12,6 +12,34 @@ impl Default for Origin {
 }
 
 impl Origin {
+    /// Every origin, in the order the `coords` and `shapes` namespaces export them.
+    pub const ALL: [Origin; 9] = [
+        Origin::Center,
+        Origin::TopLeft, Origin::TopRight,
+        Origin::BottomLeft, Origin::BottomRight,
+        Origin::Top, Origin::Bottom, Origin::Left, Origin::Right,
+    ];
+
+    /// Script-facing constant name, e.g. `top_left`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Origin::Center      => "center",
+            Origin::TopLeft     => "top_left",
+            Origin::TopRight    => "top_right",
+            Origin::BottomLeft  => "bottom_left",
+            Origin::BottomRight => "bottom_right",
+            Origin::Top         => "top",
+            Origin::Bottom      => "bottom",
+            Origin::Left        => "left",
+            Origin::Right       => "right",
+        }
+    }
+
+    /// Inverse of [`name`](Self::name).
+    pub fn from_name(name: &str) -> Option<Origin> {
+        Origin::ALL.into_iter().find(|o| o.name() == name)
+    }
+
     /// Canvas origins where y=0 is at the top and y increases downward (screen convention).
     pub fn is_y_down(self) -> bool {
         matches!(self, Origin::TopLeft | Origin::TopRight | Origin::Top)
@@ -30,15 +58,29 @@ pub struct CoordMeta {
     /// Center = (0,0) is screen center, y-up.
     /// TopLeft = (0,0) is top-left corner, y-down (screen/pixel convention).
     pub origin: Origin,
+    /// Aspect-normalized units (`normalize_aspect()`): one user unit is half
+    /// the shorter canvas side on both axes, so shapes keep their proportions.
+    pub normalized: bool,
+    /// Y direction set by `y_up()` / `y_down()`. `None` follows the origin.
+    pub y_down: Option<bool>,
 }
 
 impl Default for CoordMeta {
     fn default() -> Self {
-        Self { px_width: 0.0, px_height: 0.0, origin: Origin::Center }
+        Self { px_width: 0.0, px_height: 0.0, origin: Origin::Center, normalized: false, y_down: None }
     }
 }
 
 impl CoordMeta {
+    /// Pixels per user unit. 1.0 unless aspect-normalized with a resolution set.
+    pub fn unit_px(&self) -> f64 {
+        if self.normalized && self.px_width > 0.0 && self.px_height > 0.0 {
+            self.px_width.min(self.px_height) / 2.0
+        } else {
+            1.0
+        }
+    }
+
     /// Convert a position x coordinate to NDC [-1, 1].
     ///
     /// x=0 placement:
@@ -47,6 +89,7 @@ impl CoordMeta {
     ///   Center-x origins   (Center, Top, Bottom)          → NDC x = 0
     pub fn x_to_ndc(&self, x: f64) -> f64 {
         if self.px_width > 0.0 {
+            let x = x * self.unit_px();
             match self.origin {
                 Origin::Center | Origin::Top | Origin::Bottom
                     => 2.0 * x / self.px_width,
@@ -60,22 +103,31 @@ impl CoordMeta {
         }
     }
 
+    /// Whether user y increases down the screen: the `y_up()` / `y_down()`
+    /// override if set, otherwise the origin's convention.
+    pub fn is_y_down(&self) -> bool {
+        self.y_down.unwrap_or(self.origin.is_y_down())
+    }
+
+    /// Screen y of the origin as a fraction of the height, from the top.
+    fn y_anchor(&self) -> f64 {
+        match self.origin {
+            Origin::TopLeft | Origin::TopRight | Origin::Top          => 0.0,
+            Origin::Center | Origin::Left | Origin::Right             => 0.5,
+            Origin::BottomLeft | Origin::BottomRight | Origin::Bottom => 1.0,
+        }
+    }
+
     /// Convert a position y coordinate to NDC [-1, 1].
     ///
-    /// y=0 placement and direction:
-    ///   y-down (Top*)  → y=0 at screen top (NDC +1), y increases downward
-    ///   y-up   (Bot*)  → y=0 at screen bottom (NDC -1), y increases upward
-    ///   center (Center, Left, Right) → y=0 at screen center, y-up
+    /// y=0 placement follows the origin: screen top (NDC +1) for Top*, center
+    /// for Center/Left/Right, bottom (NDC -1) for Bottom*. Direction follows
+    /// [`is_y_down`](Self::is_y_down).
     pub fn y_to_ndc(&self, y: f64) -> f64 {
         if self.px_height > 0.0 {
-            match self.origin {
-                Origin::Center | Origin::Left | Origin::Right
-                    => 2.0 * y / self.px_height,
-                Origin::BottomLeft | Origin::BottomRight | Origin::Bottom
-                    => 2.0 * y / self.px_height - 1.0,
-                Origin::TopLeft | Origin::TopRight | Origin::Top
-                    => 1.0 - 2.0 * y / self.px_height,
-            }
+            let y = 2.0 * y * self.unit_px() / self.px_height;
+            let anchor = 1.0 - 2.0 * self.y_anchor();
+            if self.is_y_down() { anchor - y } else { anchor + y }
         } else {
             y
         }
@@ -83,23 +135,24 @@ impl CoordMeta {
 
     /// Convert a width/x-extent to NDC scale (no position bias).
     pub fn w_to_ndc(&self, w: f64) -> f64 {
-        if self.px_width > 0.0 { 2.0 * w / self.px_width } else { w }
+        if self.px_width > 0.0 { 2.0 * w * self.unit_px() / self.px_width } else { w }
     }
 
     /// Convert a height/y-extent to NDC scale (no position bias, always positive).
     pub fn h_to_ndc(&self, h: f64) -> f64 {
-        if self.px_height > 0.0 { 2.0 * h / self.px_height } else { h }
+        if self.px_height > 0.0 { 2.0 * h * self.unit_px() / self.px_height } else { h }
     }
 
     /// Convert a y-direction translation delta to NDC (respects y-axis direction).
     pub fn dy_to_ndc(&self, dy: f64) -> f64 {
-        let scale = if self.px_height > 0.0 { 2.0 / self.px_height } else { 1.0 };
-        if self.origin.is_y_down() { -dy * scale } else { dy * scale }
+        let scale = if self.px_height > 0.0 { 2.0 * self.unit_px() / self.px_height } else { 1.0 };
+        if self.is_y_down() { -dy * scale } else { dy * scale }
     }
 
     /// Convert a user-space x to screen pixels (0 = left edge of canvas).
     pub fn x_to_screen_px(&self, x: f64) -> f64 {
         if self.px_width > 0.0 {
+            let x = x * self.unit_px();
             match self.origin {
                 Origin::Center | Origin::Top | Origin::Bottom
                     => self.px_width / 2.0 + x,
@@ -116,18 +169,41 @@ impl CoordMeta {
     /// Convert a user-space y to screen pixels (0 = top edge of canvas, y-down).
     pub fn y_to_screen_px(&self, y: f64) -> f64 {
         if self.px_height > 0.0 {
-            match self.origin {
-                Origin::TopLeft | Origin::TopRight | Origin::Top
-                    => y,                                        // already screen px
-                Origin::Center | Origin::Left | Origin::Right
-                    => self.px_height / 2.0 - y,                // y-up from center
-                Origin::BottomLeft | Origin::BottomRight | Origin::Bottom
-                    => self.px_height - y,                       // y-up from bottom
-            }
+            let y = y * self.unit_px();
+            let anchor = self.px_height * self.y_anchor();
+            if self.is_y_down() { anchor + y } else { anchor - y }
         } else {
             y
         }
     }
+
+    /// Inverse of [`x_to_screen_px`](Self::x_to_screen_px).
+    pub fn screen_px_to_x(&self, px: f64) -> f64 {
+        if self.px_width > 0.0 {
+            let x = match self.origin {
+                Origin::Center | Origin::Top | Origin::Bottom
+                    => px - self.px_width / 2.0,
+                Origin::TopLeft | Origin::BottomLeft | Origin::Left
+                    => px,
+                Origin::TopRight | Origin::BottomRight | Origin::Right
+                    => self.px_width - px,
+            };
+            x / self.unit_px()
+        } else {
+            px
+        }
+    }
+
+    /// Inverse of [`y_to_screen_px`](Self::y_to_screen_px).
+    pub fn screen_px_to_y(&self, py: f64) -> f64 {
+        if self.px_height > 0.0 {
+            let dy = py - self.px_height * self.y_anchor();
+            let y = if self.is_y_down() { dy } else { -dy };
+            y / self.unit_px()
+        } else {
+            py
+        }
+    }
 }
 
 // ─── Shape description ────────────────────────────────────────────────────────
@@ -140,6 +216,9 @@ pub enum ShapeDesc {
     Rect   { center: (f64, f64), size: (f64, f64), origin: Origin },
     Line   { from: (f64, f64), to: (f64, f64) },
     Polygon(Vec<(f64, f64)>),
+    /// Pie slice from `start_angle` to `end_angle` (radians, counter-clockwise
+    /// in user space). Its outline always closes through the center.
+    Wedge  { center: (f64, f64), radius: f64, start_angle: f64, end_angle: f64 },
 }
 
 impl ShapeDesc {
@@ -151,6 +230,7 @@ impl ShapeDesc {
             Self::Rect   { center, .. } => *center,
             Self::Line   { from, .. }   => *from,
             Self::Polygon(pts) => pts.first().copied().unwrap_or((0.0, 0.0)),
+            Self::Wedge  { center, .. } => *center,
         }
     }
 }
@@ -174,44 +254,173 @@ pub fn origin_offset(origin: &Origin, hw: f64, hh: f64) -> (f64, f64) {
 // ─── Transform ────────────────────────────────────────────────────────────────
 
 /// Transform stored alongside a shape. The tessellator applies it in NDC space
-/// after coordinate conversion.
+/// after coordinate conversion, in the order scale → shear → rotate → translate.
 #[derive(Debug, Clone)]
 pub struct TransformData {
     pub tx:    f64,
     pub ty:    f64,
     pub sx:    f64,
     pub sy:    f64,
+    /// Shear factors in user space: x += shx·y, y += shy·x.
+    pub shx:   f64,
+    pub shy:   f64,
     pub angle: f64,
+    /// User-space point to scale/shear/rotate about. `None` = shape centroid.
+    pub pivot: Option<(f64, f64)>,
 }
 
 impl Default for TransformData {
     fn default() -> Self {
-        Self { tx: 0.0, ty: 0.0, sx: 1.0, sy: 1.0, angle: 0.0 }
+        Self { tx: 0.0, ty: 0.0, sx: 1.0, sy: 1.0, shx: 0.0, shy: 0.0, angle: 0.0, pivot: None }
+    }
+}
+
+impl TransformData {
+    /// Map a user-space point through this transform about `pivot`, using the
+    /// tessellator's order: scale → shear → rotate → translate.
+    pub fn apply_point(&self, (x, y): (f64, f64), (px, py): (f64, f64)) -> (f64, f64) {
+        let dx = (x - px) * self.sx;
+        let dy = (y - py) * self.sy;
+        let (dx, dy) = (dx + self.shx * dy, dy + self.shy * dx);
+        let (sin_a, cos_a) = self.angle.sin_cos();
+        (px + dx * cos_a - dy * sin_a + self.tx, py + dx * sin_a + dy * cos_a + self.ty)
+    }
+
+    /// Inverse of [`apply_point`](Self::apply_point). `None` when the transform
+    /// collapses the plane (zero scale or degenerate shear).
+    pub fn invert_point(&self, (x, y): (f64, f64), (px, py): (f64, f64)) -> Option<(f64, f64)> {
+        let (sin_a, cos_a) = self.angle.sin_cos();
+        let (rx, ry) = (x - px - self.tx, y - py - self.ty);
+        let (dx, dy) = (rx * cos_a + ry * sin_a, -rx * sin_a + ry * cos_a);
+        let det = 1.0 - self.shx * self.shy;
+        if det == 0.0 || self.sx == 0.0 || self.sy == 0.0 { return None; }
+        let (dx, dy) = ((dx - self.shx * dy) / det, (dy - self.shy * dx) / det);
+        Some((px + dx / self.sx, py + dy / self.sy))
+    }
+
+    /// `self` followed by `next` as a single transform. `None` when no single
+    /// transform matches on every canvas: one side pivots on the shape's
+    /// centroid and the other on a fixed point, or both pivot on fixed points
+    /// that don't line up (`next`'s pivot must be where `self` moves its own).
+    pub fn then(&self, next: &Self) -> Option<Self> {
+        let pivot = if next.is_move() {
+            self.pivot
+        } else if self.is_move() {
+            // `next` pivots on a point `self` has only moved.
+            next.pivot.map(|(qx, qy)| (qx - self.tx, qy - self.ty))
+        } else {
+            match (self.pivot, next.pivot) {
+                (None, None) => None,
+                (Some(p), Some(q)) if q == (p.0 + self.tx, p.1 + self.ty) => Some(p),
+                _ => return None,
+            }
+        };
+        let (a, b) = (self.linear(), next.linear());
+        let m = [
+            b[0] * a[0] + b[1] * a[2], b[0] * a[1] + b[1] * a[3],
+            b[2] * a[0] + b[3] * a[2], b[2] * a[1] + b[3] * a[3],
+        ];
+        Some(Self::from_linear(m, (self.tx + next.tx, self.ty + next.ty), pivot))
+    }
+
+    /// Whether this only translates.
+    fn is_move(&self) -> bool {
+        self.sx == 1.0 && self.sy == 1.0 && self.shx == 0.0 && self.shy == 0.0 && self.angle == 0.0
+    }
+
+    /// Scale → shear → rotate as a row-major 2×2 matrix.
+    fn linear(&self) -> [f64; 4] {
+        let (sin_a, cos_a) = self.angle.sin_cos();
+        let (m00, m01, m10, m11) = (self.sx, self.shx * self.sy, self.shy * self.sx, self.sy);
+        [
+            cos_a * m00 - sin_a * m10, cos_a * m01 - sin_a * m11,
+            sin_a * m00 + cos_a * m10, sin_a * m01 + cos_a * m11,
+        ]
+    }
+
+    /// Splits a 2×2 matrix back into rotate · shear · scale. Of the rotation
+    /// that suits a proper map and the one that suits a mirrored map, the one
+    /// leaving the larger scales wins. Both are odd in the angle, so canvases
+    /// that flip an axis (and with it the angle and shear signs) agree.
+    fn from_linear(m: [f64; 4], (tx, ty): (f64, f64), pivot: Option<(f64, f64)>) -> Self {
+        let unrotate = |angle: f64| {
+            let (sin_a, cos_a) = angle.sin_cos();
+            [
+                cos_a * m[0] + sin_a * m[2], cos_a * m[1] + sin_a * m[3],
+                cos_a * m[2] - sin_a * m[0], cos_a * m[3] - sin_a * m[1],
+            ]
+        };
+        let spread = |n: &[f64; 4]| n[0].abs().min(n[3].abs());
+        let mut angle = (m[2] - m[1]).atan2(m[0] + m[3]);
+        let mut n = unrotate(angle);
+        let mirrored = (m[2] + m[1]).atan2(m[0] - m[3]);
+        if spread(&unrotate(mirrored)) > spread(&n) {
+            angle = mirrored;
+            n = unrotate(mirrored);
+        }
+        let (sx, sy) = (n[0], n[3]);
+        Self {
+            tx, ty, sx, sy,
+            shx: if sy == 0.0 { 0.0 } else { n[1] / sy },
+            shy: if sx == 0.0 { 0.0 } else { n[2] / sx },
+            angle,
+            pivot,
+        }
     }
 }
 
 // ─── Render mode ──────────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone)]
-pub enum RenderMode { Sdf, Fill, Outline, Stroke(f64) }
+pub enum RenderMode {
+    Sdf,
+    Fill,
+    Outline,
+    Stroke(f64),
+    /// Stroked outline broken into `on` px dashes separated by `off` px gaps.
+    Dashed { width: f64, on: f64, off: f64 },
+}
 
 impl Default for RenderMode {
     fn default() -> Self { Self::Sdf }
 }
 
+// ─── Color ────────────────────────────────────────────────────────────────────
+
+/// RGBA color with components in [0, 1], as produced by `#rrggbb` literals.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color {
+    pub r: f64,
+    pub g: f64,
+    pub b: f64,
+    pub a: f64,
+}
+
 // ─── Shape data ───────────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone)]
 pub struct ShapeData {
-    pub desc:        ShapeDesc,
-    pub render_mode: RenderMode,
-    pub coord_meta:  CoordMeta,
-    pub transforms:  Vec<TransformData>,
+    pub desc:         ShapeDesc,
+    pub render_mode:  RenderMode,
+    pub coord_meta:   CoordMeta,
+    pub transforms:   Vec<TransformData>,
+    /// Interior color (`fill:` named arg). `None` = renderer default.
+    pub fill:         Option<Color>,
+    /// Outline/line color (`stroke_color:` named arg). `None` = renderer default.
+    pub stroke_color: Option<Color>,
+    /// Draw order (`layer:` named arg). Lower layers are drawn first; equal
+    /// layers keep emission order.
+    pub layer:        f64,
+    /// Opacity multiplier for fill and stroke (`alpha:` named arg), in `[0, 1]`.
+    pub alpha:        f64,
 }
 
 impl ShapeData {
     pub fn new(desc: ShapeDesc, render_mode: RenderMode, coord_meta: CoordMeta) -> Self {
-        Self { desc, render_mode, coord_meta, transforms: Vec::new() }
+        Self {
+            desc, render_mode, coord_meta,
+            transforms: Vec::new(), fill: None, stroke_color: None, layer: 0.0, alpha: 1.0,
+        }
     }
 }
 
@@ -221,3 +430,11 @@ impl ShapeData {
 pub enum DrawCommand {
     DrawShape(ShapeData),
 }
+
+impl DrawCommand {
+    pub fn layer(&self) -> f64 {
+        match self {
+            Self::DrawShape(data) => data.layer,
+        }
+    }
+}
@@ -0,0 +1,428 @@
+//! Pure polygon clipping helpers — no Value types, no RuntimeError.
+//! Polygons are vertex rings in user space; the closing edge is implicit.
+//!
+//! Boolean operations follow Greiner–Hormann. Touching or collinear edges are
+//! handled by nudging the clip polygon off the degenerate position, so results
+//! are approximate to within a tiny fraction of the inputs' extent.
+//!
+//! Used by types/registry.rs (`shape.union` / `shape.difference`).
+
+pub type Pt = (f64, f64);
+
+#[derive(Clone, Copy, PartialEq)]
+enum Op { Union, Difference }
+
+/// Union of two simple polygons as a single ring.
+pub fn union(a: &[Pt], b: &[Pt]) -> Vec<Pt> {
+    clip(a, b, Op::Union)
+}
+
+/// `a` minus `b` as a single ring. Empty when `b` covers `a`.
+pub fn difference(a: &[Pt], b: &[Pt]) -> Vec<Pt> {
+    clip(a, b, Op::Difference)
+}
+
+/// Signed area by the shoelace formula — positive for counter-clockwise
+/// rings in a y-up space.
+pub fn signed_area(ring: &[Pt]) -> f64 {
+    let n = ring.len();
+    (0..n).map(|i| {
+        let (ax, ay) = ring[i];
+        let (bx, by) = ring[(i + 1) % n];
+        ax * by - bx * ay
+    }).sum::<f64>() / 2.0
+}
+
+/// Even-odd point-in-polygon test.
+pub fn point_in_ring(p: Pt, ring: &[Pt]) -> bool {
+    let (x, y) = p;
+    let mut inside = false;
+    for i in 0..ring.len() {
+        let (ax, ay) = ring[i];
+        let (bx, by) = ring[(i + 1) % ring.len()];
+        if (ay > y) != (by > y) && x < ax + (y - ay) / (by - ay) * (bx - ax) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Splits an outline back into the rings `merge_rings` joined, by dropping
+/// every seam — an edge that is also walked in the opposite direction.
+pub fn split_seams(ring: &[Pt]) -> Vec<Vec<Pt>> {
+    let n = ring.len();
+    let edge = |k: usize| (ring[k], ring[(k + 1) % n]);
+    let mut used: Vec<bool> = (0..n).map(|k| {
+        let (p, q) = edge(k);
+        p != q && (0..n).any(|m| edge(m) == (q, p))
+    }).collect();
+    let mut rings = Vec::new();
+    while let Some(start) = used.iter().position(|u| !u) {
+        let mut out = Vec::new();
+        let mut k = start;
+        loop {
+            used[k] = true;
+            out.push(ring[k]);
+            let end = ring[(k + 1) % n];
+            if end == ring[start] {
+                break;
+            }
+            // the next unused edge leaving `end`, preferring the one right after
+            match (1..=n).map(|m| (k + m) % n).find(|&m| !used[m] && ring[m] == end) {
+                Some(next) => k = next,
+                None => break,
+            }
+        }
+        rings.push(out);
+    }
+    rings
+}
+
+/// Area enclosed by an outline that may carry seams: each ring counts
+/// positive or negative by how many of the other rings surround it, so holes
+/// are subtracted whichever way they wind.
+pub fn area(ring: &[Pt]) -> f64 {
+    let rings = split_seams(ring);
+    rings.iter().enumerate().map(|(i, r)| {
+        let depth = rings.iter().enumerate().filter(|&(j, o)| j != i && point_in_ring(r[0], o)).count();
+        let a = signed_area(r).abs();
+        if depth % 2 == 0 { a } else { -a }
+    }).sum()
+}
+
+/// Length of an outline, leaving out seams.
+pub fn perimeter(ring: &[Pt]) -> f64 {
+    split_seams(ring).iter().map(|r| {
+        (0..r.len()).map(|i| {
+            let (p, q) = (r[i], r[(i + 1) % r.len()]);
+            (q.0 - p.0).hypot(q.1 - p.1)
+        }).sum::<f64>()
+    }).sum()
+}
+
+// ─── clipping ─────────────────────────────────────────────────────────────────
+
+/// A vertex of a polygon with the intersections spliced in.
+#[derive(Clone, Copy)]
+enum Node {
+    Vertex(Pt),
+    /// Index into the shared intersection list.
+    Cross(usize),
+}
+
+/// One side of the clip: the spliced ring, where each intersection sits in
+/// it, and whether walking forward from an intersection enters the other
+/// polygon.
+struct Walk {
+    nodes: Vec<Node>,
+    pos:   Vec<usize>,
+    entry: Vec<bool>,
+}
+
+fn clip(a: &[Pt], b: &[Pt], op: Op) -> Vec<Pt> {
+    let a = dedup(a);
+    let b = dedup(b);
+    if a.len() < 3 {
+        return if op == Op::Union { b } else { Vec::new() };
+    }
+    if b.len() < 3 {
+        return a;
+    }
+    let b = nudge(&a, &b);
+
+    // every proper crossing: (edge of a, param on a, edge of b, param on b, point)
+    let mut crosses = Vec::new();
+    for i in 0..a.len() {
+        let (p0, p1) = (a[i], a[(i + 1) % a.len()]);
+        for j in 0..b.len() {
+            let (q0, q1) = (b[j], b[(j + 1) % b.len()]);
+            if let Some((s, t)) = segment_params(p0, p1, q0, q1) {
+                let pt = (p0.0 + (p1.0 - p0.0) * s, p0.1 + (p1.1 - p0.1) * s);
+                crosses.push((i, s, j, t, pt));
+            }
+        }
+    }
+
+    if crosses.is_empty() {
+        return disjoint(a, b, op);
+    }
+
+    let points: Vec<Pt> = crosses.iter().map(|c| c.4).collect();
+    let by_a: Vec<(usize, f64)> = crosses.iter().map(|c| (c.0, c.1)).collect();
+    let by_b: Vec<(usize, f64)> = crosses.iter().map(|c| (c.2, c.3)).collect();
+    let mut walk_a = splice(&a, &by_a, &b);
+    let mut walk_b = splice(&b, &by_b, &a);
+
+    // Union keeps the outside of both; difference keeps the outside of `a`
+    // and the part of `b` inside `a`.
+    for e in walk_a.entry.iter_mut() { *e = !*e; }
+    if op == Op::Union {
+        for e in walk_b.entry.iter_mut() { *e = !*e; }
+    }
+
+    let mut rings = Vec::new();
+    let mut visited = vec![false; points.len()];
+    let budget = 2 * (walk_a.nodes.len() + walk_b.nodes.len());
+    while let Some(start) = visited.iter().position(|v| !v) {
+        let mut ring = Vec::new();
+        let mut k = start;
+        let mut on_a = true;
+        let mut steps = 0;
+        loop {
+            visited[k] = true;
+            ring.push(points[k]);
+            let walk = if on_a { &walk_a } else { &walk_b };
+            let len = walk.nodes.len();
+            let forward = walk.entry[k];
+            let mut idx = walk.pos[k];
+            loop {
+                idx = if forward { (idx + 1) % len } else { (idx + len - 1) % len };
+                steps += 1;
+                match walk.nodes[idx] {
+                    Node::Vertex(p) => ring.push(p),
+                    Node::Cross(next) => { k = next; break; }
+                }
+            }
+            on_a = !on_a;
+            if k == start || steps > budget {
+                break;
+            }
+        }
+        let ring = dedup(&ring);
+        if ring.len() >= 3 && signed_area(&ring).abs() > 0.0 {
+            rings.push(ring);
+        }
+    }
+    merge_rings(rings)
+}
+
+/// Builds the spliced ring for `poly`, given where each intersection falls on
+/// it as `(edge, param)`, and flags entries relative to `other`.
+fn splice(poly: &[Pt], at: &[(usize, f64)], other: &[Pt]) -> Walk {
+    let mut nodes = Vec::with_capacity(poly.len() + at.len());
+    let mut pos = vec![0; at.len()];
+    for (i, p) in poly.iter().enumerate() {
+        nodes.push(Node::Vertex(*p));
+        let mut on_edge: Vec<usize> = (0..at.len()).filter(|&k| at[k].0 == i).collect();
+        on_edge.sort_by(|&x, &y| at[x].1.total_cmp(&at[y].1));
+        for k in on_edge {
+            pos[k] = nodes.len();
+            nodes.push(Node::Cross(k));
+        }
+    }
+    let mut entry = vec![false; at.len()];
+    let mut inside = point_in_ring(poly[0], other);
+    for node in &nodes {
+        if let Node::Cross(k) = *node {
+            entry[k] = !inside;
+            inside = !inside;
+        }
+    }
+    Walk { nodes, pos, entry }
+}
+
+/// Result when the outlines never cross: one polygon contains the other or
+/// they are apart.
+fn disjoint(a: Vec<Pt>, b: Vec<Pt>, op: Op) -> Vec<Pt> {
+    let a_in_b = point_in_ring(a[0], &b);
+    let b_in_a = point_in_ring(b[0], &a);
+    match op {
+        Op::Union if a_in_b => b,
+        Op::Union if b_in_a => a,
+        Op::Union => merge_rings(vec![a, b]),
+        Op::Difference if a_in_b => Vec::new(),
+        Op::Difference if b_in_a => merge_rings(vec![a, b]),
+        Op::Difference => a,
+    }
+}
+
+/// Joins several rings into one by bridging each to the largest with a
+/// zero-width seam. Holes and separate pieces both survive the even-odd rule;
+/// holes are also wound against the largest ring, so the non-zero rule and the
+/// shoelace formula see them as cut out.
+fn merge_rings(mut rings: Vec<Vec<Pt>>) -> Vec<Pt> {
+    rings.sort_by(|x, y| signed_area(y).abs().total_cmp(&signed_area(x).abs()));
+    if let Some(outer) = rings.first() {
+        let ccw = signed_area(outer) > 0.0;
+        let depths: Vec<usize> = rings.iter().enumerate()
+            .map(|(i, r)| rings.iter().enumerate().filter(|&(j, o)| j != i && point_in_ring(r[0], o)).count())
+            .collect();
+        for (ring, depth) in rings.iter_mut().zip(depths) {
+            if (signed_area(ring) > 0.0) != (ccw == (depth % 2 == 0)) {
+                ring.reverse();
+            }
+        }
+    }
+    let mut rings = rings.into_iter();
+    let Some(mut merged) = rings.next() else { return Vec::new() };
+    for ring in rings {
+        let mut best = (0, 0, f64::INFINITY);
+        for (i, p) in merged.iter().enumerate() {
+            for (j, q) in ring.iter().enumerate() {
+                let d = (p.0 - q.0).powi(2) + (p.1 - q.1).powi(2);
+                if d < best.2 {
+                    best = (i, j, d);
+                }
+            }
+        }
+        let (i, j, _) = best;
+        let mut joined = Vec::with_capacity(merged.len() + ring.len() + 2);
+        joined.extend_from_slice(&merged[..=i]);
+        joined.extend_from_slice(&ring[j..]);
+        joined.extend_from_slice(&ring[..=j]);
+        joined.extend_from_slice(&merged[i..]);
+        merged = joined;
+    }
+    merged
+}
+
+/// Parameters `(s, t)` where segment `p0→p1` properly crosses `q0→q1`, both
+/// strictly inside `(0, 1)`.
+fn segment_params(p0: Pt, p1: Pt, q0: Pt, q1: Pt) -> Option<(f64, f64)> {
+    let (rx, ry) = (p1.0 - p0.0, p1.1 - p0.1);
+    let (sx, sy) = (q1.0 - q0.0, q1.1 - q0.1);
+    let denom = rx * sy - ry * sx;
+    if denom == 0.0 {
+        return None;
+    }
+    let (dx, dy) = (q0.0 - p0.0, q0.1 - p0.1);
+    let s = (dx * sy - dy * sx) / denom;
+    let t = (dx * ry - dy * rx) / denom;
+    (s > 0.0 && s < 1.0 && t > 0.0 && t < 1.0).then_some((s, t))
+}
+
+/// Drops consecutive duplicate vertices, including a repeated closing vertex.
+fn dedup(ring: &[Pt]) -> Vec<Pt> {
+    let mut out: Vec<Pt> = Vec::with_capacity(ring.len());
+    for p in ring {
+        if out.last() != Some(p) {
+            out.push(*p);
+        }
+    }
+    while out.len() > 1 && out.first() == out.last() {
+        out.pop();
+    }
+    out
+}
+
+/// Shifts `b` slightly when a vertex of either polygon lies on the other's
+/// outline, which the crossing test cannot classify.
+fn nudge(a: &[Pt], b: &[Pt]) -> Vec<Pt> {
+    let extent = a.iter().chain(b).fold(0.0_f64, |m, p| m.max(p.0.abs()).max(p.1.abs())).max(1.0);
+    let tol = extent * 1e-9;
+    let step = extent * 1e-7;
+    let offsets = [(0.0, 0.0), (step, step * 0.37), (-step * 0.53, step), (step * 0.71, -step * 0.29)];
+    for (ox, oy) in offsets {
+        let moved: Vec<Pt> = b.iter().map(|p| (p.0 + ox, p.1 + oy)).collect();
+        if !touches(a, &moved, tol) && !touches(&moved, a, tol) {
+            return moved;
+        }
+    }
+    b.to_vec()
+}
+
+/// Whether any vertex of `a` lies within `tol` of an edge of `b`.
+fn touches(a: &[Pt], b: &[Pt], tol: f64) -> bool {
+    a.iter().any(|p| (0..b.len()).any(|j| {
+        let (q0, q1) = (b[j], b[(j + 1) % b.len()]);
+        segment_distance(*p, q0, q1) <= tol
+    }))
+}
+
+fn segment_distance(p: Pt, q0: Pt, q1: Pt) -> f64 {
+    let (dx, dy) = (q1.0 - q0.0, q1.1 - q0.1);
+    let len2 = dx * dx + dy * dy;
+    let t = if len2 == 0.0 { 0.0 } else {
+        (((p.0 - q0.0) * dx + (p.1 - q0.1) * dy) / len2).clamp(0.0, 1.0)
+    };
+    let (cx, cy) = (q0.0 + dx * t, q0.1 + dy * t);
+    ((p.0 - cx).powi(2) + (p.1 - cy).powi(2)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(x: f64, y: f64, s: f64) -> Vec<Pt> {
+        vec![(x, y), (x + s, y), (x + s, y + s), (x, y + s)]
+    }
+
+    fn area(ring: &[Pt]) -> f64 {
+        signed_area(ring).abs()
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-5
+    }
+
+    #[test]
+    fn union_of_overlapping_squares() {
+        let u = union(&square(0.0, 0.0, 2.0), &square(1.0, 1.0, 2.0));
+        assert!(close(area(&u), 7.0), "area {}", area(&u));
+    }
+
+    #[test]
+    fn difference_of_overlapping_squares() {
+        let d = difference(&square(0.0, 0.0, 2.0), &square(1.0, 1.0, 2.0));
+        assert!(close(area(&d), 3.0), "area {}", area(&d));
+        assert!(point_in_ring((0.5, 0.5), &d));
+        assert!(!point_in_ring((1.5, 1.5), &d));
+    }
+
+    #[test]
+    fn difference_ignores_orientation() {
+        let mut b = square(1.0, 1.0, 2.0);
+        b.reverse();
+        let d = difference(&square(0.0, 0.0, 2.0), &b);
+        assert!(close(area(&d), 3.0), "area {}", area(&d));
+    }
+
+    #[test]
+    fn shared_edges_are_nudged() {
+        let u = union(&square(0.0, 0.0, 2.0), &square(2.0, 0.0, 2.0));
+        assert!(close(area(&u), 8.0), "area {}", area(&u));
+        let d = difference(&square(0.0, 0.0, 2.0), &square(1.0, 0.0, 2.0));
+        assert!(close(area(&d), 2.0), "area {}", area(&d));
+    }
+
+    #[test]
+    fn contained_shapes() {
+        let outer = square(0.0, 0.0, 4.0);
+        let inner = square(1.0, 1.0, 1.0);
+        assert!(close(area(&union(&outer, &inner)), 16.0));
+        assert!(difference(&inner, &outer).is_empty());
+        let holed = difference(&outer, &inner);
+        assert!(point_in_ring((0.5, 0.5), &holed));
+        assert!(!point_in_ring((1.5, 1.5), &holed));
+        // the hole is wound against the outline, so it subtracts
+        assert!(close(area(&holed), 15.0), "area {}", area(&holed));
+        let mut reversed = inner.clone();
+        reversed.reverse();
+        assert!(close(area(&difference(&outer, &reversed)), 15.0));
+    }
+
+    #[test]
+    fn measures_skip_seams() {
+        let holed = difference(&square(0.0, 0.0, 4.0), &square(1.0, 1.0, 1.0));
+        assert_eq!(split_seams(&holed).len(), 2);
+        assert!(close(super::area(&holed), 15.0), "area {}", super::area(&holed));
+        assert!(close(perimeter(&holed), 20.0), "perimeter {}", perimeter(&holed));
+        let pieces = union(&square(0.0, 0.0, 1.0), &square(5.0, 5.0, 1.0));
+        assert!(close(super::area(&pieces), 2.0));
+        assert!(close(perimeter(&pieces), 8.0));
+        assert!(close(perimeter(&square(0.0, 0.0, 2.0)), 8.0));
+    }
+
+    #[test]
+    fn apart_shapes() {
+        let a = square(0.0, 0.0, 1.0);
+        let b = square(5.0, 5.0, 1.0);
+        let u = union(&a, &b);
+        assert!(point_in_ring((0.5, 0.5), &u));
+        assert!(point_in_ring((5.5, 5.5), &u));
+        assert!(!point_in_ring((3.0, 3.0), &u));
+        // separate pieces keep the same winding, so their areas add up
+        assert!(close(area(&u), 2.0), "area {}", area(&u));
+        assert!(close(area(&difference(&a, &b)), 1.0));
+    }
+}
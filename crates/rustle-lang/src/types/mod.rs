@@ -2,3 +2,4 @@ pub mod draw;
 pub mod registry;
 pub mod binop_registry;
 pub mod mat;
+pub mod geometry;
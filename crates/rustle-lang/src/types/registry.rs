@@ -7,11 +7,17 @@
 //! Adding a new built-in type = registering one TypeDesc here.
 //! No edits to interpreter.rs or resolver/ needed.
 
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::rc::Rc;
 
 use crate::syntax::ast::Type;
 use crate::error::RuntimeError;
 use crate::runtime::value::Value;
+use crate::runtime::value::values_equal;
+use crate::types::draw::{Origin, ShapeData, ShapeDesc, TransformData};
+use crate::types::geometry;
+use crate::render::{CIRCLE_SEGMENTS, centroid, wedge_points};
 
 // ─── Function pointer aliases ─────────────────────────────────────────────────
 
@@ -56,21 +62,36 @@ pub struct TypeDesc {
 
 pub struct TypeRegistry {
     types: HashMap<&'static str, TypeDesc>,
+    /// User `struct` declarations — field names and types, resolver-side only.
+    /// At runtime a `Value::Struct` carries its own fields.
+    structs: HashMap<String, Vec<(String, Type)>>,
 }
 
 impl TypeRegistry {
     pub fn new() -> Self {
-        Self { types: HashMap::new() }
+        Self { types: HashMap::new(), structs: HashMap::new() }
     }
 
     pub fn register(&mut self, desc: TypeDesc) {
         self.types.insert(desc.name, desc);
     }
 
+    pub fn register_struct(&mut self, name: &str, fields: Vec<(String, Type)>) {
+        self.structs.insert(name.to_string(), fields);
+    }
+
+    /// Whether `name` is a registered built-in type.
+    pub fn contains(&self, name: &str) -> bool {
+        self.types.contains_key(name)
+    }
+
     // ── Resolver API — by name (used internally) ──────────────────────────────
 
     /// Return the type of `field` on a concrete type named `type_name`.
     pub fn field_type(&self, type_name: &str, field: &str) -> Option<Type> {
+        if let Some(fields) = self.structs.get(type_name) {
+            return fields.iter().find(|(n, _)| n == field).map(|(_, ty)| ty.clone());
+        }
         self.types.get(type_name)?
             .fields.iter()
             .find(|f| f.name == field)
@@ -101,11 +122,16 @@ impl TypeRegistry {
                 _ => None,
             },
             // Named types and primitives — delegate to the static descriptor table.
-            Type::Named(n) => self.field_type(n.as_str(), field),
+            // Vectors additionally accept swizzles (`v.xy`, `v.zyx`).
+            Type::Named(n) => self.field_type(n.as_str(), field)
+                .or_else(|| swizzle_type(n.as_str(), field)),
             Type::Float    => self.field_type("float", field),
             Type::Bool     => self.field_type("bool", field),
-            // list<T> only has .len; everything else returns None.
+            // list<T> and map<T> only have .len; everything else returns None.
             Type::List(_)  => self.field_type("list", field),
+            Type::Map(_)   => self.field_type("map", field),
+            // (T, U): `.0`, `.1`, …
+            Type::Tuple(items) => field.parse::<usize>().ok().and_then(|i| items.get(i).cloned()),
             _ => None,
         }
     }
@@ -120,6 +146,46 @@ impl TypeRegistry {
             Type::List(elem) => match method {
                 "push" => Some((vec![*elem.clone()], None)),
                 "pop"  => Some((vec![], Some(*elem.clone()))),
+                "insert" => Some((vec![Type::Float, *elem.clone()], None)),
+                "remove" => Some((vec![Type::Float], Some(*elem.clone()))),
+                "len"  => Some((vec![], Some(Type::Float))),
+                // map(fn(T) -> U) -> list<U>. U comes from the argument, so the
+                // checker refines this T -> T shape in `resolve_method_call`.
+                "map"  => Some((
+                    vec![Type::Fn(vec![*elem.clone()], Some(elem.clone()))],
+                    Some(Type::List(elem.clone())),
+                )),
+                "reverse"  => Some((vec![], Some(Type::List(elem.clone())))),
+                "slice"    => Some((vec![Type::Float, Type::Float], Some(Type::List(elem.clone())))),
+                "sort"     => Some((vec![], Some(Type::List(elem.clone())))),
+                "contains" => Some((vec![*elem.clone()], Some(Type::Bool))),
+                "filter" => Some((
+                    vec![Type::Fn(vec![*elem.clone()], Some(Box::new(Type::Bool)))],
+                    Some(Type::List(elem.clone())),
+                )),
+                // reduce(init: A, fn(A, T) -> A) -> A. Shown with A = T; the
+                // checker takes A from `init`.
+                "reduce" => Some((
+                    vec![*elem.clone(), Type::Fn(vec![*elem.clone(), *elem.clone()], Some(elem.clone()))],
+                    Some(*elem.clone()),
+                )),
+                _ => None,
+            },
+            // res<T>: both methods are dispatched by the interpreter.
+            Type::Res(inner) => match method {
+                "unwrap_or" => Some((vec![*inner.clone()], Some(*inner.clone()))),
+                // map(fn(T) -> U) -> res<U>. Shown with U = T; the checker
+                // takes U from the argument.
+                "map" => Some((
+                    vec![Type::Fn(vec![*inner.clone()], Some(inner.clone()))],
+                    Some(Type::Res(inner.clone())),
+                )),
+                _ => None,
+            },
+            // map<T>: string keys, so only the key-facing methods are typed here.
+            Type::Map(_) => match method {
+                "keys" => Some((vec![], Some(Type::List(Box::new(named("string")))))),
+                "has"  => Some((vec![named("string")], Some(Type::Bool))),
                 "len"  => Some((vec![], Some(Type::Float))),
                 _ => None,
             },
@@ -141,16 +207,27 @@ impl TypeRegistry {
     /// Get the value of `field` from `v`.
     /// Returns None if the type or field isn't registered.
     pub fn get_field(&self, v: &Value, field: &str) -> Option<Value> {
+        if let Value::Tuple(items) = v {
+            return field.parse::<usize>().ok().and_then(|i| items.get(i).cloned());
+        }
+        if let Value::Struct { fields, .. } = v {
+            return fields.iter().find(|(n, _)| n == field).map(|(_, v)| v.clone());
+        }
         let key = value_type_key(v);
         self.types.get(key)?
             .fields.iter()
             .find(|f| f.name == field)
             .map(|f| (f.get)(v))
+            .or_else(|| get_swizzle(v, field))
     }
 
     /// Return a new Value with `field` set to `new_val`.
     /// Returns None if the type/field isn't registered or the field is read-only.
     pub fn set_field(&self, v: Value, field: &str, new_val: Value) -> Option<Value> {
+        if let Value::Struct { name, mut fields } = v {
+            fields.iter_mut().find(|(n, _)| n == field)?.1 = new_val;
+            return Some(Value::Struct { name, fields });
+        }
         let key = value_type_key(&v);
         let setter = self.types.get(key)?
             .fields.iter()
@@ -205,7 +282,9 @@ impl Default for TypeRegistry {
         r.register(rect_desc());
         r.register(line_desc());
         r.register(polygon_desc());
+        r.register(wedge_desc());
         r.register(list_desc());
+        r.register(map_desc());
         r.register(res_desc());
         r.register(input_desc());
         r
@@ -219,6 +298,7 @@ impl Default for TypeRegistry {
 /// don't have statically-known field/method descriptors).
 pub fn value_type_key(v: &Value) -> &'static str {
     match v {
+        Value::Int(_)               => "int",
         Value::Float(_)             => "float",
         Value::Bool(_)              => "bool",
         Value::Str(_)               => "string",
@@ -234,8 +314,11 @@ pub fn value_type_key(v: &Value) -> &'static str {
             crate::types::draw::ShapeDesc::Rect   { .. } => "rect",
             crate::types::draw::ShapeDesc::Line   { .. } => "line",
             crate::types::draw::ShapeDesc::Polygon(_)    => "polygon",
+            crate::types::draw::ShapeDesc::Wedge  { .. } => "wedge",
         },
         Value::List(_)              => "list",
+        Value::Map(_)               => "map",
+        Value::Tuple(_)             => "tuple",
         Value::ResOk(_)
         | Value::ResErr(_)          => "res",
         Value::Input { .. }         => "Input",
@@ -250,17 +333,111 @@ pub fn value_type_key(v: &Value) -> &'static str {
 fn float() -> Type { Type::Float }
 fn named(s: &str) -> Type { Type::Named(s.into()) }
 
+/// `v.clamp(lo, hi)` needs `lo <= hi` on every component.
+fn check_clamp_bounds(lo: &Value, hi: &Value, line: usize) -> Result<(), RuntimeError> {
+    let (Some(lo), Some(hi)) = (vec_components(lo), vec_components(hi)) else { return Ok(()) };
+    match lo.iter().zip(&hi).position(|(l, h)| l > h || l.is_nan() || h.is_nan()) {
+        Some(i) => {
+            let axis = ["x", "y", "z", "w"][i];
+            Err(RuntimeError::new(line, format!(
+                "clamp expects `lo.{axis}` <= `hi.{axis}`, got {} and {}", lo[i], hi[i]
+            )))
+        }
+        None => Ok(()),
+    }
+}
+
 fn expect_float(v: &Value, name: &str, line: usize) -> Result<f64, RuntimeError> {
     match v {
         Value::Float(x) => Ok(*x),
+        Value::Int(n)   => Ok(*n as f64),
         _ => Err(RuntimeError::new(line, format!("`{name}` expected float"))),
     }
 }
 
+// ─── Swizzles ─────────────────────────────────────────────────────────────────
+
+/// Components of a vector value, or None for non-vectors.
+fn vec_components(v: &Value) -> Option<Vec<f64>> {
+    match v {
+        Value::Vec2(x, y)       => Some(vec![*x, *y]),
+        Value::Vec3(x, y, z)    => Some(vec![*x, *y, *z]),
+        Value::Vec4(x, y, z, w) => Some(vec![*x, *y, *z, *w]),
+        _ => None,
+    }
+}
+
+/// Component indices for a swizzle like `xy` or `zyx`: 2–4 letters from
+/// `xyzw`, each valid for a source vector of `dim` components.
+fn swizzle_indices(field: &str, dim: usize) -> Option<Vec<usize>> {
+    if !(2..=4).contains(&field.len()) { return None; }
+    field.chars()
+        .map(|c| "xyzw".find(c).filter(|&i| i < dim))
+        .collect()
+}
+
+fn swizzle_type(type_name: &str, field: &str) -> Option<Type> {
+    let dim = match type_name {
+        "vec2" => 2,
+        "vec3" => 3,
+        "vec4" => 4,
+        _ => return None,
+    };
+    swizzle_indices(field, dim).map(|idx| named(&format!("vec{}", idx.len())))
+}
+
+fn get_swizzle(v: &Value, field: &str) -> Option<Value> {
+    let comps = vec_components(v)?;
+    let out: Vec<f64> = swizzle_indices(field, comps.len())?
+        .into_iter()
+        .map(|i| comps[i])
+        .collect();
+    Some(match out[..] {
+        [x, y]       => Value::Vec2(x, y),
+        [x, y, z]    => Value::Vec3(x, y, z),
+        [x, y, z, w] => Value::Vec4(x, y, z, w),
+        _ => unreachable!(),
+    })
+}
+
 // ─── Primitives ───────────────────────────────────────────────────────────────
 
+/// Fluent forms of the core math functions: `(a * a + b * b).sqrt()`.
 fn float_desc() -> TypeDesc {
-    TypeDesc { name: "float", fields: vec![], methods: vec![] }
+    TypeDesc {
+        name: "float",
+        fields: vec![],
+        methods: vec![
+            MethodDesc {
+                name: "sqrt", params: vec![], ret: Some(float()),
+                call: |v, _args, _line| { let Value::Float(x) = v else { unreachable!() }; Ok(Value::Float(x.sqrt())) },
+            },
+            MethodDesc {
+                name: "abs", params: vec![], ret: Some(float()),
+                call: |v, _args, _line| { let Value::Float(x) = v else { unreachable!() }; Ok(Value::Float(x.abs())) },
+            },
+            MethodDesc {
+                name: "floor", params: vec![], ret: Some(float()),
+                call: |v, _args, _line| { let Value::Float(x) = v else { unreachable!() }; Ok(Value::Float(x.floor())) },
+            },
+            MethodDesc {
+                name: "ceil", params: vec![], ret: Some(float()),
+                call: |v, _args, _line| { let Value::Float(x) = v else { unreachable!() }; Ok(Value::Float(x.ceil())) },
+            },
+            MethodDesc {
+                name: "round", params: vec![], ret: Some(float()),
+                call: |v, _args, _line| { let Value::Float(x) = v else { unreachable!() }; Ok(Value::Float(x.round())) },
+            },
+            MethodDesc {
+                name: "sin", params: vec![], ret: Some(float()),
+                call: |v, _args, _line| { let Value::Float(x) = v else { unreachable!() }; Ok(Value::Float(x.sin())) },
+            },
+            MethodDesc {
+                name: "cos", params: vec![], ret: Some(float()),
+                call: |v, _args, _line| { let Value::Float(x) = v else { unreachable!() }; Ok(Value::Float(x.cos())) },
+            },
+        ],
+    }
 }
 
 fn bool_desc() -> TypeDesc {
@@ -278,7 +455,42 @@ fn string_desc() -> TypeDesc {
                 set:  None,
             },
         ],
-        methods: vec![],
+        methods: vec![
+            MethodDesc {
+                name: "upper", params: vec![], ret: Some(named("string")),
+                call: |v, _args, _line| {
+                    let Value::Str(s) = v else { unreachable!() };
+                    Ok(Value::Str(s.to_uppercase()))
+                },
+            },
+            MethodDesc {
+                name: "lower", params: vec![], ret: Some(named("string")),
+                call: |v, _args, _line| {
+                    let Value::Str(s) = v else { unreachable!() };
+                    Ok(Value::Str(s.to_lowercase()))
+                },
+            },
+            MethodDesc {
+                name: "contains", params: vec![named("string")], ret: Some(Type::Bool),
+                call: |v, args, line| {
+                    let Value::Str(s) = v else { unreachable!() };
+                    let Value::Str(needle) = &args[0] else {
+                        return Err(RuntimeError::new(line, "contains expects string"));
+                    };
+                    Ok(Value::Bool(s.contains(needle.as_str())))
+                },
+            },
+            MethodDesc {
+                name: "starts_with", params: vec![named("string")], ret: Some(Type::Bool),
+                call: |v, args, line| {
+                    let Value::Str(s) = v else { unreachable!() };
+                    let Value::Str(prefix) = &args[0] else {
+                        return Err(RuntimeError::new(line, "starts_with expects string"));
+                    };
+                    Ok(Value::Bool(s.starts_with(prefix.as_str())))
+                },
+            },
+        ],
     }
 }
 
@@ -291,7 +503,25 @@ fn input_desc() -> TypeDesc {
             FieldDesc {
                 name: "dt",
                 ty:   float(),
-                get:  |v| { let Value::Input { dt } = v else { unreachable!() }; Value::Float(*dt) },
+                get:  |v| { let Value::Input { dt, .. } = v else { unreachable!() }; Value::Float(*dt) },
+                set:  None,
+            },
+            FieldDesc {
+                name: "time",
+                ty:   float(),
+                get:  |v| { let Value::Input { time, .. } = v else { unreachable!() }; Value::Float(*time) },
+                set:  None,
+            },
+            FieldDesc {
+                name: "frame",
+                ty:   float(),
+                get:  |v| { let Value::Input { frame, .. } = v else { unreachable!() }; Value::Float(*frame) },
+                set:  None,
+            },
+            FieldDesc {
+                name: "mouse",
+                ty:   named("vec2"),
+                get:  |v| { let Value::Input { mouse, .. } = v else { unreachable!() }; Value::Vec2(mouse.0, mouse.1) },
                 set:  None,
             },
         ],
@@ -422,6 +652,17 @@ fn vec2_desc() -> TypeDesc {
                     Ok(Value::Vec2(ax.max(*bx), ay.max(*by)))
                 },
             },
+            MethodDesc {
+                name: "clamp", params: vec![named("vec2"), named("vec2")], ret: Some(named("vec2")),
+                call: |v, args, line| {
+                    let Value::Vec2(x, y) = v else { unreachable!() };
+                    let (Value::Vec2(lx, ly), Value::Vec2(hx, hy)) = (&args[0], &args[1]) else {
+                        return Err(RuntimeError::new(line, "clamp expects vec2 bounds"));
+                    };
+                    check_clamp_bounds(&args[0], &args[1], line)?;
+                    Ok(Value::Vec2(x.max(*lx).min(*hx), y.max(*ly).min(*hy)))
+                },
+            },
             MethodDesc {
                 name: "perp", params: vec![], ret: Some(named("vec2")),
                 call: |v, _args, _line| {
@@ -436,6 +677,71 @@ fn vec2_desc() -> TypeDesc {
                     Ok(Value::Float(y.atan2(*x)))
                 },
             },
+            MethodDesc {
+                name: "angle_between", params: vec![named("vec2")], ret: Some(float()),
+                call: |v, args, line| {
+                    let Value::Vec2(ax, ay) = v else { unreachable!() };
+                    let Value::Vec2(bx, by) = &args[0] else {
+                        return Err(RuntimeError::new(line, "angle_between expects vec2"));
+                    };
+                    if (*ax == 0.0 && *ay == 0.0) || (*bx == 0.0 && *by == 0.0) {
+                        return Err(RuntimeError::new(line, "angle_between on zero vector"));
+                    }
+                    let cross = ax * by - ay * bx;
+                    let dot = ax * bx + ay * by;
+                    Ok(Value::Float(cross.atan2(dot)))
+                },
+            },
+            MethodDesc {
+                name: "rotate", params: vec![float()], ret: Some(named("vec2")),
+                call: |v, args, line| {
+                    let Value::Vec2(x, y) = v else { unreachable!() };
+                    let (sin, cos) = expect_float(&args[0], "rotate angle", line)?.sin_cos();
+                    Ok(Value::Vec2(x * cos - y * sin, x * sin + y * cos))
+                },
+            },
+            MethodDesc {
+                name: "rotate_around", params: vec![named("vec2"), float()], ret: Some(named("vec2")),
+                call: |v, args, line| {
+                    let Value::Vec2(x, y) = v else { unreachable!() };
+                    let Value::Vec2(px, py) = &args[0] else {
+                        return Err(RuntimeError::new(line, "rotate_around expects vec2 pivot"));
+                    };
+                    let (sin, cos) = expect_float(&args[1], "rotate_around angle", line)?.sin_cos();
+                    let (dx, dy) = (x - px, y - py);
+                    Ok(Value::Vec2(px + dx * cos - dy * sin, py + dx * sin + dy * cos))
+                },
+            },
+            MethodDesc {
+                name: "reflect", params: vec![named("vec2")], ret: Some(named("vec2")),
+                call: |v, args, line| {
+                    let Value::Vec2(vx, vy) = v else { unreachable!() };
+                    let Value::Vec2(nx, ny) = &args[0] else {
+                        return Err(RuntimeError::new(line, "reflect expects vec2 normal"));
+                    };
+                    let dot2 = 2.0 * (vx * nx + vy * ny);
+                    Ok(Value::Vec2(vx - dot2 * nx, vy - dot2 * ny))
+                },
+            },
+            MethodDesc {
+                // Snaps onto the target once it is within reach, so repeated
+                // steps land on it exactly instead of hovering around it.
+                name: "toward", params: vec![named("vec2"), float()], ret: Some(named("vec2")),
+                call: |v, args, line| {
+                    let Value::Vec2(x, y) = v else { unreachable!() };
+                    let Value::Vec2(tx, ty) = &args[0] else {
+                        return Err(RuntimeError::new(line, "toward expects vec2 target"));
+                    };
+                    let max_dist = expect_float(&args[1], "toward max_dist", line)?.max(0.0);
+                    let (dx, dy) = (tx - x, ty - y);
+                    let dist = (dx * dx + dy * dy).sqrt();
+                    if dist <= max_dist {
+                        return Ok(Value::Vec2(*tx, *ty));
+                    }
+                    let k = max_dist / dist;
+                    Ok(Value::Vec2(x + dx * k, y + dy * k))
+                },
+            },
         ],
     }
 }
@@ -514,6 +820,17 @@ fn vec3_desc() -> TypeDesc {
                     Ok(Value::Vec3(ax+(bx-ax)*t, ay+(by-ay)*t, az+(bz-az)*t))
                 },
             },
+            MethodDesc {
+                name: "distance", params: vec![named("vec3")], ret: Some(float()),
+                call: |v, args, line| {
+                    let Value::Vec3(ax,ay,az) = v else { unreachable!() };
+                    let Value::Vec3(bx,by,bz) = &args[0] else {
+                        return Err(RuntimeError::new(line, "distance expects vec3"));
+                    };
+                    let (dx, dy, dz) = (ax-bx, ay-by, az-bz);
+                    Ok(Value::Float((dx*dx + dy*dy + dz*dz).sqrt()))
+                },
+            },
             MethodDesc {
                 name: "abs", params: vec![], ret: Some(named("vec3")),
                 call: |v, _args, _line| {
@@ -521,6 +838,20 @@ fn vec3_desc() -> TypeDesc {
                     Ok(Value::Vec3(x.abs(), y.abs(), z.abs()))
                 },
             },
+            MethodDesc {
+                name: "floor", params: vec![], ret: Some(named("vec3")),
+                call: |v, _args, _line| {
+                    let Value::Vec3(x,y,z) = v else { unreachable!() };
+                    Ok(Value::Vec3(x.floor(), y.floor(), z.floor()))
+                },
+            },
+            MethodDesc {
+                name: "ceil", params: vec![], ret: Some(named("vec3")),
+                call: |v, _args, _line| {
+                    let Value::Vec3(x,y,z) = v else { unreachable!() };
+                    Ok(Value::Vec3(x.ceil(), y.ceil(), z.ceil()))
+                },
+            },
             MethodDesc {
                 name: "min", params: vec![named("vec3")], ret: Some(named("vec3")),
                 call: |v, args, line| {
@@ -541,6 +872,17 @@ fn vec3_desc() -> TypeDesc {
                     Ok(Value::Vec3(ax.max(*bx), ay.max(*by), az.max(*bz)))
                 },
             },
+            MethodDesc {
+                name: "clamp", params: vec![named("vec3"), named("vec3")], ret: Some(named("vec3")),
+                call: |v, args, line| {
+                    let Value::Vec3(x,y,z) = v else { unreachable!() };
+                    let (Value::Vec3(lx,ly,lz), Value::Vec3(hx,hy,hz)) = (&args[0], &args[1]) else {
+                        return Err(RuntimeError::new(line, "clamp expects vec3 bounds"));
+                    };
+                    check_clamp_bounds(&args[0], &args[1], line)?;
+                    Ok(Value::Vec3(x.max(*lx).min(*hx), y.max(*ly).min(*hy), z.max(*lz).min(*hz)))
+                },
+            },
             MethodDesc {
                 name: "reflect", params: vec![named("vec3")], ret: Some(named("vec3")),
                 call: |v, args, line| {
@@ -621,6 +963,17 @@ fn vec4_desc() -> TypeDesc {
                     Ok(Value::Vec4(ax+(bx-ax)*t, ay+(by-ay)*t, az+(bz-az)*t, aw+(bw-aw)*t))
                 },
             },
+            MethodDesc {
+                name: "distance", params: vec![named("vec4")], ret: Some(float()),
+                call: |v, args, line| {
+                    let Value::Vec4(ax,ay,az,aw) = v else { unreachable!() };
+                    let Value::Vec4(bx,by,bz,bw) = &args[0] else {
+                        return Err(RuntimeError::new(line, "distance expects vec4"));
+                    };
+                    let (dx, dy, dz, dw) = (ax-bx, ay-by, az-bz, aw-bw);
+                    Ok(Value::Float((dx*dx + dy*dy + dz*dz + dw*dw).sqrt()))
+                },
+            },
             MethodDesc {
                 name: "abs", params: vec![], ret: Some(named("vec4")),
                 call: |v, _args, _line| {
@@ -628,6 +981,20 @@ fn vec4_desc() -> TypeDesc {
                     Ok(Value::Vec4(x.abs(), y.abs(), z.abs(), w.abs()))
                 },
             },
+            MethodDesc {
+                name: "floor", params: vec![], ret: Some(named("vec4")),
+                call: |v, _args, _line| {
+                    let Value::Vec4(x,y,z,w) = v else { unreachable!() };
+                    Ok(Value::Vec4(x.floor(), y.floor(), z.floor(), w.floor()))
+                },
+            },
+            MethodDesc {
+                name: "ceil", params: vec![], ret: Some(named("vec4")),
+                call: |v, _args, _line| {
+                    let Value::Vec4(x,y,z,w) = v else { unreachable!() };
+                    Ok(Value::Vec4(x.ceil(), y.ceil(), z.ceil(), w.ceil()))
+                },
+            },
             MethodDesc {
                 name: "min", params: vec![named("vec4")], ret: Some(named("vec4")),
                 call: |v, args, line| {
@@ -648,12 +1015,53 @@ fn vec4_desc() -> TypeDesc {
                     Ok(Value::Vec4(ax.max(*bx), ay.max(*by), az.max(*bz), aw.max(*bw)))
                 },
             },
+            MethodDesc {
+                name: "clamp", params: vec![named("vec4"), named("vec4")], ret: Some(named("vec4")),
+                call: |v, args, line| {
+                    let Value::Vec4(x,y,z,w) = v else { unreachable!() };
+                    let (Value::Vec4(lx,ly,lz,lw), Value::Vec4(hx,hy,hz,hw)) = (&args[0], &args[1]) else {
+                        return Err(RuntimeError::new(line, "clamp expects vec4 bounds"));
+                    };
+                    check_clamp_bounds(&args[0], &args[1], line)?;
+                    Ok(Value::Vec4(
+                        x.max(*lx).min(*hx), y.max(*ly).min(*hy),
+                        z.max(*lz).min(*hz), w.max(*lw).min(*hw),
+                    ))
+                },
+            },
         ],
     }
 }
 
 // ─── color ────────────────────────────────────────────────────────────────────
 
+/// Per-channel blend of `base` (the receiver) with `top`; alpha is kept from `base`.
+fn blend(base: &Value, top: &Value, name: &str, line: usize, f: fn(f64, f64) -> f64) -> Result<Value, RuntimeError> {
+    let Value::Color { r, g, b, a } = base else { unreachable!() };
+    let Value::Color { r: tr, g: tg, b: tb, .. } = top else {
+        return Err(RuntimeError::new(line, format!("{name} expects color")));
+    };
+    Ok(Value::Color { r: f(*r, *tr), g: f(*g, *tg), b: f(*b, *tb), a: *a })
+}
+
+/// RGB in 0–1 → (hue in degrees [0, 360), saturation, value).
+fn rgb_to_hsv(r: f64, g: f64, b: f64) -> (f64, f64, f64) {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+    let h = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    let s = if max == 0.0 { 0.0 } else { delta / max };
+    (h, s, max)
+}
+
 fn color_desc() -> TypeDesc {
     TypeDesc {
         name: "color",
@@ -704,6 +1112,52 @@ fn color_desc() -> TypeDesc {
                     Ok(Value::Color { r: *r, g: *g, b: *b, a })
                 },
             },
+            MethodDesc {
+                name: "darken", params: vec![float()], ret: Some(named("color")),
+                call: |v, args, line| {
+                    let Value::Color { r, g, b, a } = v else { unreachable!() };
+                    let k = 1.0 - expect_float(&args[0], "darken amount", line)?.clamp(0.0, 1.0);
+                    Ok(Value::Color { r: r * k, g: g * k, b: b * k, a: *a })
+                },
+            },
+            MethodDesc {
+                name: "lighten", params: vec![float()], ret: Some(named("color")),
+                call: |v, args, line| {
+                    let Value::Color { r, g, b, a } = v else { unreachable!() };
+                    let t = expect_float(&args[0], "lighten amount", line)?.clamp(0.0, 1.0);
+                    Ok(Value::Color { r: r + (1.0 - r) * t, g: g + (1.0 - g) * t, b: b + (1.0 - b) * t, a: *a })
+                },
+            },
+            MethodDesc {
+                name: "grayscale", params: vec![], ret: Some(named("color")),
+                call: |v, _args, _line| {
+                    let Value::Color { r, g, b, a } = v else { unreachable!() };
+                    let y = 0.299 * r + 0.587 * g + 0.114 * b;
+                    Ok(Value::Color { r: y, g: y, b: y, a: *a })
+                },
+            },
+            MethodDesc {
+                name: "multiply", params: vec![named("color")], ret: Some(named("color")),
+                call: |v, args, line| blend(v, &args[0], "multiply", line, |a, b| a * b),
+            },
+            MethodDesc {
+                name: "screen", params: vec![named("color")], ret: Some(named("color")),
+                call: |v, args, line| blend(v, &args[0], "screen", line, |a, b| 1.0 - (1.0 - a) * (1.0 - b)),
+            },
+            MethodDesc {
+                name: "overlay", params: vec![named("color")], ret: Some(named("color")),
+                call: |v, args, line| blend(v, &args[0], "overlay", line, |a, b| {
+                    if a < 0.5 { 2.0 * a * b } else { 1.0 - 2.0 * (1.0 - a) * (1.0 - b) }
+                }),
+            },
+            MethodDesc {
+                name: "to_hsv", params: vec![], ret: Some(named("vec3")),
+                call: |v, _args, _line| {
+                    let Value::Color { r, g, b, .. } = v else { unreachable!() };
+                    let (h, s, v) = rgb_to_hsv(*r, *g, *b);
+                    Ok(Value::Vec3(h, s, v))
+                },
+            },
             MethodDesc {
                 name: "to_vec4", params: vec![], ret: Some(named("vec4")),
                 call: |v, _args, _line| {
@@ -767,10 +1221,278 @@ fn transform_desc() -> TypeDesc {
                     Ok(Value::Transform(t))
                 },
             },
+            MethodDesc {
+                name: "rotate_around", params: vec![float(), float(), float()], ret: Some(named("transform")),
+                call: |v, args, line| {
+                    let Value::Transform(td) = v else { unreachable!() };
+                    let px  = expect_float(&args[0], "rotate_around px", line)?;
+                    let py  = expect_float(&args[1], "rotate_around py", line)?;
+                    let deg = expect_float(&args[2], "rotate_around degrees", line)?;
+                    let mut t = td.clone();
+                    t.pivot = Some((px, py));
+                    t.angle += deg.to_radians();
+                    Ok(Value::Transform(t))
+                },
+            },
+            MethodDesc {
+                // `a.compose(b)` is equivalent to `@(a, b)`: apply `a`, then `b`.
+                // Runtime error when the pivots make that shape-dependent.
+                name: "compose", params: vec![named("transform")], ret: Some(named("transform")),
+                call: |v, args, line| {
+                    let Value::Transform(a) = v else { unreachable!() };
+                    let Value::Transform(b) = &args[0] else {
+                        return Err(RuntimeError::new(line, "compose expects transform"));
+                    };
+                    a.then(b).map(Value::Transform).ok_or_else(|| RuntimeError::new(line,
+                        "compose cannot merge these pivots into one transform — apply both with `@(a, b)`"
+                    ))
+                },
+            },
+            MethodDesc {
+                name: "reset", params: vec![], ret: Some(named("transform")),
+                call: |_v, _args, _line| Ok(Value::Transform(TransformData::default())),
+            },
+            MethodDesc {
+                // Where a point lands under this transform. There is no shape to take a
+                // centroid from, so scale and rotation act about the pivot or the origin.
+                name: "apply", params: vec![named("vec2")], ret: Some(named("vec2")),
+                call: |v, args, line| {
+                    let Value::Transform(td) = v else { unreachable!() };
+                    let Value::Vec2(x, y) = args[0] else {
+                        return Err(RuntimeError::new(line, "apply expects vec2"));
+                    };
+                    let (ox, oy) = td.apply_point((x, y), td.pivot.unwrap_or((0.0, 0.0)));
+                    Ok(Value::Vec2(ox, oy))
+                },
+            },
+            MethodDesc {
+                name: "shear", params: vec![float(), float()], ret: Some(named("transform")),
+                call: |v, args, line| {
+                    let Value::Transform(td) = v else { unreachable!() };
+                    let shx = expect_float(&args[0], "shear sx", line)?;
+                    let shy = expect_float(&args[1], "shear sy", line)?;
+                    let mut t = td.clone();
+                    t.shx += shx;
+                    t.shy += shy;
+                    Ok(Value::Transform(t))
+                },
+            },
         ],
     }
 }
 
+// ─── shape geometry ───────────────────────────────────────────────────────────
+
+/// Outline vertices of a shape in user space, before transforms. Rect extents
+/// follow the tessellator: the shape origin is read in screen terms, so
+/// right-edge canvas origins flip x and y-up canvases flip y.
+fn shape_vertices(shape: &ShapeData) -> Vec<(f64, f64)> {
+    let m = &shape.coord_meta;
+    match &shape.desc {
+        ShapeDesc::Circle { center, radius } => (0..CIRCLE_SEGMENTS).map(|i| {
+            let t = i as f64 / CIRCLE_SEGMENTS as f64 * std::f64::consts::TAU;
+            (center.0 + radius * t.cos(), center.1 + radius * t.sin())
+        }).collect(),
+        ShapeDesc::Rect { center, size, origin } => {
+            let (w, h) = *size;
+            let (x0, x1) = match origin {
+                Origin::TopLeft | Origin::BottomLeft | Origin::Left     => (0.0, w),
+                Origin::TopRight | Origin::BottomRight | Origin::Right  => (-w, 0.0),
+                Origin::Center | Origin::Top | Origin::Bottom           => (-w / 2.0, w / 2.0),
+            };
+            let (y0, y1) = match origin {
+                Origin::TopLeft | Origin::TopRight | Origin::Top          => (0.0, h),
+                Origin::BottomLeft | Origin::BottomRight | Origin::Bottom => (-h, 0.0),
+                Origin::Center | Origin::Left | Origin::Right             => (-h / 2.0, h / 2.0),
+            };
+            let x_sign = if m.px_width > 0.0 && matches!(m.origin, Origin::TopRight | Origin::BottomRight | Origin::Right) { -1.0 } else { 1.0 };
+            let y_sign = if m.px_height > 0.0 && !m.is_y_down() { -1.0 } else { 1.0 };
+            let (cx, cy) = *center;
+            [(x0, y0), (x1, y0), (x1, y1), (x0, y1)].iter()
+                .map(|(dx, dy)| (cx + dx * x_sign, cy + dy * y_sign))
+                .collect()
+        }
+        ShapeDesc::Line { from, to } => vec![*from, *to],
+        ShapeDesc::Polygon(pts) => pts.clone(),
+        ShapeDesc::Wedge { center, radius, start_angle, end_angle } => {
+            wedge_points(*center, *radius, *start_angle, *end_angle)
+        }
+    }
+}
+
+/// A transform step paired with the user-space pivot it acts about.
+type PivotedTransform = (TransformData, (f64, f64));
+
+/// Runs a shape's transforms in user space. Returns the final vertices and
+/// each transform paired with the pivot it was applied about (its own pivot,
+/// or the centroid of the vertices at that step).
+pub(crate) fn transform_vertices(shape: &ShapeData) -> (Vec<(f64, f64)>, Vec<PivotedTransform>) {
+    let mut verts = shape_vertices(shape);
+    // Transform angles are screen-visual (CCW); each flipped canvas axis reverses
+    // the rotation direction in user space.
+    let m = &shape.coord_meta;
+    let x_flip = matches!(m.origin, Origin::TopRight | Origin::BottomRight | Origin::Right);
+    let rot_sign = if x_flip != m.is_y_down() { -1.0 } else { 1.0 };
+    let mut steps = Vec::with_capacity(shape.transforms.len());
+    for td in &shape.transforms {
+        let pivot = td.pivot.unwrap_or_else(|| centroid(&verts));
+        let user_td = TransformData { angle: td.angle * rot_sign, ..td.clone() };
+        verts = verts.into_iter().map(|p| user_td.apply_point(p, pivot)).collect();
+        steps.push((user_td, pivot));
+    }
+    (verts, steps)
+}
+
+/// `shape.bounds()` — user-space AABB `(min_x, min_y, max_x, max_y)` after all
+/// attached transforms.
+fn shape_bounds(v: &Value, _args: &[Value], line: usize) -> Result<Value, RuntimeError> {
+    let Value::Shape(shape) = v else { unreachable!() };
+    let (verts, _) = transform_vertices(shape);
+    if verts.is_empty() {
+        return Err(RuntimeError::new(line, "bounds of an empty shape"));
+    }
+    let (mut min_x, mut min_y) = (f64::INFINITY, f64::INFINITY);
+    let (mut max_x, mut max_y) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+    for (x, y) in verts {
+        min_x = min_x.min(x);
+        min_y = min_y.min(y);
+        max_x = max_x.max(x);
+        max_y = max_y.max(y);
+    }
+    Ok(Value::Vec4(min_x, min_y, max_x, max_y))
+}
+
+/// `shape.contains(p)` — hit test. The query point is mapped back through the
+/// transforms, then tested against the untransformed geometry. Edges count as
+/// inside; lines never contain a point.
+fn shape_contains(v: &Value, args: &[Value], line: usize) -> Result<Value, RuntimeError> {
+    let Value::Shape(shape) = v else { unreachable!() };
+    let Value::Vec2(qx, qy) = args[0] else {
+        return Err(RuntimeError::new(line, "contains expects vec2"));
+    };
+    let (_, steps) = transform_vertices(shape);
+    let mut p = (qx, qy);
+    for (td, pivot) in steps.iter().rev() {
+        match td.invert_point(p, *pivot) {
+            Some(q) => p = q,
+            // a collapsed shape covers no area
+            None => return Ok(Value::Bool(false)),
+        }
+    }
+    let (x, y) = p;
+    let inside = match &shape.desc {
+        ShapeDesc::Circle { center, radius } => {
+            (x - center.0).powi(2) + (y - center.1).powi(2) <= radius * radius
+        }
+        ShapeDesc::Rect { .. } => {
+            let corners = shape_vertices(shape);
+            let (x0, x1) = (corners[0].0.min(corners[2].0), corners[0].0.max(corners[2].0));
+            let (y0, y1) = (corners[0].1.min(corners[2].1), corners[0].1.max(corners[2].1));
+            (x0..=x1).contains(&x) && (y0..=y1).contains(&y)
+        }
+        ShapeDesc::Line { .. } => false,
+        ShapeDesc::Wedge { center, radius, start_angle, end_angle } => {
+            let (dx, dy) = (x - center.0, y - center.1);
+            let sweep = end_angle - start_angle;
+            let tau = std::f64::consts::TAU;
+            // angle of the point measured from `start` in the sweep's direction
+            let turn = (dy.atan2(dx) - start_angle) * sweep.signum();
+            dx * dx + dy * dy <= radius * radius
+                && (sweep.abs() >= tau || turn.rem_euclid(tau) <= sweep.abs())
+        }
+        ShapeDesc::Polygon(pts) => {
+            // even-odd rule: count edge crossings of a ray towards +x
+            let mut inside = false;
+            for i in 0..pts.len() {
+                let (ax, ay) = pts[i];
+                let (bx, by) = pts[(i + 1) % pts.len()];
+                if (ay > y) != (by > y) && x < ax + (y - ay) / (by - ay) * (bx - ax) {
+                    inside = !inside;
+                }
+            }
+            inside
+        }
+    };
+    Ok(Value::Bool(inside))
+}
+
+/// Area and perimeter of the untransformed geometry.
+fn base_measures(shape: &ShapeData) -> (f64, f64) {
+    let tau = std::f64::consts::TAU;
+    match &shape.desc {
+        ShapeDesc::Circle { radius, .. } => (std::f64::consts::PI * radius * radius, tau * radius.abs()),
+        ShapeDesc::Rect { size: (w, h), .. } => ((w * h).abs(), 2.0 * (w.abs() + h.abs())),
+        ShapeDesc::Line { from, to } => (0.0, (to.0 - from.0).hypot(to.1 - from.1)),
+        ShapeDesc::Polygon(pts) => (geometry::area(pts), geometry::perimeter(pts)),
+        ShapeDesc::Wedge { radius, start_angle, end_angle, .. } => {
+            let sweep = (end_angle - start_angle).abs().min(tau);
+            (radius * radius * sweep / 2.0, radius.abs() * (2.0 + sweep))
+        }
+    }
+}
+
+/// Length of a closed vertex ring.
+fn outline_length(pts: &[(f64, f64)]) -> f64 {
+    (0..pts.len()).map(|i| {
+        let (a, b) = (pts[i], pts[(i + 1) % pts.len()]);
+        (b.0 - a.0).hypot(b.1 - a.1)
+    }).sum()
+}
+
+/// `shape.area()` — the exact area scaled by each transform's determinant.
+fn shape_area(v: &Value, _args: &[Value], _line: usize) -> Result<Value, RuntimeError> {
+    let Value::Shape(shape) = v else { unreachable!() };
+    let (area, _) = base_measures(shape);
+    let scale: f64 = shape.transforms.iter()
+        .map(|td| (td.sx * td.sy * (1.0 - td.shx * td.shy)).abs())
+        .product();
+    Ok(Value::Float(area * scale))
+}
+
+/// `shape.perimeter()` — the exact perimeter while transforms only rotate,
+/// move and scale uniformly. Otherwise it is measured along the transformed
+/// outline, so circles and wedges follow the tessellated arc.
+fn shape_perimeter(v: &Value, _args: &[Value], _line: usize) -> Result<Value, RuntimeError> {
+    let Value::Shape(shape) = v else { unreachable!() };
+    let similar = shape.transforms.iter()
+        .all(|td| td.sx.abs() == td.sy.abs() && td.shx == 0.0 && td.shy == 0.0);
+    if similar {
+        let (_, perimeter) = base_measures(shape);
+        let scale: f64 = shape.transforms.iter().map(|td| td.sx.abs()).product();
+        return Ok(Value::Float(perimeter * scale));
+    }
+    let (verts, _) = transform_vertices(shape);
+    let length = match shape.desc {
+        ShapeDesc::Line { .. } => (verts[1].0 - verts[0].0).hypot(verts[1].1 - verts[0].1),
+        ShapeDesc::Polygon(_) => geometry::perimeter(&verts),
+        _ => outline_length(&verts),
+    };
+    Ok(Value::Float(length))
+}
+
+/// `shape.union(other)` / `shape.difference(other)` — boolean operation on the
+/// transformed outlines. The result is a transform-free polygon that keeps the
+/// receiver's style; circles are clipped as their tessellated outline.
+fn shape_boolean(
+    v: &Value, args: &[Value], line: usize,
+    name: &str, op: fn(&[geometry::Pt], &[geometry::Pt]) -> Vec<geometry::Pt>,
+) -> Result<Value, RuntimeError> {
+    let Value::Shape(shape) = v else { unreachable!() };
+    let Value::Shape(other) = &args[0] else {
+        return Err(RuntimeError::new(line, format!("{name} expects a shape")));
+    };
+    if matches!(shape.desc, ShapeDesc::Line { .. }) || matches!(other.desc, ShapeDesc::Line { .. }) {
+        return Err(RuntimeError::new(line, format!("{name} needs shapes with area, found a line")));
+    }
+    let (a, _) = transform_vertices(shape);
+    let (b, _) = transform_vertices(other);
+    Ok(Value::Shape(ShapeData {
+        desc: ShapeDesc::Polygon(op(&a, &b)),
+        transforms: Vec::new(),
+        ..shape.clone()
+    }))
+}
+
 // ─── shape (erased) ───────────────────────────────────────────────────────────
 
 fn shape_desc() -> TypeDesc {
@@ -788,6 +1510,30 @@ fn shape_desc() -> TypeDesc {
                     Ok(Value::Vec2(ax + dx, ay + dy))
                 },
             },
+            MethodDesc {
+                name: "bounds", params: vec![], ret: Some(named("vec4")),
+                call: shape_bounds,
+            },
+            MethodDesc {
+                name: "contains", params: vec![named("vec2")], ret: Some(Type::Bool),
+                call: shape_contains,
+            },
+            MethodDesc {
+                name: "area", params: vec![], ret: Some(float()),
+                call: shape_area,
+            },
+            MethodDesc {
+                name: "perimeter", params: vec![], ret: Some(float()),
+                call: shape_perimeter,
+            },
+            MethodDesc {
+                name: "union", params: vec![named("shape")], ret: Some(named("polygon")),
+                call: |v, args, line| shape_boolean(v, args, line, "union", geometry::union),
+            },
+            MethodDesc {
+                name: "difference", params: vec![named("shape")], ret: Some(named("polygon")),
+                call: |v, args, line| shape_boolean(v, args, line, "difference", geometry::difference),
+            },
         ],
     }
 }
@@ -828,6 +1574,30 @@ fn circle_desc() -> TypeDesc {
                     Ok(Value::Vec2(ax + dx, ay + dy))
                 },
             },
+            MethodDesc {
+                name: "bounds", params: vec![], ret: Some(named("vec4")),
+                call: shape_bounds,
+            },
+            MethodDesc {
+                name: "contains", params: vec![named("vec2")], ret: Some(Type::Bool),
+                call: shape_contains,
+            },
+            MethodDesc {
+                name: "area", params: vec![], ret: Some(float()),
+                call: shape_area,
+            },
+            MethodDesc {
+                name: "perimeter", params: vec![], ret: Some(float()),
+                call: shape_perimeter,
+            },
+            MethodDesc {
+                name: "union", params: vec![named("shape")], ret: Some(named("polygon")),
+                call: |v, args, line| shape_boolean(v, args, line, "union", geometry::union),
+            },
+            MethodDesc {
+                name: "difference", params: vec![named("shape")], ret: Some(named("polygon")),
+                call: |v, args, line| shape_boolean(v, args, line, "difference", geometry::difference),
+            },
         ],
     }
 }
@@ -868,6 +1638,30 @@ fn rect_desc() -> TypeDesc {
                     Ok(Value::Vec2(ax + dx, ay + dy))
                 },
             },
+            MethodDesc {
+                name: "bounds", params: vec![], ret: Some(named("vec4")),
+                call: shape_bounds,
+            },
+            MethodDesc {
+                name: "contains", params: vec![named("vec2")], ret: Some(Type::Bool),
+                call: shape_contains,
+            },
+            MethodDesc {
+                name: "area", params: vec![], ret: Some(float()),
+                call: shape_area,
+            },
+            MethodDesc {
+                name: "perimeter", params: vec![], ret: Some(float()),
+                call: shape_perimeter,
+            },
+            MethodDesc {
+                name: "union", params: vec![named("shape")], ret: Some(named("polygon")),
+                call: |v, args, line| shape_boolean(v, args, line, "union", geometry::union),
+            },
+            MethodDesc {
+                name: "difference", params: vec![named("shape")], ret: Some(named("polygon")),
+                call: |v, args, line| shape_boolean(v, args, line, "difference", geometry::difference),
+            },
         ],
     }
 }
@@ -908,6 +1702,22 @@ fn line_desc() -> TypeDesc {
                     Ok(Value::Vec2(ax + dx, ay + dy))
                 },
             },
+            MethodDesc {
+                name: "bounds", params: vec![], ret: Some(named("vec4")),
+                call: shape_bounds,
+            },
+            MethodDesc {
+                name: "contains", params: vec![named("vec2")], ret: Some(Type::Bool),
+                call: shape_contains,
+            },
+            MethodDesc {
+                name: "area", params: vec![], ret: Some(float()),
+                call: shape_area,
+            },
+            MethodDesc {
+                name: "perimeter", params: vec![], ret: Some(float()),
+                call: shape_perimeter,
+            },
         ],
     }
 }
@@ -929,6 +1739,158 @@ fn polygon_desc() -> TypeDesc {
                     Ok(Value::Vec2(ax + dx, ay + dy))
                 },
             },
+            MethodDesc {
+                name: "bounds", params: vec![], ret: Some(named("vec4")),
+                call: shape_bounds,
+            },
+            MethodDesc {
+                name: "contains", params: vec![named("vec2")], ret: Some(Type::Bool),
+                call: shape_contains,
+            },
+            MethodDesc {
+                name: "area", params: vec![], ret: Some(float()),
+                call: shape_area,
+            },
+            MethodDesc {
+                name: "perimeter", params: vec![], ret: Some(float()),
+                call: shape_perimeter,
+            },
+            MethodDesc {
+                name: "union", params: vec![named("shape")], ret: Some(named("polygon")),
+                call: |v, args, line| shape_boolean(v, args, line, "union", geometry::union),
+            },
+            MethodDesc {
+                name: "difference", params: vec![named("shape")], ret: Some(named("polygon")),
+                call: |v, args, line| shape_boolean(v, args, line, "difference", geometry::difference),
+            },
+        ],
+    }
+}
+
+// ─── wedge ────────────────────────────────────────────────────────────────────
+
+fn wedge_desc() -> TypeDesc {
+    TypeDesc {
+        name: "wedge",
+        fields: vec![
+            FieldDesc {
+                name: "center", ty: named("vec2"),
+                get: |v| {
+                    let Value::Shape(s) = v else { unreachable!() };
+                    let ShapeDesc::Wedge { center, .. } = s.desc else { unreachable!() };
+                    Value::Vec2(center.0, center.1)
+                },
+                set: None,
+            },
+            FieldDesc {
+                name: "radius", ty: float(),
+                get: |v| {
+                    let Value::Shape(s) = v else { unreachable!() };
+                    let ShapeDesc::Wedge { radius, .. } = s.desc else { unreachable!() };
+                    Value::Float(radius)
+                },
+                set: None,
+            },
+            FieldDesc {
+                name: "start", ty: float(),
+                get: |v| {
+                    let Value::Shape(s) = v else { unreachable!() };
+                    let ShapeDesc::Wedge { start_angle, .. } = s.desc else { unreachable!() };
+                    Value::Float(start_angle)
+                },
+                set: None,
+            },
+            FieldDesc {
+                name: "end", ty: float(),
+                get: |v| {
+                    let Value::Shape(s) = v else { unreachable!() };
+                    let ShapeDesc::Wedge { end_angle, .. } = s.desc else { unreachable!() };
+                    Value::Float(end_angle)
+                },
+                set: None,
+            },
+        ],
+        methods: vec![
+            MethodDesc {
+                name: "in", params: vec![float(), float()], ret: Some(named("vec2")),
+                call: |v, args, line| {
+                    let Value::Shape(shape) = v else { unreachable!() };
+                    let dx = expect_float(&args[0], "in dx", line)?;
+                    let dy = expect_float(&args[1], "in dy", line)?;
+                    let (ax, ay) = shape.desc.anchor();
+                    Ok(Value::Vec2(ax + dx, ay + dy))
+                },
+            },
+            MethodDesc {
+                name: "bounds", params: vec![], ret: Some(named("vec4")),
+                call: shape_bounds,
+            },
+            MethodDesc {
+                name: "contains", params: vec![named("vec2")], ret: Some(Type::Bool),
+                call: shape_contains,
+            },
+            MethodDesc {
+                name: "area", params: vec![], ret: Some(float()),
+                call: shape_area,
+            },
+            MethodDesc {
+                name: "perimeter", params: vec![], ret: Some(float()),
+                call: shape_perimeter,
+            },
+            MethodDesc {
+                name: "union", params: vec![named("shape")], ret: Some(named("polygon")),
+                call: |v, args, line| shape_boolean(v, args, line, "union", geometry::union),
+            },
+            MethodDesc {
+                name: "difference", params: vec![named("shape")], ret: Some(named("polygon")),
+                call: |v, args, line| shape_boolean(v, args, line, "difference", geometry::difference),
+            },
+        ],
+    }
+}
+
+// ─── map ──────────────────────────────────────────────────────────────────────
+
+fn map_desc() -> TypeDesc {
+    TypeDesc {
+        name: "map",
+        fields: vec![
+            FieldDesc {
+                name: "len", ty: float(),
+                get: |v| { let Value::Map(entries) = v else { unreachable!() }; Value::Float(entries.borrow().len() as f64) },
+                set: None,
+            },
+        ],
+        methods: vec![
+            MethodDesc {
+                name: "len", params: vec![], ret: Some(float()),
+                call: |v, _args, _line| {
+                    let Value::Map(entries) = v else { unreachable!() };
+                    Ok(Value::Float(entries.borrow().len() as f64))
+                },
+            },
+            MethodDesc {
+                // Sorted, so iteration order doesn't depend on hashing.
+                name: "keys", params: vec![], ret: Some(Type::List(Box::new(named("string")))),
+                call: |v, _args, _line| {
+                    let Value::Map(entries) = v else { unreachable!() };
+                    let mut keys: Vec<String> = entries.borrow().keys().cloned().collect();
+                    keys.sort();
+                    Ok(Value::List(Rc::new(RefCell::new(keys.into_iter().map(Value::Str).collect()))))
+                },
+            },
+            MethodDesc {
+                name: "has", params: vec![named("string")], ret: Some(Type::Bool),
+                call: |v, args, line| {
+                    let Value::Map(entries) = v else { unreachable!() };
+                    match &args[0] {
+                        Value::Str(key) => Ok(Value::Bool(entries.borrow().contains_key(key))),
+                        other => Err(RuntimeError::new(line, format!(
+                            "`has` expects a string key, found `{}`", value_type_key(other)
+                        ))),
+                    }
+                },
+            },
         ],
     }
 }
@@ -974,6 +1936,96 @@ fn list_desc() -> TypeDesc {
                         .ok_or_else(|| RuntimeError::new(line, "pop on empty list"))
                 },
             },
+            MethodDesc {
+                // Inserts before `index`, shifting later elements; `index == len`
+                // appends. Mutates in-place.
+                name: "insert", params: vec![float(), float()], // placeholder
+                ret: None,
+                call: |v, args, line| {
+                    let Value::List(items) = v else { unreachable!() };
+                    let len = items.borrow().len();
+                    let i = expect_float(&args[0], "insert index", line)?.trunc();
+                    if !(0.0..=len as f64).contains(&i) {
+                        return Err(RuntimeError::new(line, format!(
+                            "insert index {i} out of bounds for list of length {len}"
+                        )));
+                    }
+                    items.borrow_mut().insert(i as usize, args[1].clone());
+                    Ok(Value::List(items.clone()))
+                },
+            },
+            MethodDesc {
+                // Removes and returns the element at `index`. Mutates in-place.
+                name: "remove", params: vec![float()], ret: Some(Type::Float), // placeholder
+                call: |v, args, line| {
+                    let Value::List(items) = v else { unreachable!() };
+                    let len = items.borrow().len();
+                    let i = expect_float(&args[0], "remove index", line)?.trunc();
+                    if !(0.0..len as f64).contains(&i) {
+                        return Err(RuntimeError::new(line, format!(
+                            "remove index {i} out of bounds for list of length {len}"
+                        )));
+                    }
+                    Ok(items.borrow_mut().remove(i as usize))
+                },
+            },
+            MethodDesc {
+                // Returns a new list; the receiver is unchanged.
+                name: "reverse", params: vec![], ret: None, // placeholder
+                call: |v, _args, _line| {
+                    let Value::List(items) = v else { unreachable!() };
+                    let out: Vec<Value> = items.borrow().iter().rev().cloned().collect();
+                    Ok(Value::List(Rc::new(RefCell::new(out))))
+                },
+            },
+            MethodDesc {
+                // Copy of `[start, end)`, with both bounds clamped to the list.
+                name: "slice", params: vec![float(), float()], ret: None, // placeholder
+                call: |v, args, line| {
+                    let Value::List(items) = v else { unreachable!() };
+                    let items = items.borrow();
+                    let clamp = |x: f64| (x.max(0.0) as usize).min(items.len());
+                    let start = clamp(expect_float(&args[0], "slice start", line)?);
+                    let end   = clamp(expect_float(&args[1], "slice end", line)?).max(start);
+                    Ok(Value::List(Rc::new(RefCell::new(items[start..end].to_vec()))))
+                },
+            },
+            MethodDesc {
+                // Ascending copy of a list of numbers; the receiver is unchanged.
+                // A list of ints stays ints; any float widens the result to floats.
+                name: "sort", params: vec![], ret: None, // placeholder
+                call: |v, _args, line| {
+                    let Value::List(items) = v else { unreachable!() };
+                    let items = items.borrow();
+                    if items.iter().all(|item| matches!(item, Value::Int(_))) {
+                        let mut ns: Vec<i64> = items.iter()
+                            .map(|item| match item { Value::Int(n) => *n, _ => unreachable!() })
+                            .collect();
+                        ns.sort();
+                        return Ok(Value::List(Rc::new(RefCell::new(ns.into_iter().map(Value::Int).collect()))));
+                    }
+                    let mut xs = items.iter()
+                        .map(|item| match item {
+                            Value::Float(x) => Ok(*x),
+                            Value::Int(n)   => Ok(*n as f64),
+                            other => Err(RuntimeError::new(line, format!(
+                                "sort requires a list of numbers, found `{}`", value_type_key(other)
+                            ))),
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    xs.sort_by(|a, b| a.total_cmp(b));
+                    Ok(Value::List(Rc::new(RefCell::new(xs.into_iter().map(Value::Float).collect()))))
+                },
+            },
+            MethodDesc {
+                name: "contains", params: vec![Type::Float], // placeholder — resolved generically
+                ret: Some(Type::Bool),
+                call: |v, args, _line| {
+                    let Value::List(items) = v else { unreachable!() };
+                    let found = items.borrow().iter().any(|item| values_equal(item, &args[0]));
+                    Ok(Value::Bool(found))
+                },
+            },
         ],
     }
 }
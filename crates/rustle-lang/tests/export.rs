@@ -0,0 +1,109 @@
+//! Export tests.
+//!
+//! Compiles and ticks a program, then serializes its draw output.
+
+use rustle_lang::{compile, Runtime, Input, DrawCommand};
+use rustle_lang::export::json::to_json;
+use rustle_lang::export::svg::export_svg;
+
+// ─── Helpers ─────────────────────────────────────────────────────────────────
+
+fn frame(src: &str) -> Vec<DrawCommand> {
+    let prog = compile(src).unwrap_or_else(|errs| {
+        panic!("compile failed: {errs:#?}");
+    });
+    let mut rt = Runtime::new(prog).unwrap_or_else(|e| {
+        panic!("Runtime::new failed: {e:?}");
+    });
+    rt.tick(&Input { dt: 0.016, ..Input::default() })
+        .unwrap_or_else(|e| panic!("tick failed: {e:?}"))
+}
+
+// ─── JSON ────────────────────────────────────────────────────────────────────
+
+#[test]
+fn json_circle_has_type_and_radius() {
+    let json = to_json(&frame(r#"
+        import shapes { circle }
+        out << circle(vec2(0.0, 0.0), 0.25)
+    "#));
+    assert!(json.starts_with('[') && json.ends_with(']'));
+    assert!(json.contains(r#""type":"circle""#), "{json}");
+    assert!(json.contains(r#""radius":0.25"#), "{json}");
+}
+
+#[test]
+fn json_includes_transforms_and_coord_meta() {
+    let json = to_json(&frame(r#"
+        import shapes { rect }
+        import coords { resolution, origin, top_left }
+        resolution(800, 600)
+        origin(top_left)
+        let t = transform().translate(5, 0)
+        out << rect(vec2(10, 20), vec2(30, 40))@t
+    "#));
+    assert!(json.contains(r#""type":"rect""#), "{json}");
+    assert!(json.contains(r#""tx":5"#), "{json}");
+    assert!(json.contains(r#""px_width":800,"px_height":600,"origin":"top_left""#), "{json}");
+}
+
+#[test]
+fn json_empty_frame() {
+    assert_eq!(to_json(&[]), "[]");
+}
+
+// ─── SVG ─────────────────────────────────────────────────────────────────────
+
+#[test]
+fn svg_single_circle() {
+    let svg = export_svg(&frame(r#"
+        import shapes { circle }
+        import coords { resolution, origin, top_left }
+        resolution(200, 100)
+        origin(top_left)
+        out << circle(vec2(50, 40), 10)
+    "#), 200, 100);
+    assert!(svg.starts_with("<svg "), "{svg}");
+    assert!(svg.contains(r#"xmlns="http://www.w3.org/2000/svg""#), "{svg}");
+    assert!(svg.trim_end().ends_with("</svg>"), "{svg}");
+    assert_eq!(svg.matches("<circle").count(), 1, "{svg}");
+    assert!(svg.contains(r#"cx="50" cy="40" r="10""#), "{svg}");
+}
+
+#[test]
+fn svg_render_mode_selects_fill_or_stroke() {
+    let svg = export_svg(&frame(r#"
+        import shapes { rect, line }
+        import render { outline }
+        import coords { resolution, origin, top_left }
+        resolution(100, 100)
+        origin(top_left)
+        out << rect(vec2(10, 10), vec2(20, 30), origin: top_left, fill: #ff0000)
+        out << rect(vec2(50, 50), vec2(10, 10), render: outline)
+        out << line(vec2(0, 0), vec2(100, 100))
+    "#), 100, 100);
+    assert!(svg.contains(r##"<rect x="10" y="10" width="20" height="30" fill="#ff0000""##), "{svg}");
+    assert!(svg.contains(r##"fill="none" stroke="#000000""##), "{svg}");
+    assert!(svg.contains(r#"<line x1="0" y1="0" x2="100" y2="100""#), "{svg}");
+}
+
+#[test]
+fn svg_dashed_sets_dasharray() {
+    let svg = export_svg(&frame(r#"
+        import shapes { circle }
+        import render { dashed }
+        out << circle(vec2(0, 0), 0.5, render: dashed(2.0, 5.0, 3.0))
+    "#), 100, 100);
+    assert!(svg.contains(r#"stroke-width="2" stroke-dasharray="5 3""#), "{svg}");
+}
+
+#[test]
+fn svg_transformed_shape_becomes_polygon() {
+    let svg = export_svg(&frame(r#"
+        import shapes { rect }
+        let t = transform().rotate(0.5)
+        out << rect(vec2(0, 0), vec2(0.5, 0.5))@t
+    "#), 100, 100);
+    assert_eq!(svg.matches("<polygon").count(), 1, "{svg}");
+    assert!(!svg.contains("<rect"), "{svg}");
+}
@@ -0,0 +1,253 @@
+//! Tessellation tests.
+//!
+//! Checks `render::tessellate_screen_px` / `tessellate_ndc` output for
+//! hand-built shapes and for shapes emitted by scripts.
+
+use rustle_lang::{compile, Runtime, Input, DrawCommand, CoordMeta, Origin, RenderMode, ShapeData, ShapeDesc, TransformData};
+use rustle_lang::render::{tessellate_ndc, tessellate_screen_px};
+
+// ─── Helpers ─────────────────────────────────────────────────────────────────
+
+/// 1×1 square centered at the origin of a 100×100 center-origin canvas.
+fn unit_square(transforms: Vec<TransformData>) -> ShapeData {
+    let meta = CoordMeta { px_width: 100.0, px_height: 100.0, origin: Origin::Center, normalized: false, y_down: None };
+    let mut data = ShapeData::new(
+        ShapeDesc::Rect { center: (0.0, 0.0), size: (1.0, 1.0), origin: Origin::Center },
+        RenderMode::Fill,
+        meta,
+    );
+    data.transforms = transforms;
+    data
+}
+
+fn assert_close(a: (f64, f64), b: (f64, f64)) {
+    assert!((a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9, "{a:?} != {b:?}");
+}
+
+// ─── Tessellation ────────────────────────────────────────────────────────────
+
+#[test]
+fn unit_circle_has_segment_vertices_at_radius() {
+    let meta = CoordMeta { px_width: 200.0, px_height: 200.0, origin: Origin::Center, normalized: false, y_down: None };
+    let data = ShapeData::new(
+        ShapeDesc::Circle { center: (0.0, 0.0), radius: 1.0 },
+        RenderMode::Fill,
+        meta,
+    );
+    let verts = tessellate_screen_px(&data);
+    assert_eq!(verts.len(), 64);
+    for (x, y) in verts {
+        let d = ((x - 100.0).powi(2) + (y - 100.0).powi(2)).sqrt();
+        assert!((d - 1.0).abs() < 1e-9, "vertex ({x}, {y}) at distance {d}");
+    }
+}
+
+#[test]
+fn wedge_outline_closes_through_center() {
+    let meta = CoordMeta { px_width: 200.0, px_height: 200.0, origin: Origin::Center, normalized: false, y_down: None };
+    for mode in [RenderMode::Fill, RenderMode::Outline, RenderMode::Stroke(2.0)] {
+        let data = ShapeData::new(
+            ShapeDesc::Wedge { center: (0.0, 0.0), radius: 1.0, start_angle: 0.0, end_angle: std::f64::consts::FRAC_PI_2 },
+            mode,
+            meta.clone(),
+        );
+        let verts = tessellate_screen_px(&data);
+        // Center first, then a quarter of the circle's segments along the arc.
+        assert_eq!(verts.len(), 18);
+        assert_close(verts[0], (100.0, 100.0));
+        assert_close(verts[1], (101.0, 100.0));
+        // y-down screen: the counter-clockwise arc ends above the center.
+        assert_close(verts[17], (100.0, 99.0));
+    }
+}
+
+#[test]
+fn clockwise_wedge_sweeps_backwards() {
+    let meta = CoordMeta { px_width: 200.0, px_height: 200.0, origin: Origin::Center, normalized: false, y_down: None };
+    let data = ShapeData::new(
+        ShapeDesc::Wedge { center: (0.0, 0.0), radius: 1.0, start_angle: 0.0, end_angle: -std::f64::consts::PI },
+        RenderMode::Fill,
+        meta,
+    );
+    let verts = tessellate_ndc(&data);
+    assert_eq!(verts.len(), 34);
+    assert_close(verts[0], (0.0, 0.0));
+    // Every arc vertex sits on or below the center in y-up NDC.
+    assert!(verts.iter().all(|v| v.1 <= 1e-12), "{verts:?}");
+}
+
+#[test]
+fn shear_offsets_top_edge_horizontally() {
+    let td = TransformData { shx: 1.0, ..TransformData::default() };
+    let verts = tessellate_screen_px(&unit_square(vec![td]));
+    // Screen px, y-down: the first two vertices are the top edge (user y = +0.5),
+    // which shifts right by shx·0.5; the bottom edge shifts left by the same.
+    assert_close(verts[0], (50.0, 49.5));
+    assert_close(verts[1], (51.0, 49.5));
+    assert_close(verts[2], (50.0, 50.5));
+    assert_close(verts[3], (49.0, 50.5));
+}
+
+#[test]
+fn rotate_around_pivot_mirrors_point() {
+    // 180° about user (1, 0) maps (x, y) → (2 - x, -y).
+    let meta = CoordMeta { px_width: 100.0, px_height: 100.0, origin: Origin::Center, normalized: false, y_down: None };
+    let mut data = ShapeData::new(
+        ShapeDesc::Polygon(vec![(0.0, 0.0), (0.5, 0.0), (0.0, 0.5)]),
+        RenderMode::Fill,
+        meta,
+    );
+    data.transforms = vec![TransformData {
+        angle: std::f64::consts::PI,
+        pivot: Some((1.0, 0.0)),
+        ..TransformData::default()
+    }];
+
+    let px = tessellate_screen_px(&data);
+    assert_close(px[0], (52.0, 50.0));
+    assert_close(px[1], (51.5, 50.0));
+    assert_close(px[2], (52.0, 50.5));
+
+    let ndc = tessellate_ndc(&data);
+    assert_close(ndc[0], (0.04, 0.0));
+    assert_close(ndc[1], (0.03, 0.0));
+    assert_close(ndc[2], (0.04, -0.01));
+}
+
+#[test]
+fn compose_matches_sequential_application() {
+    let src = r#"
+        import shapes { rect }
+        import coords { resolution }
+        resolution(400.0, 400.0)
+        let a = transform().move(10.0, 0.0).scale(2.0).rotate(30.0)
+        let b = transform().move(0.0, 5.0).scale(1.5).rotate(45.0)
+        let s = rect(vec2(20.0, 10.0), vec2(8.0, 4.0))
+        out << s@(a, b)
+        out << s@(a.compose(b))
+    "#;
+    let mut rt = Runtime::new(compile(src).expect("compile")).expect("init");
+    let cmds = rt.tick(&Input { dt: 0.016, ..Input::default() }).expect("tick");
+    let (DrawCommand::DrawShape(seq), DrawCommand::DrawShape(composed)) = (&cmds[0], &cmds[1]);
+    for (a, b) in tessellate_screen_px(seq).iter().zip(&tessellate_screen_px(composed)) {
+        assert_close(*a, *b);
+    }
+    for (a, b) in tessellate_ndc(seq).iter().zip(&tessellate_ndc(composed)) {
+        assert_close(*a, *b);
+    }
+}
+
+#[test]
+fn compose_matches_sequential_application_with_shear_and_pivots() {
+    let src = r#"
+        import shapes { rect }
+        import coords { resolution }
+        resolution(400.0, 400.0)
+        let s = rect(vec2(20.0, 10.0), vec2(8.0, 4.0))
+        let a = transform().shear(0.5, 0.0).scale(2.0).rotate(30.0).move(10.0, 0.0)
+        let b = transform().shear(0.0, 0.25).rotate(45.0).move(0.0, 5.0)
+        out << s@(a, b)
+        out << s@(a.compose(b))
+        let c = transform().rotate_around(5.0, 5.0, 30.0).shear(0.5, 0.0)
+        let d = transform().rotate_around(5.0, 5.0, 60.0).scale(1.5)
+        out << s@(c, d)
+        out << s@(c.compose(d))
+        let e = transform().move(3.0, -2.0)
+        out << s@(e, d)
+        out << s@(e.compose(d))
+    "#;
+    let mut rt = Runtime::new(compile(src).expect("compile")).expect("init");
+    let cmds = rt.tick(&Input { dt: 0.016, ..Input::default() }).expect("tick");
+    for pair in cmds.chunks(2) {
+        let [DrawCommand::DrawShape(seq), DrawCommand::DrawShape(composed)] = pair else { unreachable!() };
+        for (a, b) in tessellate_screen_px(seq).iter().zip(&tessellate_screen_px(composed)) {
+            assert_close(*a, *b);
+        }
+        for (a, b) in tessellate_ndc(seq).iter().zip(&tessellate_ndc(composed)) {
+            assert_close(*a, *b);
+        }
+    }
+}
+
+#[test]
+fn compose_rejects_mixed_pivots() {
+    let src = r#"
+        let a = transform().rotate_around(5.0, 5.0, 30.0)
+        let b = transform().scale(2.0)
+        let c = a.compose(b)
+    "#;
+    let e = Runtime::new(compile(src).expect("compile")).err().expect("expected a runtime error");
+    assert!(e.message.contains("compose cannot merge"), "{}", e.message);
+}
+
+#[test]
+fn default_transform_leaves_square_unchanged() {
+    let plain = tessellate_ndc(&unit_square(vec![]));
+    let with_default = tessellate_ndc(&unit_square(vec![TransformData::default()]));
+    for (a, b) in plain.iter().zip(&with_default) {
+        assert_close(*a, *b);
+    }
+}
+
+#[test]
+fn normalize_aspect_keeps_circle_round_on_wide_canvas() {
+    let src = r#"
+        import shapes { circle }
+        import coords { resolution, normalize_aspect }
+        resolution(800.0, 400.0)
+        normalize_aspect()
+        out << circle(vec2(0.0, 0.0), 0.5)
+    "#;
+    let mut rt = Runtime::new(compile(src).expect("compile")).expect("init");
+    let cmds = rt.tick(&Input { dt: 0.016, ..Input::default() }).expect("tick");
+    let DrawCommand::DrawShape(data) = &cmds[0];
+    let verts = tessellate_screen_px(data);
+    let extent = |axis: fn(&(f64, f64)) -> f64| {
+        let vals: Vec<f64> = verts.iter().map(axis).collect();
+        vals.iter().cloned().fold(f64::MIN, f64::max) - vals.iter().cloned().fold(f64::MAX, f64::min)
+    };
+    // One unit is half the shorter side (200 px), so the diameter is 200 px.
+    assert!((extent(|v| v.0) - 200.0).abs() < 1e-9);
+    assert!((extent(|v| v.1) - 200.0).abs() < 1e-9);
+}
+
+/// Screen-px center of the first shape a script emits.
+fn first_shape_center_px(src: &str) -> (f64, f64) {
+    let mut rt = Runtime::new(compile(src).expect("compile")).expect("init");
+    let cmds = rt.tick(&Input { dt: 0.016, ..Input::default() }).expect("tick");
+    let DrawCommand::DrawShape(data) = &cmds[0];
+    rustle_lang::render::centroid(&tessellate_screen_px(data))
+}
+
+#[test]
+fn y_up_and_y_down_mirror_the_same_circle() {
+    let script = |toggle: &str| format!(r#"
+        import shapes {{ circle }}
+        import coords {{ resolution, {toggle} }}
+        resolution(200.0, 200.0)
+        {toggle}()
+        out << circle(vec2(0.0, 1.0), 0.1)
+    "#);
+    let up = first_shape_center_px(&script("y_up"));
+    let down = first_shape_center_px(&script("y_down"));
+    assert_close(up, (100.0, 99.0));
+    assert_close(down, (100.0, 101.0));
+}
+
+#[test]
+fn y_flip_keeps_origin_anchor() {
+    // top_left is y-down by default; y_up keeps y = 0 on the top edge but
+    // points the axis up, so positive y and upward moves leave the canvas.
+    let meta = CoordMeta { px_width: 100.0, px_height: 100.0, origin: Origin::TopLeft, normalized: false, y_down: Some(false) };
+    assert_eq!(meta.y_to_screen_px(0.0), 0.0);
+    assert_eq!(meta.y_to_screen_px(10.0), -10.0);
+    assert_eq!(meta.y_to_ndc(0.0), 1.0);
+    assert_eq!(meta.screen_px_to_y(meta.y_to_screen_px(25.0)), 25.0);
+    let data = ShapeData::new(ShapeDesc::Circle { center: (0.0, 0.0), radius: 1.0 }, RenderMode::Fill, meta);
+    let moved = ShapeData {
+        transforms: vec![TransformData { ty: 5.0, ..TransformData::default() }],
+        ..data
+    };
+    let (_, cy) = rustle_lang::render::centroid(&tessellate_screen_px(&moved));
+    assert!((cy + 5.0).abs() < 1e-9, "{cy}");
+}
@@ -2,9 +2,12 @@
 //!
 //! Tests the full compile pipeline through the public `compile()` API.
 //! Each test covers one specific semantic rule or success path.
-//! Error codes: S001–S012.
+//! Error codes: S001–S012, warnings: W001–W003.
 
 use rustle_lang::{compile, Error, ErrorCode};
+use rustle_lang::analysis::resolve;
+use rustle_lang::namespaces::NamespaceRegistry;
+use rustle_lang::syntax::{lexer::Lexer, parser::Parser};
 
 // ─── Helpers ─────────────────────────────────────────────────────────────────
 
@@ -21,6 +24,16 @@ fn err(src: &str) -> Vec<Error> {
     }
 }
 
+/// Warnings from a program that must compile — `compile()` drops them.
+fn warnings(src: &str) -> Vec<Error> {
+    let tokens = Lexer::new(src).tokenize().expect("lex");
+    let ast = Parser::new(tokens).parse().expect("parse");
+    match resolve(&ast, &NamespaceRegistry::standard()) {
+        Ok(result) => result.warnings,
+        Err(errs)  => panic!("expected compile to succeed, got errors: {errs:#?}"),
+    }
+}
+
 fn has(errs: &[Error], code: ErrorCode) -> bool {
     errs.iter().any(|e| e.code == code)
 }
@@ -136,12 +149,26 @@ fn s002_let_annotation_mismatch() {
     assert!(has(&errs, ErrorCode::S002));
 }
 
+#[test]
+fn s002_float_not_assignable_to_int() {
+    let errs = err("let n: int = 1.5");
+    assert!(has(&errs, ErrorCode::S002));
+    assert!(has_msg(&errs, "expected `int`, found `float`"));
+}
+
 #[test]
 fn s002_assign_type_mismatch() {
     let errs = err("let x: float = 0.0\nx = true");
     assert!(has(&errs, ErrorCode::S002));
 }
 
+#[test]
+fn s002_compound_assign_result_mismatch() {
+    let errs = err("let n = 1\nn += 0.5");
+    assert!(has(&errs, ErrorCode::S002));
+    assert!(has_msg(&errs, "expected `int`, found `float`"));
+}
+
 #[test]
 fn s002_out_expects_shape() {
     let errs = err("import shapes { circle }\nlet x = 3.14\nout << x");
@@ -149,12 +176,37 @@ fn s002_out_expects_shape() {
     assert!(has_msg(&errs, "shape"));
 }
 
+#[test]
+fn s002_propagate_outside_res_function() {
+    let errs = err(r#"
+        fn f() -> float {
+            let r: res<float> = ok(1.0)
+            return r?
+        }
+    "#);
+    assert!(has(&errs, ErrorCode::S002));
+    assert!(has_msg(&errs, "res<"));
+}
+
+#[test]
+fn s002_res_unwrap_or_wrong_default() {
+    let errs = err("let r: res<float> = ok(1.0)\nlet v = r.unwrap_or(true)");
+    assert!(has(&errs, ErrorCode::S002));
+}
+
 #[test]
 fn s002_ternary_branches_different_types() {
     let errs = err("let x = true ? 1.0 : false");
     assert!(has(&errs, ErrorCode::S002));
 }
 
+#[test]
+fn s002_match_expression_arms_different_types() {
+    let errs = err("let x = match 1.0 { 1.0 => 2.0, else => true }");
+    assert!(has(&errs, ErrorCode::S002));
+    assert!(has_msg(&errs, "match branches"));
+}
+
 #[test]
 fn s002_transform_on_non_shape() {
     let errs = err(r#"
@@ -202,6 +254,51 @@ fn s002_circle_expects_vec2_not_vec3() {
     assert!(has_msg(&errs, "vec2"));
 }
 
+#[test]
+fn s002_list_reduce_wrong_accumulator() {
+    let errs = err(r#"
+        fn both(acc: bool, x: float) -> bool { return acc }
+        let total = [1.0, 2.0].reduce(0.0, both)
+    "#);
+    assert!(has(&errs, ErrorCode::S002));
+}
+
+#[test]
+fn s002_list_map_param_mismatch() {
+    let errs = err("let ys = [1.0, 2.0].map((v: vec2) -> float { return v.x })");
+    assert!(has(&errs, ErrorCode::S002));
+}
+
+#[test]
+fn ok_map_int_elements_into_float_param() {
+    ok(r#"
+        let ys = [1, 2].map((x: float) -> float { return x / 2.0 })
+        let r: res<int> = ok(3)
+        let m: res<float> = r.map((x: float) -> float { return x.sqrt() })
+    "#);
+}
+
+#[test]
+fn s002_map_float_elements_into_int_param() {
+    let errs = err("let ys = [1.0, 2.0].map((n: int) -> int { return n })");
+    assert!(has(&errs, ErrorCode::S002));
+}
+
+#[test]
+fn s002_whole_namespace_call_arg_type() {
+    let errs = err("import shapes\nout << shapes.circle(0.0, 0.2)");
+    assert!(has(&errs, ErrorCode::S002));
+    let errs = err("import shapes\nlet r: float = shapes.rect(vec2(0.0, 0.0), vec2(1.0, 1.0))");
+    assert!(has_msg(&errs, "found `rect`"));
+}
+
+#[test]
+fn s002_vec_clamp_bounds_type() {
+    let errs = err("let a = 1.0\nlet p = vec2(5.0, -5.0).clamp(vec3(0.0, 0.0, 0.0), vec2(1.0, 1.0))");
+    assert!(has(&errs, ErrorCode::S002));
+    assert!(errs.iter().any(|e| e.line == 2), "{errs:?}");
+}
+
 // ─── S003: redeclaration ──────────────────────────────────────────────────────
 
 #[test]
@@ -225,6 +322,21 @@ fn s003_duplicate_import_member() {
     assert!(has(&errs, ErrorCode::S003));
 }
 
+#[test]
+fn s003_aliases_avoid_member_collision() {
+    ok("import shapes { circle }\nimport shapes { circle as c }");
+    let errs = err("import shapes { circle as c }\nimport shapes { rect as c }");
+    assert!(has(&errs, ErrorCode::S003));
+    assert!(has_msg(&errs, "`c` already declared"));
+}
+
+#[test]
+fn s003_duplicate_wildcard_import() {
+    ok("import core { * }\nimport shapes { * }");
+    let errs = err("import shapes { * }\nimport shapes { * }");
+    assert!(has(&errs, ErrorCode::S003));
+}
+
 #[test]
 fn s003_var_same_name_as_fn() {
     let errs = err(r#"
@@ -245,6 +357,37 @@ fn s003_local_shadow_in_inner_scope_ok() {
     "#);
 }
 
+#[test]
+fn s003_duplicate_type_alias() {
+    let errs = err("type P = vec2\ntype P = float");
+    assert!(has(&errs, ErrorCode::S003));
+    assert!(has_msg(&errs, "type alias `P` already declared"));
+}
+
+#[test]
+fn s003_type_alias_shadowing_builtin() {
+    let errs = err("type vec2 = float");
+    assert!(has(&errs, ErrorCode::S003));
+    assert!(has_msg(&errs, "shadows a built-in type"));
+}
+
+#[test]
+fn s003_duplicate_struct_and_field() {
+    let errs = err("struct P { a: float }\nstruct P { b: float }");
+    assert!(has_msg(&errs, "struct `P` already declared"));
+    let errs = err("struct P { a: float, a: vec2 }");
+    assert!(has(&errs, ErrorCode::S003));
+    assert!(has_msg(&errs, "field `a` already declared in `P`"));
+}
+
+#[test]
+fn s003_duplicate_enum_variant() {
+    let errs = err("enum Mode { Menu, Menu }");
+    assert!(has_msg(&errs, "variant `Menu` already declared in `Mode`"));
+    let errs = err("struct Mode { a: float }\nenum Mode { Menu }");
+    assert!(has_msg(&errs, "enum `Mode` already declared"));
+}
+
 // ─── S004: const reassignment ─────────────────────────────────────────────────
 
 #[test]
@@ -295,6 +438,13 @@ fn s006_member_not_exported() {
     assert!(has_msg(&errs, "not_a_shape"));
 }
 
+#[test]
+fn s006_aliased_member_not_exported() {
+    let errs = err("import shapes { hexagon as h }");
+    assert!(has(&errs, ErrorCode::S006));
+    assert!(has_msg(&errs, "does not export `hexagon`"));
+}
+
 #[test]
 fn s006_wrong_namespace_for_member() {
     let errs = err("import coords { circle }");
@@ -309,6 +459,71 @@ fn s006_render_nonexistent_mode() {
 
 // ─── S007: wrong argument count ───────────────────────────────────────────────
 
+#[test]
+fn s007_namespace_call_wrong_arg_count() {
+    let errs = err("import shapes\nout << shapes.circle(vec2(0.0, 0.0))");
+    assert!(has(&errs, ErrorCode::S007));
+    assert!(has_msg(&errs, "`shapes.circle` expects 2 argument(s), got 1"));
+}
+
+#[test]
+fn default_params_allow_fewer_args() {
+    ok(r#"
+        fn grow(x: float, by: float = 1.0, times: int = 1) -> float { return x + by * times }
+        let a = grow(1.0)
+        let b = grow(1.0, 2.0)
+        let c = grow(1.0, 2.0, 3)
+    "#);
+}
+
+#[test]
+fn s007_default_params_still_need_required_args() {
+    let errs = err("fn grow(x: float, by: float = 1.0) -> float { return x + by }
+let a = grow()");
+    assert!(has(&errs, ErrorCode::S007));
+    assert!(has_msg(&errs, "`grow` expects 1 to 2 argument(s), got 0"));
+}
+
+#[test]
+fn s002_default_param_type_mismatch() {
+    let errs = err("fn grow(x: float, by: float = true) -> float { return x }
+let a = grow(1.0)");
+    assert!(has(&errs, ErrorCode::S002));
+}
+
+#[test]
+fn named_args_bind_user_fn_params() {
+    ok(r#"
+        fn place(x: float, y: float, r: float = 1.0) -> vec2 { return vec2(x * r, y * r) }
+        let a = place(y: 2.0, x: 1.0)
+        let b = place(1.0, r: 3.0, y: 2.0)
+    "#);
+}
+
+#[test]
+fn s007_named_arg_unknown() {
+    let errs = err("fn place(x: float, y: float) -> vec2 { return vec2(x, y) }
+let a = place(1.0, z: 2.0)");
+    assert!(has(&errs, ErrorCode::S007));
+    assert!(has_msg(&errs, "`place` has no parameter named `z`"));
+    assert!(has_msg(&errs, "missing argument `y`"));
+}
+
+#[test]
+fn s007_named_arg_duplicate() {
+    let errs = err("fn place(x: float, y: float) -> vec2 { return vec2(x, y) }
+let a = place(1.0, x: 1.0, y: 2.0)");
+    assert!(has(&errs, ErrorCode::S007));
+    assert!(has_msg(&errs, "argument `x` is given more than once"));
+}
+
+#[test]
+fn s002_named_arg_type_mismatch() {
+    let errs = err("fn place(x: float, y: float) -> vec2 { return vec2(x, y) }
+let a = place(x: 1.0, y: true)");
+    assert!(has(&errs, ErrorCode::S002));
+}
+
 #[test]
 fn s007_circle_too_few_args() {
     let errs = err("import shapes { circle }\nlet c = circle(vec2(0.0, 0.0))");
@@ -363,6 +578,17 @@ fn s007_user_fn_too_many_args() {
     assert!(has(&errs, ErrorCode::S007));
 }
 
+#[test]
+fn to_string_accepts_any_type() {
+    ok(r#"
+        enum Mode { Menu }
+        let a: string = to_string(1.5) + to_string(vec2(1.0, 2.0)) + to_string([true])
+        let b: string = to_string(Mode.Menu) + format_float(PI, 2.0)
+    "#);
+    let errs = err("let s = to_string(1.0, 2.0)");
+    assert!(has(&errs, ErrorCode::S007));
+}
+
 // ─── S008: operator not applicable ───────────────────────────────────────────
 
 #[test]
@@ -378,12 +604,146 @@ fn s002_index_assign_not_float() {
     assert!(has_msg(&errs, "float"));
 }
 
+#[test]
+fn s002_map_index_not_string() {
+    let errs = err(r#"let m = { "a": 1.0 }
+let x = m[0]"#);
+    assert!(has(&errs, ErrorCode::S002));
+    assert!(has_msg(&errs, "string"));
+}
+
+#[test]
+fn s002_map_mixed_value_types() {
+    let errs = err(r#"let m = { "a": 1.0, "b": true }"#);
+    assert!(has(&errs, ErrorCode::S002));
+}
+
+#[test]
+fn map_index_and_methods_typed() {
+    ok(r#"
+        let m = { "a": 1.0, "b": 2 }
+        let x: float = m["b"]
+        let ks: list[string] = m.keys()
+        let found: bool = m.has("a")
+        let n: float = m.len
+    "#);
+}
+
+#[test]
+fn int_map_literal_widens_to_annotated_float() {
+    ok(r#"
+        let m: map[float] = { "a": 1, "b": 2 }
+        let nested: list[map[float]] = [{ "x": 0 }]
+    "#);
+}
+
+#[test]
+fn s002_annotated_map_literal_key_and_value() {
+    let errs = err(r#"let m: map[float] = { "a": true }"#);
+    assert!(has(&errs, ErrorCode::S002));
+    let errs = err(r#"let m: map[float] = { 1: 1.0 }"#);
+    assert!(has_msg(&errs, "map keys must be `string`"));
+}
+
+#[test]
+fn empty_literals_take_annotated_type() {
+    ok(r#"
+        let shapes: list[vec2] = []
+        let palette: map[color] = {}
+        palette["sky"] = color(0.4, 0.6, 1.0, 1.0)
+    "#);
+}
+
+#[test]
+fn tuple_fields_and_destructure_typed() {
+    ok(r#"
+        fn split(v: vec2) -> (float, vec2) {
+            return (v.length(), v.normalize())
+        }
+        let pair = split(vec2(3.0, 4.0))
+        let n: float = pair.0
+        let (len, dir) = pair
+        let d: vec2 = dir
+        let widened: (float, float) = (1, 2.5)
+    "#);
+}
+
+#[test]
+fn s009_tuple_field_out_of_range() {
+    let errs = err("let t = (1.0, 2.0)
+let x = t.2");
+    assert!(has(&errs, ErrorCode::S009));
+}
+
+#[test]
+fn s002_destructure_arity_mismatch() {
+    let errs = err("let (a, b, c) = (1.0, 2.0)");
+    assert!(has(&errs, ErrorCode::S002));
+    assert!(has_msg(&errs, "2-element"));
+}
+
+#[test]
+fn s002_destructure_non_tuple() {
+    let errs = err("let (a, b) = vec2(1.0, 2.0)");
+    assert!(has(&errs, ErrorCode::S002));
+}
+
+#[test]
+fn s002_int_list_variable_not_float_list() {
+    // Only a literal widens; a `list[int]` variable is shared by reference.
+    let errs = err("let xs = [1, 2]
+let t = sum(xs)");
+    assert!(has(&errs, ErrorCode::S002));
+}
+
+#[test]
+fn interpolated_string_is_string() {
+    ok(r#"
+        let v = vec2(1.0, 2.0)
+        let label: string = "v={v} len={v.length()} ok={true}"
+    "#);
+}
+
+#[test]
+fn s002_interpolate_function() {
+    let errs = err("fn f() -> float { return 1.0 }\nlet s = \"{f}\"");
+    assert!(has(&errs, ErrorCode::S002));
+    assert!(has_msg(&errs, "cannot interpolate"));
+}
+
+#[test]
+fn comprehension_typed_as_list_of_expr() {
+    ok(r#"
+        let pts: list[vec2] = [vec2(x, 0.0) for x in [1.0, 2.0] if x > 1.0]
+    "#);
+}
+
+#[test]
+fn s002_comprehension_filter_not_bool() {
+    let errs = err("let xs = [x for x in [1.0, 2.0] if x]");
+    assert!(has(&errs, ErrorCode::S002));
+    assert!(has_msg(&errs, "comprehension filter must be `bool`"));
+}
+
+#[test]
+fn s002_comprehension_over_non_list() {
+    let errs = err("let xs = [x for x in 3.0]");
+    assert!(has(&errs, ErrorCode::S002));
+    assert!(has_msg(&errs, "comprehension expects a list or array"));
+}
+
 #[test]
 fn s008_compare_vec2_with_float() {
     let errs = err("let a = vec2(1.0, 0.0)\nlet c = a == 1.0");
     assert!(has(&errs, ErrorCode::S008));
 }
 
+#[test]
+fn s008_chained_comparison_checks_each_link() {
+    let errs = err("let b = 1.0 < vec2(1.0, 2.0) < 3.0");
+    assert!(has(&errs, ErrorCode::S008));
+}
+
 #[test]
 fn s008_add_bool_and_float() {
     let errs = err("let a = true\nlet b = a + 1.0");
@@ -430,6 +790,12 @@ fn s009_vec2_has_no_z_field() {
     assert!(has(&errs, ErrorCode::S009));
 }
 
+#[test]
+fn s009_swizzle_out_of_range_component() {
+    let errs = err("let v = vec2(1.0, 2.0).xz");
+    assert!(has(&errs, ErrorCode::S009));
+}
+
 #[test]
 fn s009_res_invalid_field() {
     let errs = err("let r: res<float> = ok(1.0)\nlet x = r.bad_field");
@@ -513,6 +879,113 @@ fn s012_on_exit_wrong_signature() {
     assert!(has(&errs, ErrorCode::S012));
 }
 
+// ─── W001: unused variable ────────────────────────────────────────────────────
+
+#[test]
+fn w001_unused_let() {
+    let warns = warnings("let x = 1.0");
+    assert_eq!(warns.len(), 1);
+    assert!(has(&warns, ErrorCode::W001));
+    assert!(has_msg(&warns, "`x`"));
+}
+
+#[test]
+fn w001_underscore_suppresses() {
+    assert!(warnings("let _y = 1.0").is_empty());
+}
+
+#[test]
+fn w001_unused_param_and_local() {
+    let warns = warnings(r#"
+        import shapes { circle }
+        fn f(a: float, b: float) -> float {
+            let t = 2.0
+            return a
+        }
+        let r = f(1.0, 2.0)
+        out << circle(vec2(r, 0.0), 0.1)
+    "#);
+    assert_eq!(warns.len(), 2);
+    assert!(has_msg(&warns, "unused parameter `b`"));
+    assert!(has_msg(&warns, "unused variable `t`"));
+}
+
+#[test]
+fn w001_plain_assignment_is_not_a_read() {
+    let warns = warnings("let x = 1.0\nx = 2.0");
+    assert!(has_msg(&warns, "`x`"));
+    assert!(warnings("let xs = [1.0]\nxs[0] = 2.0").is_empty());
+}
+
+#[test]
+fn w001_hook_params_and_loop_bindings_exempt() {
+    let warns = warnings(r#"
+        state { let n: float = 0.0 }
+        fn on_update(s: State, input: Input) -> State {
+            foreach i, v in [1.0, 2.0] { s.n += 1.0 }
+            return s
+        }
+    "#);
+    assert!(warns.is_empty(), "{warns:#?}");
+}
+
+// ─── W002: unused import ──────────────────────────────────────────────────────
+
+#[test]
+fn w002_unused_import_member() {
+    let warns = warnings(r#"
+        import shapes { circle, rect }
+        out << circle(vec2(0.0, 0.0), 0.1)
+    "#);
+    assert_eq!(warns.len(), 1);
+    assert!(has(&warns, ErrorCode::W002));
+    assert!(has_msg(&warns, "unused import `rect`"));
+    assert_eq!((warns[0].line, warns[0].column), (2, 9));
+}
+
+#[test]
+fn w002_references_through_named_args_and_namespaces() {
+    let warns = warnings(r#"
+        import shapes
+        import render { sdf }
+        import coords as co
+        co.resolution(800.0, 600.0)
+        out << shapes.circle(vec2(0.0, 0.0), 0.1, render: sdf)
+    "#);
+    assert!(warns.is_empty(), "{warns:#?}");
+    let warns = warnings("import render\nimport core { * }");
+    assert_eq!(warns.len(), 1);
+    assert!(has_msg(&warns, "unused import `render`"));
+}
+
+// ─── W003: non-exhaustive enum match ──────────────────────────────────────────
+
+#[test]
+fn w003_enum_match_missing_variant() {
+    let w = warnings(r#"
+        enum Mode { Menu, Playing, Paused }
+        fn code(m: Mode) -> float {
+            return match m { Mode.Menu => 0.0, Mode.Playing => 1.0 }
+        }
+    "#);
+    assert!(has(&w, ErrorCode::W003));
+    assert!(has_msg(&w, "match on `Mode` does not cover `Paused`"));
+}
+
+#[test]
+fn w003_not_reported_when_covered_or_else() {
+    let w = warnings(r#"
+        enum Mode { Menu, Playing, Paused }
+        fn a(m: Mode) -> float {
+            return match m { Mode.Menu => 0.0, Mode.Playing, Mode.Paused => 1.0 }
+        }
+        fn b(m: Mode) -> float {
+            return match m { Mode.Menu => 0.0, else => 1.0 }
+        }
+    "#);
+    assert!(!has(&w, ErrorCode::W003));
+}
+
 // ─── Success: type system ─────────────────────────────────────────────────────
 
 #[test]
@@ -690,6 +1163,15 @@ fn ok_try_expr() {
     "#);
 }
 
+#[test]
+fn ok_res_map_changes_inner_type() {
+    ok(r#"
+        fn positive(x: float) -> bool { return x > 0.0 }
+        let r: res<float> = ok(1.0)
+        let m: res<bool> = r.map(positive)
+    "#);
+}
+
 #[test]
 fn ok_ternary() {
     ok("let x = 5.0\nlet y = x > 0.0 ? x : 0.0");
@@ -760,6 +1242,116 @@ fn ok_update_uses_input_dt() {
     "#);
 }
 
+#[test]
+fn int_widens_to_float() {
+    ok(r#"
+        fn half(x: float) -> float { return x / 2 }
+        let a: float = 3
+        let b = half(4) + a
+        let xs = [1, 2.5]
+        let c: float = xs[0] + (true ? 1 : 0.5)
+    "#);
+}
+
+#[test]
+fn nested_int_literals_widen_to_float() {
+    ok(r#"
+        fn half() -> res<float> { return ok(4) }
+        let ys: list[list[float]] = [[1, 2], [3]]
+        let ps: list[(float, float)] = [(0, 1), (2.5, 3)]
+        let t: (list[float], int) = ([1], 2)
+    "#);
+}
+
+#[test]
+fn s002_nested_literal_element_mismatch() {
+    let errs = err("let ys: list[list[float]] = [[1.0], [true]]");
+    assert!(has(&errs, ErrorCode::S002));
+}
+
+#[test]
+fn ok_struct_literal_and_field_types() {
+    ok(r#"
+        struct Particle { pos: vec2, vel: vec2, life: float }
+        fn advance(p: Particle, dt: float) -> Particle {
+            p.pos = p.pos + p.vel * dt
+            p.life = p.life - dt
+            return p
+        }
+        let p = advance(Particle { pos: vec2(0.0, 0.0), vel: vec2(1.0, 0.0), life: 1 }, 0.5)
+        let x: float = p.pos.x
+        let ps: list[Particle] = [p]
+    "#);
+}
+
+#[test]
+fn struct_literal_field_errors() {
+    let src = |lit: &str| format!("struct P {{ pos: vec2, r: float }}\nlet p = {lit}");
+    let errs = err(&src("P { pos: vec2(0.0, 0.0) }"));
+    assert!(has(&errs, ErrorCode::S007));
+    assert!(has_msg(&errs, "missing field `r` in `P`"));
+    let errs = err(&src("P { pos: vec2(0.0, 0.0), r: 1.0, z: 2.0 }"));
+    assert!(has(&errs, ErrorCode::S009));
+    assert!(has_msg(&errs, "struct `P` has no field `z`"));
+    let errs = err(&src("P { pos: 1.0, r: 1.0 }"));
+    assert!(has(&errs, ErrorCode::S002));
+}
+
+#[test]
+fn enum_unknown_variant_and_mismatch() {
+    let errs = err("enum Mode { Menu }\nlet m = Mode.Quit");
+    assert!(has(&errs, ErrorCode::S009));
+    assert!(has_msg(&errs, "enum `Mode` has no variant `Quit`"));
+    let errs = err("enum Mode { Menu }\nlet m: float = Mode.Menu");
+    assert!(has(&errs, ErrorCode::S002));
+    let errs = err(r#"
+        enum Mode { Menu }
+        fn f(m: Mode) -> float { return match m { 1.0 => 1.0, else => 0.0 } }
+    "#);
+    assert!(has(&errs, ErrorCode::S002));
+}
+
+#[test]
+fn struct_unknown_field_read() {
+    let errs = err("struct P { r: float }\nlet p = P { r: 1.0 }\nlet z = p.z");
+    assert!(has(&errs, ErrorCode::S009));
+}
+
+#[test]
+fn ok_type_alias_param_accepts_matching_literal() {
+    ok(r#"
+        type Points = list[vec2]
+        fn count(ps: Points) -> float { return ps.len() }
+        let n = count([vec2(0.0, 0.0), vec2(1.0, 2.0)])
+    "#);
+}
+
+#[test]
+fn ok_type_alias_used_before_declaration() {
+    ok(r#"
+        fn first(ps: Path) -> vec2 { return ps[0] }
+        type Path = Points
+        type Points = list[vec2]
+        let p: Path = [vec2(1.0, 2.0)]
+        let q = first(p)
+    "#);
+}
+
+#[test]
+fn type_alias_mismatch_still_reported() {
+    let errs = err(r#"
+        type Points = list[vec2]
+        fn count(ps: Points) -> float { return ps.len() }
+        let n = count([1.0, 2.0])
+    "#);
+    assert!(has(&errs, ErrorCode::S002));
+}
+
+#[test]
+fn ok_chained_comparison_is_bool() {
+    ok("let x = 2.0\nlet inside: bool = 0.0 <= x < 3 and 1.0 < x > 0.5");
+}
+
 // ─── Success: control flow ────────────────────────────────────────────────────
 
 #[test]
@@ -835,6 +1427,14 @@ fn ok_foreach_with_type_annotation() {
     "#);
 }
 
+#[test]
+fn ok_foreach_with_index() {
+    ok(r#"
+        let xs: list[vec2] = [vec2(1.0, 2.0)]
+        foreach i, v in xs { let d = v * i }
+    "#);
+}
+
 #[test]
 fn ok_nested_control_flow() {
     ok(r#"
@@ -869,6 +1469,28 @@ fn ok_whole_namespace_field_access() {
     ok("import render\nlet mode = render.fill");
 }
 
+#[test]
+fn ok_whole_namespace_call_typed() {
+    ok(r#"
+        import shapes
+        import coords
+        coords.resolution(800.0, 600.0)
+        let c: circle = shapes.circle(vec2(0.0, 0.0), 0.2)
+        out << c
+    "#);
+}
+
+#[test]
+fn ok_namespace_alias() {
+    ok("import shapes as s\nout << s.circle(vec2(0.0, 0.0), 0.2)");
+}
+
+#[test]
+fn s001_aliased_namespace_original_name_unbound() {
+    let errs = err("import shapes as s\nout << shapes.circle(vec2(0.0, 0.0), 0.2)");
+    assert!(has(&errs, ErrorCode::S001));
+}
+
 #[test]
 fn ok_render_stroke() {
     ok(r#"
@@ -5,7 +5,7 @@
 //! Draw commands are inspected for shape emission.
 
 use rustle_lang::{compile, Runtime, Input, Value, DrawCommand};
-use rustle_lang::types::draw::ShapeDesc;
+use rustle_lang::types::draw::{Color, Origin, ShapeDesc};
 
 // ─── Helpers ─────────────────────────────────────────────────────────────────
 
@@ -29,12 +29,12 @@ fn run_err(src: &str) -> rustle_lang::RuntimeError {
 }
 
 fn tick(rt: &mut Runtime) -> Vec<DrawCommand> {
-    rt.tick(&Input { dt: 0.016 })
+    rt.tick(&Input { dt: 0.016, ..Input::default() })
         .unwrap_or_else(|e| panic!("tick failed: {e:?}"))
 }
 
 fn tick_err(rt: &mut Runtime) -> rustle_lang::RuntimeError {
-    rt.tick(&Input { dt: 0.016 })
+    rt.tick(&Input { dt: 0.016, ..Input::default() })
         .expect_err("expected tick to fail")
 }
 
@@ -45,6 +45,13 @@ fn f(rt: &Runtime, key: &str) -> f64 {
     }
 }
 
+fn i(rt: &Runtime, key: &str) -> i64 {
+    match rt.state().0.get(key) {
+        Some(Value::Int(n)) => *n,
+        other => panic!("expected Int for '{key}', got: {other:?}"),
+    }
+}
+
 fn b(rt: &Runtime, key: &str) -> bool {
     match rt.state().0.get(key) {
         Some(Value::Bool(x)) => *x,
@@ -59,6 +66,34 @@ fn v2(rt: &Runtime, key: &str) -> (f64, f64) {
     }
 }
 
+fn v3(rt: &Runtime, key: &str) -> (f64, f64, f64) {
+    match rt.state().0.get(key) {
+        Some(Value::Vec3(x, y, z)) => (*x, *y, *z),
+        other => panic!("expected Vec3 for '{key}', got: {other:?}"),
+    }
+}
+
+fn v4(rt: &Runtime, key: &str) -> (f64, f64, f64, f64) {
+    match rt.state().0.get(key) {
+        Some(Value::Vec4(x, y, z, w)) => (*x, *y, *z, *w),
+        other => panic!("expected Vec4 for '{key}', got: {other:?}"),
+    }
+}
+
+fn rgba(rt: &Runtime, key: &str) -> (f64, f64, f64, f64) {
+    match rt.state().0.get(key) {
+        Some(Value::Color { r, g, b, a }) => (*r, *g, *b, *a),
+        other => panic!("expected Color for '{key}', got: {other:?}"),
+    }
+}
+
+fn str_val(rt: &Runtime, key: &str) -> String {
+    match rt.state().0.get(key) {
+        Some(Value::Str(s)) => s.clone(),
+        other => panic!("expected Str for '{key}', got: {other:?}"),
+    }
+}
+
 fn list_floats(rt: &Runtime, key: &str) -> Vec<f64> {
     match rt.state().0.get(key) {
         Some(Value::List(rc)) => rc.borrow().iter().map(|v| {
@@ -68,6 +103,24 @@ fn list_floats(rt: &Runtime, key: &str) -> Vec<f64> {
     }
 }
 
+fn list_ints(rt: &Runtime, key: &str) -> Vec<i64> {
+    match rt.state().0.get(key) {
+        Some(Value::List(rc)) => rc.borrow().iter().map(|v| {
+            match v { Value::Int(n) => *n, other => panic!("list element not Int: {other:?}") }
+        }).collect(),
+        other => panic!("expected List for '{key}', got: {other:?}"),
+    }
+}
+
+fn list_vec2s(rt: &Runtime, key: &str) -> Vec<(f64, f64)> {
+    match rt.state().0.get(key) {
+        Some(Value::List(rc)) => rc.borrow().iter().map(|v| {
+            match v { Value::Vec2(x, y) => (*x, *y), other => panic!("list element not Vec2: {other:?}") }
+        }).collect(),
+        other => panic!("expected List for '{key}', got: {other:?}"),
+    }
+}
+
 // ─── Float arithmetic ─────────────────────────────────────────────────────────
 
 #[test]
@@ -129,6 +182,22 @@ fn float_div_by_zero_runtime_error() {
     "#);
 }
 
+#[test]
+fn float_pow() {
+    let rt = run(r#"
+        state {
+            let x: float = 2.0 ** 10.0
+            let y: float = 2 ** 3 ** 2
+            let z: float = -2 ** 2
+            let w: float = 4 ** -0.5 * 3
+        }
+    "#);
+    assert_eq!(f(&rt, "x"), 1024.0);
+    assert_eq!(f(&rt, "y"), 512.0);
+    assert_eq!(f(&rt, "z"), -4.0);
+    assert_eq!(f(&rt, "w"), 1.5);
+}
+
 #[test]
 fn float_mod_by_zero_runtime_error() {
     run_err(r#"
@@ -140,6 +209,84 @@ fn float_mod_by_zero_runtime_error() {
     "#);
 }
 
+// ─── Int arithmetic ───────────────────────────────────────────────────────────
+
+#[test]
+fn int_arithmetic_stays_int() {
+    let rt = run(r#"
+        state {
+            let sum = 2 + 3
+            let prod = 4 * -3
+            let rem = 17 % 5
+            let n: int = 7
+        }
+        fn on_init(s: State) -> State {
+            s.n++
+            s.n -= 2
+            return s
+        }
+    "#);
+    assert_eq!(i(&rt, "sum"), 5);
+    assert_eq!(i(&rt, "prod"), -12);
+    assert_eq!(i(&rt, "rem"), 2);
+    assert_eq!(i(&rt, "n"), 6);
+}
+
+#[test]
+fn int_promotes_to_float() {
+    let rt = run(r#"
+        state {
+            let mixed = 2 * 1.5
+            let half = 7 / 2
+            let wide: float = 3
+            let bigger = 3 > 2.5
+            let v = vec2(1, 2) * 2
+        }
+        fn on_init(s: State) -> State {
+            s.wide += 1
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "mixed"), 3.0);
+    assert_eq!(f(&rt, "half"), 3.5);
+    assert_eq!(f(&rt, "wide"), 4.0);
+    assert!(b(&rt, "bigger"));
+    assert_eq!(v2(&rt, "v"), (2.0, 4.0));
+}
+
+#[test]
+fn int_overflow_runtime_error() {
+    let e = run_err(r#"
+        state { let n: int = 9223372036854775807 }
+        fn on_init(s: State) -> State {
+            s.n += 1
+            return s
+        }
+    "#);
+    assert!(e.message.contains("overflow"), "{}", e.message);
+}
+
+#[test]
+fn int_index() {
+    let rt = run(r#"
+        state {
+            let xs: list[float] = [10.0, 20.0, 30.0]
+            let second: float = 0.0
+            let last: float = 0.0
+        }
+        fn on_init(s: State) -> State {
+            let k = 1
+            s.second = s.xs[k]
+            s.last = s.xs[-1]
+            s.xs[k + 1] = 5.0
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "second"), 20.0);
+    assert_eq!(f(&rt, "last"), 30.0);
+    assert_eq!(list_floats(&rt, "xs"), vec![10.0, 20.0, 5.0]);
+}
+
 // ─── Float comparisons ────────────────────────────────────────────────────────
 
 #[test]
@@ -204,6 +351,42 @@ fn bool_or_false() {
     assert!(!b(&rt, "r"));
 }
 
+#[test]
+fn bool_and_short_circuits() {
+    let rt = run(r#"
+        state { let r: bool = true }
+        fn on_init(s: State) -> State {
+            s.r = false and (1.0 / 0.0 > 0.0)
+            return s
+        }
+    "#);
+    assert!(!b(&rt, "r"));
+}
+
+#[test]
+fn bool_or_short_circuits() {
+    let rt = run(r#"
+        state { let r: bool = false }
+        fn on_init(s: State) -> State {
+            s.r = true or (1.0 / 0.0 > 0.0)
+            return s
+        }
+    "#);
+    assert!(b(&rt, "r"));
+}
+
+#[test]
+fn bool_and_evaluates_right_when_needed() {
+    let e = run_err(r#"
+        state { let r: bool = false }
+        fn on_init(s: State) -> State {
+            s.r = true and (1.0 / 0.0 > 0.0)
+            return s
+        }
+    "#);
+    assert!(e.message.contains("division by zero"));
+}
+
 #[test]
 fn bool_not_true() {
     let rt = run("state { let r: bool = not false }");
@@ -302,6 +485,238 @@ fn math_pi_constant() {
     assert!((f(&rt, "x") - std::f64::consts::PI).abs() < 1e-10);
 }
 
+#[test]
+fn math_step() {
+    let rt = run("state { let a: float = step(0.5, 0.7) let b: float = step(0.5, 0.3) }");
+    assert_eq!(f(&rt, "a"), 1.0);
+    assert_eq!(f(&rt, "b"), 0.0);
+}
+
+#[test]
+fn math_smoothstep() {
+    let rt = run("state { let x: float = smoothstep(0.0, 1.0, 0.5) let y: float = smoothstep(0.0, 1.0, 2.0) }");
+    assert_eq!(f(&rt, "x"), 0.5);
+    assert_eq!(f(&rt, "y"), 1.0);
+}
+
+#[test]
+fn math_mix() {
+    let rt = run("state { let x: float = mix(0.0, 10.0, 0.25) }");
+    assert_eq!(f(&rt, "x"), 2.5);
+}
+
+#[test]
+fn math_sign() {
+    let rt = run("state { let a: float = sign(-3.0) let b: float = sign(0.0) let c: float = sign(2.5) }");
+    assert_eq!(f(&rt, "a"), -1.0);
+    assert_eq!(f(&rt, "b"), 0.0);
+    assert_eq!(f(&rt, "c"), 1.0);
+}
+
+#[test]
+fn math_fract_floor_based() {
+    let rt = run("state { let a: float = fract(2.75) let b: float = fract(-0.25) }");
+    assert_eq!(f(&rt, "a"), 0.75);
+    assert_eq!(f(&rt, "b"), 0.75);
+}
+
+#[test]
+fn math_trunc() {
+    let rt = run("state { let a: float = trunc(2.7) let b: float = trunc(-2.7) }");
+    assert_eq!(f(&rt, "a"), 2.0);
+    assert_eq!(f(&rt, "b"), -2.0);
+}
+
+#[test]
+fn math_radians_degrees() {
+    let rt = run("state { let r: float = radians(180.0) let d: float = degrees(PI) let t: float = TAU }");
+    assert!((f(&rt, "r") - std::f64::consts::PI).abs() < 1e-10);
+    assert!((f(&rt, "d") - 180.0).abs() < 1e-10);
+    assert!((f(&rt, "t") - std::f64::consts::TAU).abs() < 1e-10);
+}
+
+#[test]
+fn math_remap() {
+    let rt = run("state { let x: float = remap(5.0, 0.0, 10.0, 0.0, 100.0) }");
+    assert_eq!(f(&rt, "x"), 50.0);
+}
+
+#[test]
+fn math_remap_empty_range_runtime_error() {
+    run_err(r#"
+        state { let x: float = 0.0 }
+        fn on_init(s: State) -> State {
+            s.x = remap(0.0, 0.0, 0.0, 1.0, 2.0)
+            return s
+        }
+    "#);
+}
+
+#[test]
+fn math_clamp01() {
+    let rt = run("state { let a: float = clamp01(1.5) let b: float = clamp01(-0.5) let c: float = clamp01(0.25) }");
+    assert_eq!(f(&rt, "a"), 1.0);
+    assert_eq!(f(&rt, "b"), 0.0);
+    assert_eq!(f(&rt, "c"), 0.25);
+}
+
+#[test]
+fn random_same_seed_same_sequence() {
+    let src = r#"
+        state { let a: float = 0.0 let b: float = 0.0 }
+        fn on_init(s: State) -> State {
+            seed(42.0)
+            s.a = random()
+            s.b = random()
+            return s
+        }
+    "#;
+    let (rt1, rt2) = (run(src), run(src));
+    assert_eq!(f(&rt1, "a"), f(&rt2, "a"));
+    assert_eq!(f(&rt1, "b"), f(&rt2, "b"));
+    assert_ne!(f(&rt1, "a"), f(&rt1, "b"));
+    assert!((0.0..1.0).contains(&f(&rt1, "a")));
+}
+
+#[test]
+fn random_continues_across_ticks() {
+    let mut rt = run(r#"
+        state { let x: float = 0.0 let prev: float = 0.0 }
+        fn on_init(s: State) -> State {
+            seed(7.0)
+            return s
+        }
+        fn on_update(s: State, input: Input) -> State {
+            s.prev = s.x
+            s.x = random()
+            return s
+        }
+    "#);
+    tick(&mut rt);
+    tick(&mut rt);
+    assert_ne!(f(&rt, "x"), f(&rt, "prev"));
+}
+
+#[test]
+fn frame_counts_ticks() {
+    let mut rt = run(r#"
+        state { let at_init: float = frame() let x: float = -1.0 }
+        fn on_update(s: State, input: Input) -> State {
+            s.x = frame()
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "at_init"), 0.0);
+    tick(&mut rt);
+    assert_eq!(f(&rt, "x"), 0.0);
+    tick(&mut rt);
+    tick(&mut rt);
+    assert_eq!(f(&rt, "x"), 2.0);
+}
+
+#[test]
+fn random_range_degenerate() {
+    let rt = run("state { let x: float = random_range(5.0, 5.0) }");
+    assert_eq!(f(&rt, "x"), 5.0);
+}
+
+#[test]
+fn noise2d_stable_across_ticks() {
+    let mut rt = run(r#"
+        state { let first: float = noise2d(vec2(0.0, 0.0)) let x: float = 0.0 }
+        fn on_update(s: State, input: Input) -> State {
+            s.x = noise2d(vec2(0.0, 0.0))
+            return s
+        }
+    "#);
+    tick(&mut rt);
+    let a = f(&rt, "x");
+    tick(&mut rt);
+    assert_eq!(f(&rt, "x"), a);
+    assert_eq!(f(&rt, "first"), a);
+}
+
+#[test]
+fn noise_is_continuous_and_bounded() {
+    let rt = run(r#"
+        state {
+            let a: float = noise2d(vec2(3.3, 1.7))
+            let b: float = noise2d(vec2(3.301, 1.7))
+            let c: float = noise(12.5)
+            let d: float = noise(12.501)
+        }
+    "#);
+    assert!((f(&rt, "a") - f(&rt, "b")).abs() < 1e-2);
+    assert!((f(&rt, "c") - f(&rt, "d")).abs() < 1e-2);
+    for key in ["a", "b", "c", "d"] {
+        assert!((-1.0..=1.0).contains(&f(&rt, key)));
+    }
+}
+
+#[test]
+fn mod_floor_wraps_negatives() {
+    let rt = run(r#"
+        state {
+            let wrapped: float = mod_floor(-1.0, 3.0)
+            let truncated: float = -1.0 % 3.0
+            let positive: float = mod_floor(7.5, 3.0)
+            let exact: float = mod_floor(-6.0, 3.0)
+            let negative_divisor: float = mod_floor(1.0, -3.0)
+        }
+    "#);
+    assert_eq!(f(&rt, "wrapped"), 2.0);
+    assert_eq!(f(&rt, "truncated"), -1.0);
+    assert_eq!(f(&rt, "positive"), 1.5);
+    assert_eq!(f(&rt, "exact"), 0.0);
+    assert_eq!(f(&rt, "negative_divisor"), -2.0);
+}
+
+#[test]
+fn mod_floor_by_zero_errors() {
+    let e = run_err(r#"
+        state { let m: float = 0.0 }
+        fn on_init(s: State) -> State {
+            s.m = mod_floor(1.0, 0.0)
+            return s
+        }
+    "#);
+    assert!(e.message.contains("mod by zero"), "{}", e.message);
+}
+
+#[test]
+fn wrap_angle_into_half_open_range() {
+    let rt = run("state { let w: float = wrap_angle(3.0 * PI)\n let n: float = wrap_angle(-PI) }");
+    assert!((f(&rt, "w") - std::f64::consts::PI).abs() < 1e-9);
+    assert!((f(&rt, "n") - std::f64::consts::PI).abs() < 1e-9);
+}
+
+#[test]
+fn lerp_angle_takes_short_way() {
+    let rt = run("state { let m: float = lerp_angle(3.0, -3.0, 0.5) }");
+    // Halfway across the ±PI seam, not through 0.
+    assert!((f(&rt, "m").abs() - std::f64::consts::PI).abs() < 1e-9);
+}
+
+#[test]
+fn float_methods_mirror_core_functions() {
+    let rt = run(r#"
+        state {
+            let r: float = 0.0
+            let a: float = 0.0
+            let h: float = 0.0
+        }
+        fn on_init(s: State) -> State {
+            s.r = 9.0.sqrt()
+            s.a = (-2.0).abs()
+            s.h = (3.0 * 3.0 + 4.0 * 4.0).sqrt().floor()
+            return s
+        }
+    "#);
+    assert!((f(&rt, "r") - 3.0).abs() < 1e-12);
+    assert_eq!(f(&rt, "a"), 2.0);
+    assert_eq!(f(&rt, "h"), 5.0);
+}
+
 // ─── Variables ────────────────────────────────────────────────────────────────
 
 #[test]
@@ -342,6 +757,31 @@ fn var_local_scope_in_init() {
     assert_eq!(f(&rt, "x"), 42.0);
 }
 
+#[test]
+fn const_referencing_const_is_folded() {
+    use rustle_lang::namespaces::NamespaceRegistry;
+    use rustle_lang::optimize::fold_constants;
+    use rustle_lang::syntax::{lexer::Lexer, parser::Parser};
+
+    let src = r#"
+        const HALF = 0.5
+        const TURN = HALF * TAU
+        const BIG = -TURN > 1.0 ? 10.0 : 20.0
+        const S = sin(HALF)
+        state { let turn: float = TURN let big: float = BIG let s: float = S }
+    "#;
+    let ast = Parser::new(Lexer::new(src).tokenize().unwrap()).parse().unwrap();
+    let folded = fold_constants(&ast, &NamespaceRegistry::standard());
+    assert!(matches!(folded.get("TURN"), Some(Value::Float(x)) if *x == std::f64::consts::PI));
+    assert!(matches!(folded.get("BIG"), Some(Value::Float(x)) if *x == 20.0));
+    assert!(!folded.contains_key("S"), "calls are not folded");
+
+    let rt = run(src);
+    assert_eq!(f(&rt, "turn"), std::f64::consts::PI);
+    assert_eq!(f(&rt, "big"), 20.0);
+    assert_eq!(f(&rt, "s"), 0.5f64.sin());
+}
+
 // ─── Control flow ─────────────────────────────────────────────────────────────
 
 #[test]
@@ -406,6 +846,40 @@ fn compound_assignment() {
     assert_eq!(f(&rt, "x"), 30.0);
 }
 
+#[test]
+fn compound_assignment_all_operators() {
+    let rt = run(r#"
+        state { let x: float = 10.0  let v: vec2 = vec2(1.0, 2.0) }
+        fn on_init(s: State) -> State {
+            s.x -= 1.0
+            s.x /= 2.0
+            s.x %= 3.0
+            s.v *= 3.0
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "x"), 1.5);
+    assert_eq!(v2(&rt, "v"), (3.0, 6.0));
+}
+
+#[test]
+fn compound_assignment_evaluates_index_once() {
+    let rt = run(r#"
+        state { let calls: float = 0.0  let xs: list[float] = [1.0, 2.0, 3.0] }
+        fn next(s: State) -> float {
+            s.calls += 1.0
+            return s.calls
+        }
+        fn on_init(s: State) -> State {
+            s.xs[next(s)] += 10.0
+            s.xs[next(s)]++
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "calls"), 2.0);
+    assert_eq!(list_floats(&rt, "xs"), [1.0, 12.0, 4.0]);
+}
+
 #[test]
 fn match_float_with_else() {
     let rt = run(r#"
@@ -454,18 +928,68 @@ fn match_multi_value_arm() {
 }
 
 #[test]
-fn inc_dec_prefix_postfix() {
+fn match_expression_assigns_arm_value() {
     let rt = run(r#"
-        state { let x: float = 5.0 }
+        state { let x: float = 0.0  let y: float = 0.0 }
         fn on_init(s: State) -> State {
-            let a = s.x++   // a = 5, s.x = 6
-            let b = ++s.x   // b = 7, s.x = 7
-            s.x--           // s.x = 6
-            let c = --s.x   // c = 5, s.x = 5
+            let n = 2.0
+            s.x = match n { 1.0 => 10.0, 2.0, 3.0 => 23.0, else => 99.0 }
+            s.y = match n { 1.0 => 10.0, else => 99.0 }
             return s
         }
     "#);
-    assert_eq!(f(&rt, "x"), 5.0);
+    assert_eq!(f(&rt, "x"), 23.0);
+    assert_eq!(f(&rt, "y"), 99.0);
+}
+
+#[test]
+fn match_expression_no_match_no_else_runtime_error() {
+    run_err(r#"
+        state { let x: float = 0.0 }
+        fn on_init(s: State) -> State {
+            s.x = match 5.0 { 1.0 => 10.0, 2.0 => 20.0 }
+            return s
+        }
+    "#);
+}
+
+#[test]
+fn inc_dec_prefix_postfix() {
+    let rt = run(r#"
+        state { let x: float = 5.0 }
+        fn on_init(s: State) -> State {
+            let a = s.x++   // a = 5, s.x = 6
+            let b = ++s.x   // b = 7, s.x = 7
+            s.x--           // s.x = 6
+            let c = --s.x   // c = 5, s.x = 5
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "x"), 5.0);
+}
+
+#[test]
+fn inc_dec_yields_old_or_new_value() {
+    let rt = run(r#"
+        state {
+            let x: float = 5.0
+            let a: float = 0.0  let b: float = 0.0  let c: float = 0.0
+            let xs: list[float] = [1.0]
+            let d: float = 0.0  let e: float = 0.0
+        }
+        fn on_init(s: State) -> State {
+            s.a = s.x++
+            s.b = ++s.x
+            s.c = --s.x
+            s.d = s.xs[0]--
+            s.e = ++s.xs[0]
+            return s
+        }
+    "#);
+    assert_eq!((f(&rt, "a"), f(&rt, "b"), f(&rt, "c")), (5.0, 7.0, 6.0));
+    assert_eq!(f(&rt, "x"), 6.0);
+    assert_eq!((f(&rt, "d"), f(&rt, "e")), (1.0, 1.0));
+    assert_eq!(list_floats(&rt, "xs"), [1.0]);
 }
 
 #[test]
@@ -549,6 +1073,89 @@ fn foreach_iterates_all_elements() {
     assert_eq!(f(&rt, "sum"), 10.0);
 }
 
+#[test]
+fn foreach_with_index() {
+    let rt = run(r#"
+        state { let sum: float = 0.0 let weighted: float = 0.0 }
+        fn on_init(s: State) -> State {
+            let xs: list[float] = [10.0, 20.0, 30.0, 40.0]
+            foreach i, v in xs {
+                s.sum = s.sum + i
+                s.weighted = s.weighted + i * v
+            }
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "sum"), 6.0); // 0+1+2+3
+    assert_eq!(f(&rt, "weighted"), 200.0);
+}
+
+#[test]
+fn for_in_range() {
+    let rt = run(r#"
+        state { let n: float = 0.0 let sum: float = 0.0 }
+        fn on_init(s: State) -> State {
+            for i in 0..5 {
+                s.n = s.n + 1.0
+                s.sum = s.sum + i
+            }
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "n"), 5.0);
+    assert_eq!(f(&rt, "sum"), 10.0);
+}
+
+#[test]
+fn for_in_range_with_step() {
+    let rt = run(r#"
+        state { let n: float = 0.0 let last: float = 0.0 }
+        fn on_init(s: State) -> State {
+            for i in 0..10 step 2 {
+                s.n = s.n + 1.0
+                s.last = i
+            }
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "n"), 5.0);
+    assert_eq!(f(&rt, "last"), 8.0);
+}
+
+#[test]
+fn range_zero_step_runtime_error() {
+    run_err(r#"
+        state { let n: float = 0.0 }
+        fn on_init(s: State) -> State {
+            for i in 0..10 step 0 { s.n = i }
+            return s
+        }
+    "#);
+}
+
+#[test]
+fn huge_range_loop_is_lazy() {
+    let prog = compile(r#"
+        fn on_update(s: State, input: Input) -> State {
+            for i in 0..1000000000000 { }
+            return s
+        }
+    "#).expect("compile failed");
+    let mut rt = Runtime::with_iteration_limit(prog, 1000).expect("init failed");
+    assert!(tick_err(&mut rt).message.contains("iteration limit exceeded"));
+}
+
+#[test]
+fn huge_range_list_runtime_error() {
+    let e = run_err(r#"
+        fn on_init(s: State) -> State {
+            let xs = 0..1000000000000
+            return s
+        }
+    "#);
+    assert!(e.message.contains("range has"), "{}", e.message);
+}
+
 #[test]
 fn nested_if_in_for() {
     let rt = run(r#"
@@ -603,6 +1210,28 @@ fn ternary_false_branch() {
     assert_eq!(f(&rt, "x"), 20.0);
 }
 
+#[test]
+fn mixed_int_float_branches_widen_the_chosen_int() {
+    let rt = run(r#"
+        const K = false ? 0.5 : 16
+        state {
+            let x: float = 0.0
+            let y: float = 0.0
+            let z: float = K.sqrt()
+        }
+        fn on_init(s: State) -> State {
+            let r = true ? 4 : 2.5
+            s.x = r.sqrt()
+            let m = match 1 { 1 => 9, else => 0.5 }
+            s.y = m.sqrt()
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "x"), 2.0);
+    assert_eq!(f(&rt, "y"), 3.0);
+    assert_eq!(f(&rt, "z"), 4.0);
+}
+
 // ─── Functions ────────────────────────────────────────────────────────────────
 
 #[test]
@@ -614,6 +1243,39 @@ fn fn_basic_call() {
     assert_eq!(f(&rt, "x"), 7.0);
 }
 
+#[test]
+fn fn_default_params() {
+    let rt = run(r#"
+        fn grow(x: float, by: float = 1.0, scale: float = by * 2.0) -> float {
+            return (x + by) * scale
+        }
+        state {
+            let none: float = grow(1.0)
+            let one: float = grow(1.0, 3.0)
+            let both: float = grow(1.0, 3.0, 1.0)
+        }
+    "#);
+    assert_eq!(f(&rt, "none"), 4.0);
+    // `scale` defaults from the `by` actually passed.
+    assert_eq!(f(&rt, "one"), 24.0);
+    assert_eq!(f(&rt, "both"), 4.0);
+}
+
+#[test]
+fn fn_named_args_any_order() {
+    let rt = run(r#"
+        fn weigh(a: float, b: float, c: float = 100.0) -> float {
+            return a + b * 10.0 + c
+        }
+        state {
+            let named: float = weigh(b: 2.0, a: 1.0)
+            let mixed: float = weigh(1.0, c: 0.0, b: 3.0)
+        }
+    "#);
+    assert_eq!(f(&rt, "named"), 121.0);
+    assert_eq!(f(&rt, "mixed"), 31.0);
+}
+
 #[test]
 fn fn_called_in_init() {
     let rt = run(r#"
@@ -663,6 +1325,67 @@ fn fn_lambda() {
     assert_eq!(f(&rt, "x"), 12.0);
 }
 
+#[test]
+fn fn_unbounded_recursion_is_runtime_error() {
+    let e = run_err(r#"
+        fn f(x: float) -> float { return f(x + 1.0) }
+        let y = f(0.0)
+    "#);
+    assert!(e.message.contains("call depth limit exceeded"), "{}", e.message);
+    assert_eq!(e.line, 2);
+}
+
+#[test]
+fn fn_int_return_widens_to_declared_float() {
+    let rt = run(r#"
+        fn g() -> float { return 9 }
+        state {
+            let x: float = 0.0
+            let y: float = 0.0
+        }
+        fn on_init(s: State) -> State {
+            s.x = g().sqrt()
+            let h = () -> float { return 16 }
+            s.y = h().sqrt()
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "x"), 3.0);
+    assert_eq!(f(&rt, "y"), 4.0);
+}
+
+#[test]
+fn fn_bounded_recursion_ok() {
+    let rt = run(r#"
+        fn total(n: float) -> float {
+            if n <= 0.0 { return 0.0 }
+            return n + total(n - 1.0)
+        }
+        state { let x: float = 0.0 }
+        fn on_init(s: State) -> State {
+            s.x = total(10.0)
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "x"), 55.0);
+}
+
+#[test]
+fn fn_deep_recursion_fits_default_stack() {
+    let rt = run(r#"
+        fn total(n: float) -> float {
+            if n <= 0.0 { return 0.0 }
+            return n + total(n - 1.0)
+        }
+        state { let x: float = 0.0 }
+        fn on_init(s: State) -> State {
+            s.x = total(1900.0)
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "x"), 1900.0 * 1901.0 / 2.0);
+}
+
 // ─── Vec2 ─────────────────────────────────────────────────────────────────────
 
 #[test]
@@ -757,479 +1480,2271 @@ fn vec2_normalize_zero_vector_error() {
 }
 
 #[test]
-fn vec2_eq() {
+fn vec2_angle_between() {
     let rt = run(r#"
-        state { let r: bool = vec2(1.0, 2.0) == vec2(1.0, 2.0) }
+        state {
+            let ccw: float = vec2(1.0, 0.0).angle_between(vec2(0.0, 1.0))
+            let cw: float = vec2(0.0, 1.0).angle_between(vec2(1.0, 0.0))
+            let scaled: float = vec2(2.0, 0.0).angle_between(vec2(-3.0, 0.0))
+        }
     "#);
-    assert!(b(&rt, "r"));
+    let half_pi = std::f64::consts::FRAC_PI_2;
+    assert!((f(&rt, "ccw") - half_pi).abs() < 1e-12);
+    assert!((f(&rt, "cw") + half_pi).abs() < 1e-12);
+    assert!((f(&rt, "scaled") - std::f64::consts::PI).abs() < 1e-12);
 }
 
 #[test]
-fn vec2_neq() {
-    let rt = run(r#"
-        state { let r: bool = vec2(1.0, 2.0) != vec2(3.0, 4.0) }
+fn vec2_angle_between_zero_vector_error() {
+    let e = run_err(r#"
+        state { let a: float = 0.0 }
+        fn on_init(s: State) -> State {
+            s.a = vec2(1.0, 0.0).angle_between(vec2(0.0, 0.0))
+            return s
+        }
     "#);
-    assert!(b(&rt, "r"));
+    assert_eq!(e.line, 4);
+    assert!(e.message.contains("zero vector"), "{}", e.message);
 }
 
-// ─── Lists ────────────────────────────────────────────────────────────────────
-
 #[test]
-fn list_push_increases_len() {
+fn vec2_rotate_quarter_turn() {
     let rt = run(r#"
-        state { let xs: list[float] = [] }
+        state { let v: vec2 = vec2(0.0, 0.0) }
         fn on_init(s: State) -> State {
-            s.xs.push(1.0)
-            s.xs.push(2.0)
-            s.xs.push(3.0)
+            s.v = vec2(1.0, 0.0).rotate(PI / 2.0)
             return s
         }
     "#);
-    assert_eq!(list_floats(&rt, "xs"), vec![1.0, 2.0, 3.0]);
+    let (x, y) = v2(&rt, "v");
+    assert!(x.abs() < 1e-10);
+    assert!((y - 1.0).abs() < 1e-10);
 }
 
 #[test]
-fn list_pop_removes_last() {
+fn vec2_rotate_around_pivot() {
     let rt = run(r#"
-        state {
-            let xs: list[float] = []
-            let last: float = 0.0
-        }
+        state { let v: vec2 = vec2(0.0, 0.0) }
         fn on_init(s: State) -> State {
-            s.xs.push(10.0)
-            s.xs.push(20.0)
-            s.xs.push(30.0)
-            s.last = s.xs.pop()
+            s.v = vec2(2.0, 1.0).rotate_around(vec2(1.0, 1.0), PI)
             return s
         }
     "#);
-    assert_eq!(f(&rt, "last"), 30.0);
-    assert_eq!(list_floats(&rt, "xs"), vec![10.0, 20.0]);
+    let (x, y) = v2(&rt, "v");
+    assert!(x.abs() < 1e-10);
+    assert!((y - 1.0).abs() < 1e-10);
 }
 
 #[test]
-fn list_pop_empty_runtime_error() {
-    run_err(r#"
-        state { let x: float = 0.0 }
+fn vec2_reflect() {
+    let rt = run(r#"
+        state { let v: vec2 = vec2(0.0, 0.0) }
         fn on_init(s: State) -> State {
-            let empty: list[float] = []
-            s.x = empty.pop()
+            s.v = vec2(1.0, -1.0).reflect(vec2(0.0, 1.0))
             return s
         }
     "#);
+    let (x, y) = v2(&rt, "v");
+    assert!((x - 1.0).abs() < 1e-10);
+    assert!((y - 1.0).abs() < 1e-10);
 }
 
 #[test]
-fn list_index_assignment() {
+fn vec2_toward() {
     let rt = run(r#"
-        state { let x: float = 0.0 }
-        fn on_init(s: State) -> State {
-            let xs: list[float] = [10.0, 20.0, 30.0]
-            xs[1] = 99.0
-            s.x = xs[1]
-            return s
+        state {
+            let step: vec2 = vec2(0.0, 0.0).toward(vec2(10.0, 0.0), 3.0)
+            let snap: vec2 = vec2(0.1, 0.2).toward(vec2(0.3, 0.4), 5.0)
+            let diag: vec2 = vec2(1.0, 1.0).toward(vec2(4.0, 5.0), 2.5)
+            let still: vec2 = vec2(1.0, 1.0).toward(vec2(4.0, 5.0), -1.0)
         }
     "#);
-    assert_eq!(f(&rt, "x"), 99.0);
+    assert_eq!(v2(&rt, "step"), (3.0, 0.0));
+    assert_eq!(v2(&rt, "snap"), (0.3, 0.4));
+    assert_eq!(v2(&rt, "diag"), (2.5, 3.0));
+    assert_eq!(v2(&rt, "still"), (1.0, 1.0));
 }
 
 #[test]
-fn list_index_compound_assignment() {
+fn vec_clamp_componentwise() {
     let rt = run(r#"
-        state { let x: float = 0.0 }
-        fn on_init(s: State) -> State {
-            let xs: list[float] = [10.0, 20.0, 30.0]
-            xs[1] += 5.0
-            s.x = xs[1]
-            return s
+        state {
+            let p: vec2 = vec2(5.0, -5.0).clamp(vec2(0.0, 0.0), vec2(1.0, 1.0))
+            let q: vec3 = vec3(-1.0, 0.5, 2.0).clamp(vec3(0.0, 0.0, 0.0), vec3(1.0, 1.0, 1.0))
         }
     "#);
-    assert_eq!(f(&rt, "x"), 25.0);
+    assert_eq!(v2(&rt, "p"), (1.0, 0.0));
+    assert_eq!(v3(&rt, "q"), (0.0, 0.5, 1.0));
 }
 
 #[test]
-fn list_index_access() {
-    let rt = run(r#"
-        state { let x: float = 0.0 }
+fn vec_clamp_inverted_bounds_runtime_error() {
+    let e = run_err(r#"
+        state { let p: vec2 = vec2(0.0, 0.0) }
         fn on_init(s: State) -> State {
-            let xs: list[float] = [10.0, 20.0, 30.0]
-            s.x = xs[1]
+            s.p = vec2(0.5, 0.5).clamp(vec2(0.0, 1.0), vec2(1.0, 0.0))
             return s
         }
     "#);
-    assert_eq!(f(&rt, "x"), 20.0);
+    assert!(e.message.contains("`lo.y` <= `hi.y`"), "{}", e.message);
 }
 
 #[test]
-fn list_len_field() {
+fn vec2_eq() {
     let rt = run(r#"
-        state { let n: float = 0.0 }
-        fn on_init(s: State) -> State {
-            let xs: list[float] = [1.0, 2.0, 3.0]
-            s.n = xs.len
-            return s
-        }
+        state { let r: bool = vec2(1.0, 2.0) == vec2(1.0, 2.0) }
     "#);
-    assert_eq!(f(&rt, "n"), 3.0);
+    assert!(b(&rt, "r"));
 }
 
 #[test]
-fn list_len_method() {
+fn vec2_neq() {
     let rt = run(r#"
-        state { let n: float = 0.0 }
-        fn on_init(s: State) -> State {
-            let xs: list[float] = [1.0, 2.0]
-            s.n = xs.len()
-            return s
-        }
+        state { let r: bool = vec2(1.0, 2.0) != vec2(3.0, 4.0) }
     "#);
-    assert_eq!(f(&rt, "n"), 2.0);
+    assert!(b(&rt, "r"));
 }
 
 #[test]
-fn list_foreach_sum() {
+fn polar_round_trip() {
     let rt = run(r#"
-        state { let sum: float = 0.0 }
-        fn on_init(s: State) -> State {
-            let xs: list[float] = [1.0, 2.0, 3.0, 4.0, 5.0]
-            foreach v in xs { s.sum = s.sum + v }
-            return s
+        state {
+            let p: vec2 = polar(1.0, 0.0)
+            let q: vec2 = to_polar(vec2(0.0, 1.0))
+            let back: vec2 = to_polar(polar(2.0, 1.0))
         }
     "#);
-    assert_eq!(f(&rt, "sum"), 15.0);
+    let (px, py) = v2(&rt, "p");
+    assert!((px - 1.0).abs() < 1e-12 && py.abs() < 1e-12);
+    let (r, theta) = v2(&rt, "q");
+    assert!((r - 1.0).abs() < 1e-12);
+    assert!((theta - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+    let (r, theta) = v2(&rt, "back");
+    assert!((r - 2.0).abs() < 1e-12 && (theta - 1.0).abs() < 1e-12);
 }
 
 #[test]
-fn list_mutation_is_shared() {
-    // Pushing to a list stored in state mutates in-place.
+fn grid_positions_are_centered() {
     let rt = run(r#"
-        state { let xs: list[float] = [] }
-        fn on_init(s: State) -> State {
-            for let i = 1.0; i <= 5.0; i = i + 1.0 {
-                s.xs.push(i)
-            }
-            return s
-        }
+        state { let pts: list[vec2] = grid(2.0, 2.0, vec2(1.0, 1.0)) }
     "#);
-    assert_eq!(list_floats(&rt, "xs"), vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+    let pts: Vec<(f64, f64)> = match rt.state().0.get("pts") {
+        Some(Value::List(rc)) => rc.borrow().iter().map(|v| match v {
+            Value::Vec2(x, y) => (*x, *y),
+            other => panic!("list element not Vec2: {other:?}"),
+        }).collect(),
+        other => panic!("expected List for 'pts', got: {other:?}"),
+    };
+    assert_eq!(pts, vec![(-0.5, -0.5), (0.5, -0.5), (-0.5, 0.5), (0.5, 0.5)]);
 }
 
 #[test]
-fn list_literal_initial_values() {
-    let rt = run("state { let xs: list[float] = [10.0, 20.0, 30.0] }");
-    assert_eq!(list_floats(&rt, "xs"), vec![10.0, 20.0, 30.0]);
+fn grid_rejects_non_finite_and_oversized_counts() {
+    for counts in ["pow(10.0, 400.0), 2.0", "2.0, (0.0 - 1.0).sqrt()", "100000.0, 100000.0"] {
+        let e = run_err(&format!(r#"
+            state {{ let n: float = 0.0 }}
+            fn on_init(s: State) -> State {{
+                s.n = grid({counts}, vec2(1.0, 1.0)).len
+                return s
+            }}
+        "#));
+        assert!(e.message.contains("`grid`"), "{}", e.message);
+    }
 }
 
-// ─── State lifecycle ──────────────────────────────────────────────────────────
+// ─── Vec3 / Vec4 ──────────────────────────────────────────────────────────────
 
 #[test]
-fn state_initializers_run() {
+fn vec3_floor_ceil() {
     let rt = run(r#"
         state {
-            let a: float = 2.0 + 3.0
-            let b: bool  = 10.0 > 5.0
+            let lo: vec3 = vec3(1.7, 2.2, -0.5).floor()
+            let hi: vec3 = vec3(1.7, 2.2, -0.5).ceil()
+        }
+    "#);
+    assert_eq!(v3(&rt, "lo"), (1.0, 2.0, -1.0));
+    assert_eq!(v3(&rt, "hi"), (2.0, 3.0, 0.0));
+}
+
+#[test]
+fn vec4_floor_ceil() {
+    let rt = run(r#"
+        state {
+            let lo: vec4 = vec4(1.7, 2.2, -0.5, 3.0).floor()
+            let hi: vec4 = vec4(1.7, 2.2, -0.5, 3.0).ceil()
+        }
+    "#);
+    assert_eq!(v4(&rt, "lo"), (1.0, 2.0, -1.0, 3.0));
+    assert_eq!(v4(&rt, "hi"), (2.0, 3.0, 0.0, 3.0));
+}
+
+#[test]
+fn vec3_vec4_distance() {
+    let rt = run(r#"
+        state {
+            let a: float = vec3(0.0, 0.0, 0.0).distance(vec3(0.0, 0.0, 5.0))
+            let b: float = vec4(1.0, 1.0, 1.0, 1.0).distance(vec4(2.0, 2.0, 2.0, 2.0))
         }
     "#);
     assert_eq!(f(&rt, "a"), 5.0);
-    assert!(b(&rt, "b"));
+    assert_eq!(f(&rt, "b"), 2.0);
 }
 
 #[test]
-fn init_runs_before_first_tick() {
+fn vector_swizzles() {
     let rt = run(r#"
-        state { let x: float = 0.0 }
-        fn on_init(s: State) -> State {
-            s.x = 99.0
-            return s
+        state {
+            let a: vec2 = vec3(1.0, 2.0, 3.0).xy
+            let b: vec2 = vec2(1.0, 2.0).yx
+            let c: vec3 = vec4(1.0, 2.0, 3.0, 4.0).wzx
+            let d: vec4 = vec2(5.0, 6.0).xxyy
         }
     "#);
-    assert_eq!(f(&rt, "x"), 99.0);
+    assert_eq!(v2(&rt, "a"), (1.0, 2.0));
+    assert_eq!(v2(&rt, "b"), (2.0, 1.0));
+    assert_eq!(v3(&rt, "c"), (4.0, 3.0, 1.0));
+    assert_eq!(v4(&rt, "d"), (5.0, 5.0, 6.0, 6.0));
 }
 
+// ─── Matrices ─────────────────────────────────────────────────────────────────
+
 #[test]
-fn update_accumulates_over_ticks() {
-    let mut rt = run(r#"
-        state { let t: float = 0.0 }
-        fn on_update(s: State, input: Input) -> State {
-            s.t = s.t + 1.0
-            return s
+fn mat_identity_products_are_noops() {
+    let rt = run(r#"
+        state {
+            let a: vec3 = (mat3() * mat3_rotate(30.0)) * vec3(1.0, 2.0, 1.0)
+            let b: vec3 = mat3_rotate(30.0) * vec3(1.0, 2.0, 1.0)
+            let c: vec3 = mat3() * vec3(1.0, 2.0, 3.0)
+            let d: vec4 = (mat4_translate(1.0, 2.0, 3.0) * mat4()) * vec4(1.0, 1.0, 1.0, 1.0)
+            let e: vec4 = mat4() * vec4(1.0, 2.0, 3.0, 4.0)
         }
     "#);
-    tick(&mut rt);
-    tick(&mut rt);
-    tick(&mut rt);
-    assert_eq!(f(&rt, "t"), 3.0);
+    assert_eq!(v3(&rt, "a"), v3(&rt, "b"));
+    assert_eq!(v3(&rt, "c"), (1.0, 2.0, 3.0));
+    assert_eq!(v4(&rt, "d"), (2.0, 3.0, 4.0, 1.0));
+    assert_eq!(v4(&rt, "e"), (1.0, 2.0, 3.0, 4.0));
 }
 
 #[test]
-fn update_uses_input_dt() {
-    let mut rt = run(r#"
-        state { let t: float = 0.0 }
-        fn on_update(s: State, input: Input) -> State {
-            s.t = s.t + input.dt
-            return s
+fn mat_times_vec_matches_mul_vec() {
+    let rt = run(r#"
+        state {
+            let a: vec3 = mat3_rotate(90.0) * vec3(1.0, 0.0, 1.0)
+            let b: vec3 = mat3_rotate(90.0).mul_vec(vec3(1.0, 0.0, 1.0))
+            let c: vec4 = mat4_rotate_z(45.0) * vec4(1.0, 0.0, 0.0, 1.0)
+            let d: vec4 = mat4_rotate_z(45.0).mul_vec(vec4(1.0, 0.0, 0.0, 1.0))
         }
     "#);
-    tick(&mut rt);
-    // dt is 0.016 per tick
-    assert!((f(&rt, "t") - 0.016).abs() < 1e-10);
+    assert_eq!(v3(&rt, "a"), v3(&rt, "b"));
+    let (x, y, _) = v3(&rt, "a");
+    assert!(x.abs() < 1e-10 && (y - 1.0).abs() < 1e-10);
+    assert_eq!(v4(&rt, "c"), v4(&rt, "d"));
 }
 
 #[test]
-fn init_and_update_together() {
-    let mut rt = run(r#"
-        state { let x: float = 0.0 }
+fn mat_vector_builders() {
+    let rt = run(r#"
+        state {
+            let r: vec3 = mat3_rotation(PI / 2.0).mul_vec(vec3(1.0, 0.0, 1.0))
+            let t: vec3 = mat3_translation(vec2(2.0, 3.0)) * vec3(1.0, 1.0, 1.0)
+            let s: vec3 = mat3_scaling(vec2(2.0, 3.0)) * vec3(1.0, 1.0, 1.0)
+            let r4: vec4 = mat4_rotation(PI / 2.0) * vec4(1.0, 0.0, 0.0, 1.0)
+            let t4: vec4 = mat4_translation(vec3(1.0, 2.0, 3.0)) * vec4(0.0, 0.0, 0.0, 1.0)
+            let s4: vec4 = mat4_scaling(vec3(1.0, 2.0, 3.0)) * vec4(1.0, 1.0, 1.0, 1.0)
+        }
+    "#);
+    let (x, y, z) = v3(&rt, "r");
+    assert!(x.abs() < 1e-10 && (y - 1.0).abs() < 1e-10 && (z - 1.0).abs() < 1e-10);
+    assert_eq!(v3(&rt, "t"), (3.0, 4.0, 1.0));
+    assert_eq!(v3(&rt, "s"), (2.0, 3.0, 1.0));
+    let (x, y, _, _) = v4(&rt, "r4");
+    assert!(x.abs() < 1e-10 && (y - 1.0).abs() < 1e-10);
+    assert_eq!(v4(&rt, "t4"), (1.0, 2.0, 3.0, 1.0));
+    assert_eq!(v4(&rt, "s4"), (1.0, 2.0, 3.0, 1.0));
+}
+
+// ─── Color ────────────────────────────────────────────────────────────────────
+
+#[test]
+fn color_hsv_constructor_red() {
+    let rt = run("state { let c: color = hsv(0.0, 1.0, 1.0) }");
+    let (r, g, b, a) = rgba(&rt, "c");
+    assert!((r - 1.0).abs() < 1e-10);
+    assert!(g.abs() < 1e-10);
+    assert!(b.abs() < 1e-10);
+    assert_eq!(a, 1.0);
+}
+
+#[test]
+fn color_to_hsv_roundtrip() {
+    let rt = run(r#"
+        state {
+            let red_h: float = color(1.0, 0.0, 0.0, 1.0).to_hsv().x
+            let back: vec3 = hsv(210.0, 0.5, 0.8).to_hsv()
+        }
+    "#);
+    assert!(f(&rt, "red_h").abs() < 1e-10);
+    let (h, s, v) = v3(&rt, "back");
+    assert!((h - 210.0).abs() < 1e-9);
+    assert!((s - 0.5).abs() < 1e-9);
+    assert!((v - 0.8).abs() < 1e-9);
+}
+
+#[test]
+fn color_darken_lighten() {
+    let rt = run(r#"
+        state {
+            let d: color = color(0.5, 0.5, 0.5, 1.0).darken(0.5)
+            let l: color = color(0.5, 0.5, 0.5, 1.0).lighten(0.5)
+            let over: color = color(0.5, 0.5, 0.5, 1.0).darken(2.0)
+        }
+    "#);
+    assert_eq!(rgba(&rt, "d"), (0.25, 0.25, 0.25, 1.0));
+    assert_eq!(rgba(&rt, "l"), (0.75, 0.75, 0.75, 1.0));
+    assert_eq!(rgba(&rt, "over"), (0.0, 0.0, 0.0, 1.0));
+}
+
+#[test]
+fn color_grayscale_preserves_alpha() {
+    let rt = run("state { let c: color = color(1.0, 0.5, 0.25, 0.4).grayscale() }");
+    let (r, g, b, a) = rgba(&rt, "c");
+    assert_eq!(r, g);
+    assert_eq!(g, b);
+    assert!((r - (0.299 + 0.587 * 0.5 + 0.114 * 0.25)).abs() < 1e-10);
+    assert_eq!(a, 0.4);
+}
+
+#[test]
+fn color_blend_modes() {
+    let rt = run(r#"
+        state {
+            let m: color = white.multiply(color(0.25, 0.5, 0.75, 1.0))
+            let s: color = black.screen(color(0.25, 0.5, 0.75, 1.0))
+            let o: color = color(0.25, 0.75, 0.5, 0.3).overlay(color(0.5, 0.5, 0.5, 1.0))
+        }
+    "#);
+    assert_eq!(rgba(&rt, "m"), (0.25, 0.5, 0.75, 1.0));
+    assert_eq!(rgba(&rt, "s"), (0.25, 0.5, 0.75, 1.0));
+    // Dark channels multiply, light ones screen; alpha comes from the base.
+    assert_eq!(rgba(&rt, "o"), (0.25, 0.75, 0.5, 0.3));
+}
+
+// ─── Strings ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn string_interpolation() {
+    let rt = run(r#"
+        state {
+            let a: string = "v={1.0+1.0}"
+            let b: string = "{0.1 + 0.2}|{7 / 2}|{-0.0000001}"
+            let c: string = "\{not} {vec2(1.0, 2.0)} {true}"
+            let d: string = ""
+        }
         fn on_init(s: State) -> State {
-            s.x = 10.0
+            let m = { "k": 3 }
+            s.d = "k={m["k"] * 2}"
             return s
         }
-        fn on_update(s: State, input: Input) -> State {
-            s.x = s.x + 1.0
+    "#);
+    assert_eq!(str_val(&rt, "a"), "v=2");
+    assert_eq!(str_val(&rt, "b"), "0.3|3.5|0");
+    assert_eq!(str_val(&rt, "c"), "{not} vec2(1, 2) true");
+    assert_eq!(str_val(&rt, "d"), "k=6");
+}
+
+#[test]
+fn string_upper_lower() {
+    let rt = run(r#"
+        state { let u: string = "" let l: string = "" }
+        fn on_init(s: State) -> State {
+            s.u = "Hi there".upper()
+            s.l = "Hi There".lower()
             return s
         }
     "#);
-    assert_eq!(f(&rt, "x"), 10.0);
-    tick(&mut rt);
-    assert_eq!(f(&rt, "x"), 11.0);
-    tick(&mut rt);
-    assert_eq!(f(&rt, "x"), 12.0);
+    assert_eq!(str_val(&rt, "u"), "HI THERE");
+    assert_eq!(str_val(&rt, "l"), "hi there");
 }
 
 #[test]
-fn on_exit_runs_when_exit_called() {
-    let mut rt = run(r#"
-        state { let x: float = 0.0 }
-        fn on_update(s: State, input: Input) -> State {
-            s.x = s.x + 1.0
-            return s
+fn string_contains_starts_with() {
+    let rt = run(r#"
+        state {
+            let c: bool = false
+            let nc: bool = true
+            let sw: bool = false
+            let nsw: bool = true
         }
-        fn on_exit(s: State) -> State {
-            s.x = 999.0
+        fn on_init(s: State) -> State {
+            s.c = "rustle".contains("stl")
+            s.nc = "rustle".contains("xyz")
+            s.sw = "rustle".starts_with("rus")
+            s.nsw = "rustle".starts_with("tle")
             return s
         }
     "#);
-    tick(&mut rt);
-    tick(&mut rt);
-    assert_eq!(f(&rt, "x"), 2.0);
-    rt.exit().unwrap();
-    assert_eq!(f(&rt, "x"), 999.0);
+    assert!(b(&rt, "c"));
+    assert!(!b(&rt, "nc"));
+    assert!(b(&rt, "sw"));
+    assert!(!b(&rt, "nsw"));
 }
 
-// ─── Result type ──────────────────────────────────────────────────────────────
+#[test]
+fn string_concat() {
+    let rt = run(r#"
+        state {
+            let ab: string = "a" + "b"
+            let label: string = "x=" + 3.0
+            let frac: string = "t=" + 0.5
+        }
+    "#);
+    assert_eq!(str_val(&rt, "ab"), "ab");
+    assert_eq!(str_val(&rt, "label"), "x=3");
+    assert_eq!(str_val(&rt, "frac"), "t=0.5");
+}
 
 #[test]
-fn res_ok_fields() {
+fn string_ordering() {
     let rt = run(r#"
         state {
-            let flag: bool = false
-            let val: float = 0.0
+            let lt: bool = "apple" < "banana"
+            let rev: bool = "b" < "a"
+            let prefix: bool = "app" < "apple"
+            let le: bool = "a" <= "a"
+            let gt: bool = "b" > "a"
+            let ge: bool = "a" >= "b"
+        }
+    "#);
+    assert!(b(&rt, "lt"));
+    assert!(!b(&rt, "rev"));
+    assert!(b(&rt, "prefix"));
+    assert!(b(&rt, "le"));
+    assert!(b(&rt, "gt"));
+    assert!(!b(&rt, "ge"));
+}
+
+// ─── Casts ────────────────────────────────────────────────────────────────────
+
+#[test]
+fn cast_float_bool() {
+    let rt = run(r#"
+        state {
+            let t: bool = 2.5 as bool
+            let z: bool = 0.0 as bool
+            let one: float = true as float
+            let zero: float = false as float
+        }
+    "#);
+    assert!(b(&rt, "t"));
+    assert!(!b(&rt, "z"));
+    assert_eq!(f(&rt, "one"), 1.0);
+    assert_eq!(f(&rt, "zero"), 0.0);
+}
+
+#[test]
+fn cast_int_float() {
+    let rt = run(r#"
+        state {
+            let down: int = 3.9 as int
+            let neg: int = -3.9 as int
+            let up: float = 3 as float
+            let t: bool = 2 as bool
+            let one: int = true as int
+        }
+    "#);
+    assert_eq!(i(&rt, "down"), 3);
+    assert_eq!(i(&rt, "neg"), -3);
+    assert_eq!(f(&rt, "up"), 3.0);
+    assert!(b(&rt, "t"));
+    assert_eq!(i(&rt, "one"), 1);
+}
+
+#[test]
+fn cast_vec2_vec3() {
+    let rt = run(r#"
+        state {
+            let up: vec3 = vec2(1.0, 2.0) as vec3
+            let down: vec2 = vec3(1.0, 2.0, 3.0) as vec2
+        }
+    "#);
+    assert_eq!(v3(&rt, "up"), (1.0, 2.0, 0.0));
+    assert_eq!(v2(&rt, "down"), (1.0, 2.0));
+}
+
+#[test]
+fn cast_color_vec4() {
+    let rt = run(r#"
+        state {
+            let v: vec4 = color(0.1, 0.2, 0.3, 0.4) as vec4
+            let c: color = vec4(0.5, 0.6, 0.7, 0.8) as color
         }
+    "#);
+    assert_eq!(v4(&rt, "v"), (0.1, 0.2, 0.3, 0.4));
+    assert_eq!(rgba(&rt, "c"), (0.5, 0.6, 0.7, 0.8));
+}
+
+#[test]
+fn cast_unsupported_runtime_error() {
+    let e = run_err(r#"
+        state { let c: color = black }
         fn on_init(s: State) -> State {
-            let r: res<float> = ok(42.0)
-            s.flag = r.ok
-            s.val  = r.value
+            s.c = 1.0 as color
             return s
         }
     "#);
-    assert!(b(&rt, "flag"));
-    assert_eq!(f(&rt, "val"), 42.0);
+    assert!(e.message.contains("cannot cast"), "{}", e.message);
 }
 
+// ─── Lists ────────────────────────────────────────────────────────────────────
+
 #[test]
-fn res_error_fields() {
+fn list_push_increases_len() {
     let rt = run(r#"
-        state { let flag: bool = true }
+        state { let xs: list[float] = [] }
         fn on_init(s: State) -> State {
-            let r: res<float> = error("oops")
-            s.flag = r.ok
+            s.xs.push(1.0)
+            s.xs.push(2.0)
+            s.xs.push(3.0)
             return s
         }
     "#);
-    assert!(!b(&rt, "flag"));
+    assert_eq!(list_floats(&rt, "xs"), vec![1.0, 2.0, 3.0]);
 }
 
 #[test]
-fn res_from_fn_success() {
+fn list_push_widens_int_into_float_list() {
     let rt = run(r#"
-        fn safe_div(a: float, b: float) -> res<float> {
-            if b == 0.0 { return error("div by zero") }
-            return ok(a / b)
-        }
         state {
-            let x: float = 0.0
-            let ok_flag: bool = false
+            let xs: list[float] = []
+            let sorted: list[float] = []
+            let half: float = 0.0
         }
         fn on_init(s: State) -> State {
-            let r = safe_div(10.0, 2.0)
-            s.ok_flag = r.ok
-            s.x = r.value
+            s.xs.push(3)
+            s.xs.push(1.5)
+            s.sorted = s.xs.sort()
+            s.half = s.xs[0] / 2
             return s
         }
     "#);
-    assert!(b(&rt, "ok_flag"));
-    assert_eq!(f(&rt, "x"), 5.0);
+    assert_eq!(list_floats(&rt, "xs"), vec![3.0, 1.5]);
+    assert_eq!(list_floats(&rt, "sorted"), vec![1.5, 3.0]);
+    assert_eq!(f(&rt, "half"), 1.5);
 }
 
 #[test]
-fn res_from_fn_failure() {
+fn list_sort_ints() {
     let rt = run(r#"
-        fn safe_div(a: float, b: float) -> res<float> {
-            if b == 0.0 { return error("div by zero") }
-            return ok(a / b)
-        }
-        state { let ok_flag: bool = true }
+        state { let ns: list[int] = [] }
         fn on_init(s: State) -> State {
-            let r = safe_div(10.0, 0.0)
-            s.ok_flag = r.ok
+            s.ns = [3, 1, 2].sort()
             return s
         }
     "#);
-    assert!(!b(&rt, "ok_flag"));
+    assert_eq!(list_ints(&rt, "ns"), vec![1, 2, 3]);
 }
 
 #[test]
-fn try_successful_expr() {
+fn list_pop_removes_last() {
     let rt = run(r#"
         state {
-            let flag: bool = false
-            let val: float = 0.0
+            let xs: list[float] = []
+            let last: float = 0.0
         }
         fn on_init(s: State) -> State {
-            let r: res<float> = try 10.0 / 2.0
-            s.flag = r.ok
-            s.val  = r.value
+            s.xs.push(10.0)
+            s.xs.push(20.0)
+            s.xs.push(30.0)
+            s.last = s.xs.pop()
             return s
         }
     "#);
-    assert!(b(&rt, "flag"));
-    assert_eq!(f(&rt, "val"), 5.0);
+    assert_eq!(f(&rt, "last"), 30.0);
+    assert_eq!(list_floats(&rt, "xs"), vec![10.0, 20.0]);
 }
 
 #[test]
-fn try_catches_div_by_zero() {
+fn list_pop_empty_runtime_error() {
+    run_err(r#"
+        state { let x: float = 0.0 }
+        fn on_init(s: State) -> State {
+            let empty: list[float] = []
+            s.x = empty.pop()
+            return s
+        }
+    "#);
+}
+
+#[test]
+fn list_insert_and_remove() {
     let rt = run(r#"
-        state { let flag: bool = true }
+        state {
+            let xs: list[float] = [1.0, 2.0, 3.0]
+            let removed: float = 0.0
+        }
         fn on_init(s: State) -> State {
-            let r: res<float> = try 1.0 / 0.0
-            s.flag = r.ok
+            s.xs.insert(1, 9.0)
+            s.xs.insert(s.xs.len, 4.0)
+            s.removed = s.xs.remove(2)
             return s
         }
     "#);
-    assert!(!b(&rt, "flag"));
+    assert_eq!(list_floats(&rt, "xs"), vec![1.0, 9.0, 3.0, 4.0]);
+    assert_eq!(f(&rt, "removed"), 2.0);
+}
+
+#[test]
+fn list_insert_widens_int_into_float_list() {
+    let rt = run(r#"
+        state {
+            let xs: list[float] = []
+            let sorted: list[float] = []
+        }
+        fn on_init(s: State) -> State {
+            s.xs.insert(0, 2)
+            s.xs.insert(0, 3)
+            s.sorted = s.xs.sort()
+            return s
+        }
+    "#);
+    assert_eq!(list_floats(&rt, "xs"), vec![3.0, 2.0]);
+    assert_eq!(list_floats(&rt, "sorted"), vec![2.0, 3.0]);
+}
+
+#[test]
+fn list_insert_remove_out_of_bounds() {
+    for call in ["xs.insert(3.0, 1.0)", "xs.remove(2.0)", "xs.remove(-1.0)"] {
+        let e = run_err(&format!(r#"
+            state {{ let x: float = 0.0 }}
+            fn on_init(s: State) -> State {{
+                let xs: list[float] = [1.0, 2.0]
+                {call}
+                return s
+            }}
+        "#));
+        assert_eq!(e.line, 5, "{call}");
+        assert!(e.message.contains("out of bounds"), "{call}: {}", e.message);
+    }
+}
+
+#[test]
+fn list_index_assignment() {
+    let rt = run(r#"
+        state { let x: float = 0.0 }
+        fn on_init(s: State) -> State {
+            let xs: list[float] = [10.0, 20.0, 30.0]
+            xs[1] = 99.0
+            s.x = xs[1]
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "x"), 99.0);
+}
+
+#[test]
+fn list_index_compound_assignment() {
+    let rt = run(r#"
+        state { let x: float = 0.0 }
+        fn on_init(s: State) -> State {
+            let xs: list[float] = [10.0, 20.0, 30.0]
+            xs[1] += 5.0
+            s.x = xs[1]
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "x"), 25.0);
+}
+
+#[test]
+fn list_index_access() {
+    let rt = run(r#"
+        state { let x: float = 0.0 }
+        fn on_init(s: State) -> State {
+            let xs: list[float] = [10.0, 20.0, 30.0]
+            s.x = xs[1]
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "x"), 20.0);
+}
+
+#[test]
+fn list_len_field() {
+    let rt = run(r#"
+        state { let n: float = 0.0 }
+        fn on_init(s: State) -> State {
+            let xs: list[float] = [1.0, 2.0, 3.0]
+            s.n = xs.len
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "n"), 3.0);
+}
+
+#[test]
+fn list_len_method() {
+    let rt = run(r#"
+        state { let n: float = 0.0 }
+        fn on_init(s: State) -> State {
+            let xs: list[float] = [1.0, 2.0]
+            s.n = xs.len()
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "n"), 2.0);
+}
+
+#[test]
+fn list_foreach_sum() {
+    let rt = run(r#"
+        state { let sum: float = 0.0 }
+        fn on_init(s: State) -> State {
+            let xs: list[float] = [1.0, 2.0, 3.0, 4.0, 5.0]
+            foreach v in xs { s.sum = s.sum + v }
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "sum"), 15.0);
+}
+
+#[test]
+fn list_mutation_is_shared() {
+    // Pushing to a list stored in state mutates in-place.
+    let rt = run(r#"
+        state { let xs: list[float] = [] }
+        fn on_init(s: State) -> State {
+            for let i = 1.0; i <= 5.0; i = i + 1.0 {
+                s.xs.push(i)
+            }
+            return s
+        }
+    "#);
+    assert_eq!(list_floats(&rt, "xs"), vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+}
+
+#[test]
+fn list_literal_initial_values() {
+    let rt = run("state { let xs: list[float] = [10.0, 20.0, 30.0] }");
+    assert_eq!(list_floats(&rt, "xs"), vec![10.0, 20.0, 30.0]);
+}
+
+#[test]
+fn list_map_doubles() {
+    let rt = run(r#"
+        state { let xs: list[float] = [] }
+        fn on_init(s: State) -> State {
+            s.xs = [1.0, 2.0, 3.0].map((x: float) -> float { return x * 2.0 })
+            return s
+        }
+    "#);
+    assert_eq!(list_floats(&rt, "xs"), vec![2.0, 4.0, 6.0]);
+}
+
+#[test]
+fn list_map_widens_int_elements_to_float_param() {
+    let rt = run(r#"
+        state { let xs: list[float] = [] }
+        fn on_init(s: State) -> State {
+            s.xs = [1, 4].map((x: float) -> float { return x / 2 })
+            return s
+        }
+    "#);
+    assert_eq!(list_floats(&rt, "xs"), vec![0.5, 2.0]);
+}
+
+#[test]
+fn list_map_changes_element_type() {
+    let rt = run(r#"
+        state { let n: float = 0.0 }
+        fn on_init(s: State) -> State {
+            let flags: list[bool] = [1.0, 2.0, 3.0].map((x: float) -> bool { return x > 1.5 })
+            foreach f in flags { if f { s.n = s.n + 1.0 } }
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "n"), 2.0);
+}
+
+#[test]
+fn list_reduce_sum() {
+    let rt = run(r#"
+        state { let total: float = 0.0 }
+        fn add(a: float, b: float) -> float { return a + b }
+        fn on_init(s: State) -> State {
+            s.total = [1.0, 2.0, 3.0, 4.0].reduce(0.0, add)
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "total"), 10.0);
+}
+
+#[test]
+fn list_filter_evens() {
+    let rt = run(r#"
+        state { let xs: list[float] = [] }
+        fn on_init(s: State) -> State {
+            s.xs = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0].filter((x: float) -> bool { return x % 2.0 == 0.0 })
+            return s
+        }
+    "#);
+    assert_eq!(list_floats(&rt, "xs"), vec![2.0, 4.0, 6.0]);
+}
+
+#[test]
+fn list_sort_reverse_contains() {
+    let rt = run(r#"
+        state {
+            let src: list[float] = [3.0, 1.0, 2.0]
+            let sorted: list[float] = []
+            let rev: list[float] = []
+            let has: bool = false
+            let missing: bool = true
+        }
+        fn on_init(s: State) -> State {
+            s.sorted = s.src.sort()
+            s.rev = [1.0, 2.0].reverse()
+            s.has = [1.0, 2.0].contains(2.0)
+            s.missing = [1.0, 2.0].contains(5.0)
+            return s
+        }
+    "#);
+    assert_eq!(list_floats(&rt, "sorted"), vec![1.0, 2.0, 3.0]);
+    assert_eq!(list_floats(&rt, "src"), vec![3.0, 1.0, 2.0]);
+    assert_eq!(list_floats(&rt, "rev"), vec![2.0, 1.0]);
+    assert!(b(&rt, "has"));
+    assert!(!b(&rt, "missing"));
+}
+
+#[test]
+fn list_sort_non_float_runtime_error() {
+    run_err(r#"
+        state { let n: float = 0.0 }
+        fn on_init(s: State) -> State {
+            let sorted = [vec2(1.0, 0.0), vec2(0.0, 1.0)].sort()
+            s.n = sorted.len
+            return s
+        }
+    "#);
+}
+
+#[test]
+fn list_slice() {
+    let rt = run(r#"
+        state {
+            let mid: list[float] = []
+            let clamped: list[float] = []
+            let empty: list[float] = [1.0]
+        }
+        fn on_init(s: State) -> State {
+            s.mid = [10.0, 20.0, 30.0].slice(1.0, 3.0)
+            s.clamped = [10.0, 20.0, 30.0].slice(-5.0, 99.0)
+            s.empty = [10.0, 20.0, 30.0].slice(2.0, 1.0)
+            return s
+        }
+    "#);
+    assert_eq!(list_floats(&rt, "mid"), vec![20.0, 30.0]);
+    assert_eq!(list_floats(&rt, "clamped"), vec![10.0, 20.0, 30.0]);
+    assert_eq!(list_floats(&rt, "empty"), Vec::<f64>::new());
+}
+
+#[test]
+fn list_negative_index() {
+    let rt = run(r#"
+        state {
+            let xs: list[float] = [10.0, 20.0, 30.0]
+            let last: float = 0.0
+            let first: float = 0.0
+        }
+        fn on_init(s: State) -> State {
+            s.last = s.xs[-1.0]
+            s.first = s.xs[-3.0]
+            s.xs[-2.0] = 99.0
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "last"), 30.0);
+    assert_eq!(f(&rt, "first"), 10.0);
+    assert_eq!(list_floats(&rt, "xs"), vec![10.0, 99.0, 30.0]);
+}
+
+#[test]
+fn list_negative_index_out_of_bounds() {
+    run_err(r#"
+        state { let x: float = 0.0 }
+        fn on_init(s: State) -> State {
+            s.x = [1.0, 2.0][-3.0]
+            return s
+        }
+    "#);
+}
+
+#[test]
+fn list_aggregates() {
+    let rt = run(r#"
+        state {
+            let xs: list[float] = [4.0, -1.0, 3.0, 2.0]
+            let total: float = 0.0
+            let lo: float = 0.0
+            let hi: float = 0.0
+            let mean: float = 0.0
+            let none: float = 1.0
+        }
+        fn on_init(s: State) -> State {
+            s.total = sum(s.xs)
+            s.lo = min_of(s.xs)
+            s.hi = max_of(s.xs)
+            s.mean = avg(s.xs)
+            s.none = sum([])
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "total"), 8.0);
+    assert_eq!(f(&rt, "lo"), -1.0);
+    assert_eq!(f(&rt, "hi"), 4.0);
+    assert_eq!(f(&rt, "mean"), 2.0);
+    assert_eq!(f(&rt, "none"), 0.0);
+}
+
+#[test]
+fn list_aggregates_empty_runtime_error() {
+    for func in ["min_of", "max_of", "avg"] {
+        let e = run_err(&format!(r#"
+            state {{ let xs: list[float] = [] let x: float = 0.0 }}
+            fn on_init(s: State) -> State {{
+                s.x = {func}(s.xs)
+                return s
+            }}
+        "#));
+        assert_eq!(e.line, 4, "{func}");
+    }
+}
+
+#[test]
+fn zip_and_enumerate_pair_into_vec2() {
+    let rt = run(r#"
+        state {
+            let zipped = zip([1, 2], [3, 4])
+            let uneven = zip([1.0, 2.0, 3.0], [5.0])
+            let numbered = enumerate([9, 8])
+            let none = enumerate([])
+        }
+    "#);
+    assert_eq!(list_vec2s(&rt, "zipped"), vec![(1.0, 3.0), (2.0, 4.0)]);
+    assert_eq!(list_vec2s(&rt, "uneven"), vec![(1.0, 5.0)]);
+    assert_eq!(list_vec2s(&rt, "numbered"), vec![(0.0, 9.0), (1.0, 8.0)]);
+    assert_eq!(list_vec2s(&rt, "none"), vec![]);
+}
+
+#[test]
+fn int_list_literal_widens_to_float_list() {
+    let rt = run(r#"
+        state {
+            let xs: list[float] = [1, 2]
+            let total: float = sum([1, 2, 3])
+        }
+    "#);
+    assert_eq!(list_floats(&rt, "xs"), vec![1.0, 2.0]);
+    assert_eq!(f(&rt, "total"), 6.0);
+}
+
+#[test]
+fn nested_int_literals_widen_to_float() {
+    let rt = run(r#"
+        fn four() -> res<float> { return ok(4) }
+        state {
+            let x: float = 0.0
+            let y: float = 0.0
+            let z: float = 0.0
+        }
+        fn on_init(s: State) -> State {
+            let ys: list[list[float]] = [[1, 9]]
+            s.x = ys[0][1].sqrt()
+            let t: (list[float], int) = ([16], 2)
+            s.y = t.0[0].sqrt()
+            s.z = four().value.sqrt()
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "x"), 3.0);
+    assert_eq!(f(&rt, "y"), 4.0);
+    assert_eq!(f(&rt, "z"), 2.0);
+}
+
+#[test]
+fn list_comprehension() {
+    let rt = run(r#"
+        state {
+            let doubled = [x * 2 for x in [1, 2, 3]]
+            let big = [x for x in [1, 2, 3, 4] if x > 2]
+            let xs: list[float] = [i * 0.5 for i in 0..3]
+        }
+    "#);
+    assert_eq!(list_ints(&rt, "doubled"), vec![2, 4, 6]);
+    assert_eq!(list_ints(&rt, "big"), vec![3, 4]);
+    assert_eq!(list_floats(&rt, "xs"), vec![0.0, 0.5, 1.0]);
+}
+
+#[test]
+fn list_comprehension_binding_is_scoped() {
+    let rt = run(r#"
+        state { let x: float = 10.0 let sum: float = 0.0 }
+        fn on_init(s: State) -> State {
+            let x = 10.0
+            s.sum = sum([x + 1.0 for x in [1.0, 2.0]])
+            s.x = x
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "sum"), 5.0);
+    assert_eq!(f(&rt, "x"), 10.0);
+}
+
+// ─── Maps ─────────────────────────────────────────────────────────────────────
+
+#[test]
+fn map_literal_lookup() {
+    let rt = run(r#"
+        state {
+            let a: float = 0.0
+            let b: float = 0.0
+        }
+        fn on_init(s: State) -> State {
+            let m = { "a": 1.5, "b": 2 }
+            s.a = m["a"]
+            s.b = m["b"]
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "a"), 1.5);
+    // Mixed int/float values widen to float, like list literals.
+    assert_eq!(f(&rt, "b"), 2.0);
+}
+
+#[test]
+fn map_methods() {
+    let rt = run(r#"
+        state {
+            let n: float = 0.0
+            let has_a = false
+            let has_c = true
+            let first = ""
+        }
+        fn on_init(s: State) -> State {
+            let m = { "b": 1, "a": 2 }
+            s.n = m.len
+            s.has_a = m.has("a")
+            s.has_c = m.has("c")
+            s.first = m.keys()[0]
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "n"), 2.0);
+    assert!(b(&rt, "has_a"));
+    assert!(!b(&rt, "has_c"));
+    assert_eq!(str_val(&rt, "first"), "a");
+}
+
+#[test]
+fn map_assign_inserts_and_updates() {
+    let rt = run(r#"
+        state {
+            let m: map[int] = {}
+            let x: int = 0
+            let n: float = 0.0
+        }
+        fn on_init(s: State) -> State {
+            s.m["x"] = 1
+            s.m["x"] += 4
+            s.m["y"] = 7
+            s.x = s.m["x"]
+            s.n = s.m.len
+            return s
+        }
+    "#);
+    assert_eq!(i(&rt, "x"), 5);
+    assert_eq!(f(&rt, "n"), 2.0);
+}
+
+#[test]
+fn float_map_widens_int_literals_and_writes() {
+    let rt = run(r#"
+        state {
+            let x: float = 0.0
+            let y: float = 0.0
+            let z: float = 0.0
+        }
+        fn on_init(s: State) -> State {
+            let m: map[float] = { "a": 9, "b": 1 }
+            s.x = m["a"].sqrt()
+            m["b"] = 4
+            m["c"] = 16
+            s.y = m["b"].sqrt()
+            s.z = m["c"].sqrt()
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "x"), 3.0);
+    assert_eq!(f(&rt, "y"), 2.0);
+    assert_eq!(f(&rt, "z"), 4.0);
+}
+
+#[test]
+fn assigned_int_literals_widen_to_the_target_type() {
+    let rt = run(r#"
+        state { let x: float = 0.0 }
+        fn on_init(s: State) -> State {
+            let xs: list[float] = []
+            xs = [1, 9]
+            let ms: map[list[float]] = {}
+            ms["k"] = [16]
+            s.x = xs[1].sqrt() + ms["k"][0].sqrt()
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "x"), 7.0);
+}
+
+#[test]
+fn map_missing_key_runtime_error() {
+    let e = run_err(r#"
+        state { let x: float = 0.0 }
+        fn on_init(s: State) -> State {
+            let m = { "a": 1.0 }
+            s.x = m["b"]
+            return s
+        }
+    "#);
+    assert_eq!(e.line, 5);
+    assert!(e.message.contains("\"b\""), "{}", e.message);
+}
+
+#[test]
+fn map_equality_compares_entries() {
+    let rt = run(r#"
+        state {
+            let same: bool = { "a": 1.0, "b": 2.0 } == { "b": 2.0, "a": 1.0 }
+            let differ: bool = { "a": 1.0 } == { "a": 2.0 }
+            let extra: bool = { "a": 1.0 } != { "a": 1.0, "b": 2.0 }
+        }
+    "#);
+    assert!(b(&rt, "same"));
+    assert!(!b(&rt, "differ"));
+    assert!(b(&rt, "extra"));
+}
+
+// ─── Tuples ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn tuple_construct_and_field_access() {
+    let rt = run(r#"
+        state {
+            let first: float = 0.0
+            let inner: int = 0
+        }
+        fn on_init(s: State) -> State {
+            let t = (1.5, (2, 3))
+            s.first = t.0
+            s.inner = t.1.1
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "first"), 1.5);
+    assert_eq!(i(&rt, "inner"), 3);
+}
+
+#[test]
+fn tuple_destructure_from_fn() {
+    let rt = run(r#"
+        state {
+            let r: float = 0.0
+            let t: float = 0.0
+        }
+        fn to_radial(v: vec2) -> (float, float) {
+            return (v.length(), atan2(v.y, v.x))
+        }
+        fn on_init(s: State) -> State {
+            let (radius, theta) = to_radial(vec2(3.0, 4.0))
+            s.r = radius
+            s.t = theta
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "r"), 5.0);
+    assert_eq!(f(&rt, "t"), 4.0_f64.atan2(3.0));
+}
+
+// ─── State lifecycle ──────────────────────────────────────────────────────────
+
+#[test]
+fn state_initializers_run() {
+    let rt = run(r#"
+        state {
+            let a: float = 2.0 + 3.0
+            let b: bool  = 10.0 > 5.0
+        }
+    "#);
+    assert_eq!(f(&rt, "a"), 5.0);
+    assert!(b(&rt, "b"));
+}
+
+#[test]
+fn init_runs_before_first_tick() {
+    let rt = run(r#"
+        state { let x: float = 0.0 }
+        fn on_init(s: State) -> State {
+            s.x = 99.0
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "x"), 99.0);
+}
+
+#[test]
+fn update_accumulates_over_ticks() {
+    let mut rt = run(r#"
+        state { let t: float = 0.0 }
+        fn on_update(s: State, input: Input) -> State {
+            s.t = s.t + 1.0
+            return s
+        }
+    "#);
+    tick(&mut rt);
+    tick(&mut rt);
+    tick(&mut rt);
+    assert_eq!(f(&rt, "t"), 3.0);
+}
+
+#[test]
+fn update_uses_input_dt() {
+    let mut rt = run(r#"
+        state { let t: float = 0.0 }
+        fn on_update(s: State, input: Input) -> State {
+            s.t = s.t + input.dt
+            return s
+        }
+    "#);
+    tick(&mut rt);
+    // dt is 0.016 per tick
+    assert!((f(&rt, "t") - 0.016).abs() < 1e-10);
+}
+
+#[test]
+fn update_reads_input_time_frame_and_mouse() {
+    let mut rt = run(r#"
+        state {
+            let total: float = 0.0
+            let last_time: float = 0.0
+            let frame: float = 0.0
+            let mouse: vec2 = vec2(0.0, 0.0)
+        }
+        fn on_update(s: State, input: Input) -> State {
+            s.total += input.time
+            s.last_time = input.time
+            s.frame = input.frame
+            s.mouse = input.mouse
+            return s
+        }
+    "#);
+    for i in 0..3 {
+        let input = Input { dt: 0.5, time: 0.5 * (i + 1) as f64, frame: i as f64, mouse: (10.0, -4.0) };
+        rt.tick(&input).expect("tick failed");
+    }
+    assert_eq!(f(&rt, "last_time"), 1.5);
+    assert_eq!(f(&rt, "total"), 0.5 + 1.0 + 1.5);
+    assert_eq!(f(&rt, "frame"), 2.0);
+    assert_eq!(v2(&rt, "mouse"), (10.0, -4.0));
+}
+
+#[test]
+fn init_and_update_together() {
+    let mut rt = run(r#"
+        state { let x: float = 0.0 }
+        fn on_init(s: State) -> State {
+            s.x = 10.0
+            return s
+        }
+        fn on_update(s: State, input: Input) -> State {
+            s.x = s.x + 1.0
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "x"), 10.0);
+    tick(&mut rt);
+    assert_eq!(f(&rt, "x"), 11.0);
+    tick(&mut rt);
+    assert_eq!(f(&rt, "x"), 12.0);
+}
+
+#[test]
+fn on_exit_runs_when_exit_called() {
+    let mut rt = run(r#"
+        state { let x: float = 0.0 }
+        fn on_update(s: State, input: Input) -> State {
+            s.x = s.x + 1.0
+            return s
+        }
+        fn on_exit(s: State) -> State {
+            s.x = 999.0
+            return s
+        }
+    "#);
+    tick(&mut rt);
+    tick(&mut rt);
+    assert_eq!(f(&rt, "x"), 2.0);
+    rt.exit().unwrap();
+    assert_eq!(f(&rt, "x"), 999.0);
+}
+
+// ─── Result type ──────────────────────────────────────────────────────────────
+
+#[test]
+fn res_ok_fields() {
+    let rt = run(r#"
+        state {
+            let flag: bool = false
+            let val: float = 0.0
+        }
+        fn on_init(s: State) -> State {
+            let r: res<float> = ok(42.0)
+            s.flag = r.ok
+            s.val  = r.value
+            return s
+        }
+    "#);
+    assert!(b(&rt, "flag"));
+    assert_eq!(f(&rt, "val"), 42.0);
+}
+
+#[test]
+fn res_error_fields() {
+    let rt = run(r#"
+        state { let flag: bool = true }
+        fn on_init(s: State) -> State {
+            let r: res<float> = error("oops")
+            s.flag = r.ok
+            return s
+        }
+    "#);
+    assert!(!b(&rt, "flag"));
+}
+
+#[test]
+fn res_from_fn_success() {
+    let rt = run(r#"
+        fn safe_div(a: float, b: float) -> res<float> {
+            if b == 0.0 { return error("div by zero") }
+            return ok(a / b)
+        }
+        state {
+            let x: float = 0.0
+            let ok_flag: bool = false
+        }
+        fn on_init(s: State) -> State {
+            let r = safe_div(10.0, 2.0)
+            s.ok_flag = r.ok
+            s.x = r.value
+            return s
+        }
+    "#);
+    assert!(b(&rt, "ok_flag"));
+    assert_eq!(f(&rt, "x"), 5.0);
+}
+
+#[test]
+fn res_from_fn_failure() {
+    let rt = run(r#"
+        fn safe_div(a: float, b: float) -> res<float> {
+            if b == 0.0 { return error("div by zero") }
+            return ok(a / b)
+        }
+        state { let ok_flag: bool = true }
+        fn on_init(s: State) -> State {
+            let r = safe_div(10.0, 0.0)
+            s.ok_flag = r.ok
+            return s
+        }
+    "#);
+    assert!(!b(&rt, "ok_flag"));
+}
+
+#[test]
+fn try_successful_expr() {
+    let rt = run(r#"
+        state {
+            let flag: bool = false
+            let val: float = 0.0
+        }
+        fn on_init(s: State) -> State {
+            let r: res<float> = try 10.0 / 2.0
+            s.flag = r.ok
+            s.val  = r.value
+            return s
+        }
+    "#);
+    assert!(b(&rt, "flag"));
+    assert_eq!(f(&rt, "val"), 5.0);
+}
+
+#[test]
+fn try_catches_div_by_zero() {
+    let rt = run(r#"
+        state { let flag: bool = true }
+        fn on_init(s: State) -> State {
+            let r: res<float> = try 1.0 / 0.0
+            s.flag = r.ok
+            return s
+        }
+    "#);
+    assert!(!b(&rt, "flag"));
+}
+
+#[test]
+fn res_unwrap_or() {
+    let rt = run(r#"
+        state {
+            let a: float = 0.0
+            let b: float = 0.0
+        }
+        fn on_init(s: State) -> State {
+            let e: res<float> = error("x")
+            s.a = e.unwrap_or(9.0)
+            s.b = ok(3.0).unwrap_or(9.0)
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "a"), 9.0);
+    assert_eq!(f(&rt, "b"), 3.0);
+}
+
+#[test]
+fn res_map() {
+    let rt = run(r#"
+        fn double(x: float) -> float { return x * 2.0 }
+        state {
+            let x: float = 0.0
+            let err_ok: bool = true
+            let msg: string = ""
+        }
+        fn on_init(s: State) -> State {
+            s.x = ok(2.0).map(double).value
+            let e: res<float> = error("bad")
+            let m = e.map(double)
+            s.err_ok = m.ok
+            s.msg = m.error
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "x"), 4.0);
+    assert!(!b(&rt, "err_ok"));
+    assert_eq!(str_val(&rt, "msg"), "bad");
+}
+
+#[test]
+fn propagate_unwraps_ok() {
+    let rt = run(r#"
+        fn safe_div(a: float, b: float) -> res<float> {
+            if b == 0.0 { return error("div by zero") }
+            return ok(a / b)
+        }
+        fn halve_twice(x: float) -> res<float> {
+            let h = safe_div(x, 2.0)?
+            return ok(safe_div(h, 2.0)? + 0.5)
+        }
+        state { let x: float = 0.0 }
+        fn on_init(s: State) -> State {
+            s.x = halve_twice(10.0).value
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "x"), 3.0);
+}
+
+#[test]
+fn propagate_returns_error_early() {
+    let rt = run(r#"
+        fn safe_div(a: float, b: float) -> res<float> {
+            if b == 0.0 { return error("div by zero") }
+            return ok(a / b)
+        }
+        state {
+            let reached: float = 0.0
+            let ok_flag: bool = true
+            let msg: string = ""
+        }
+        fn attempt(s: State, d: float) -> res<float> {
+            if true {
+                let v = safe_div(1.0, d)?
+                s.reached = 1.0
+            }
+            return ok(1.0)
+        }
+        fn on_init(s: State) -> State {
+            let r = attempt(s, 0.0)
+            s.ok_flag = r.ok
+            s.msg = r.error
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "reached"), 0.0);
+    assert!(!b(&rt, "ok_flag"));
+    assert_eq!(str_val(&rt, "msg"), "div by zero");
+}
+
+// ─── Runtime control ──────────────────────────────────────────────────────────
+
+#[test]
+fn debug_writes_log_lines() {
+    let mut rt = run(r#"
+        debug(vec2(1, 2))
+        state { let n: float = 0.0 }
+        fn on_update(s: State, input: Input) -> State {
+            s.n += 1.0
+            debug([s.n, 0.5])
+            debug(s.n > 1.0 ? "late" : "early")
+            return s
+        }
+    "#);
+    let logs = rt.take_logs();
+    assert_eq!(logs.len(), 1);
+    assert!(logs[0].contains("vec2(1, 2)"), "{logs:?}");
+    tick(&mut rt);
+    tick(&mut rt);
+    assert_eq!(rt.take_logs(), ["[2] vec2(1, 2)", "[6] [1, 0.5]", "[7] early", "[2] vec2(1, 2)", "[6] [2, 0.5]", "[7] late"]);
+    assert!(rt.take_logs().is_empty());
+}
+
+#[test]
+fn debug_logs_survive_failed_tick() {
+    let mut rt = run(r#"
+        fn on_update(s: State, input: Input) -> State {
+            debug("before")
+            assert(false, "stop")
+            return s
+        }
+    "#);
+    tick_err(&mut rt);
+    assert_eq!(rt.take_logs(), ["[3] before"]);
+}
+
+#[test]
+fn debug_logs_keep_only_the_newest_lines() {
+    let mut rt = run(r#"
+        fn on_update(s: State, input: Input) -> State {
+            for i in 0..700 { debug(i) }
+            return s
+        }
+    "#);
+    tick(&mut rt);
+    tick(&mut rt);
+    let logs = rt.take_logs();
+    assert_eq!(logs.len(), rustle_lang::namespaces::MAX_LOGS);
+    assert_eq!(logs.first().map(String::as_str), Some("[3] 400"));
+    assert_eq!(logs.last().map(String::as_str), Some("[3] 699"));
+}
+
+#[test]
+fn try_tick_keeps_last_frame_and_state_on_error() {
+    let mut rt = run(r#"
+        import shapes { circle }
+        state {
+            let n: float = 0.0
+            let fail: bool = false
+        }
+        fn on_update(s: State, input: Input) -> State {
+            s.n += 1.0
+            assert(not s.fail, "transient")
+            out << circle(vec2(s.n, 0.0), 1.0)
+            return s
+        }
+    "#);
+    let input = Input { dt: 0.016, ..Input::default() };
+    assert_eq!(tick(&mut rt).len(), 1);
+
+    rt.set_field("fail", Value::Bool(true));
+    let (cmds, err) = rt.try_tick(&input);
+    assert!(err.expect("expected an error").message.contains("transient"));
+    let DrawCommand::DrawShape(data) = &cmds[0];
+    assert!(matches!(data.desc, ShapeDesc::Circle { center: (1.0, _), .. }));
+    assert_eq!(f(&rt, "n"), 1.0);
+
+    rt.set_field("fail", Value::Bool(false));
+    let (cmds, err) = rt.try_tick(&input);
+    assert!(err.is_none());
+    assert_eq!(cmds.len(), 1);
+    assert_eq!(f(&rt, "n"), 2.0);
+}
+
+#[test]
+fn try_tick_rolls_back_list_mutations_on_error() {
+    let mut rt = run(r#"
+        state {
+            let xs: list[float] = [1.0]
+            let ys: list[float] = []
+            let fail: bool = false
+        }
+        fn on_init(s: State) -> State {
+            s.ys = s.xs
+            return s
+        }
+        fn on_update(s: State, input: Input) -> State {
+            s.xs.push(2.0)
+            assert(not s.fail, "transient")
+            return s
+        }
+    "#);
+    let input = Input { dt: 0.016, ..Input::default() };
+    rt.set_field("fail", Value::Bool(true));
+    let (_, err) = rt.try_tick(&input);
+    assert!(err.is_some());
+    assert_eq!(list_floats(&rt, "xs"), vec![1.0]);
+
+    // Fields that shared a list before the rollback still share one after it.
+    rt.set_field("fail", Value::Bool(false));
+    let (_, err) = rt.try_tick(&input);
+    assert!(err.is_none());
+    assert_eq!(list_floats(&rt, "ys"), vec![1.0, 2.0]);
+}
+
+#[test]
+fn assert_fails_with_message() {
+    let e = run_err(r#"
+        let x = 1.0
+        assert(x < 2.0, "fine")
+        assert(false, "boom")
+    "#);
+    assert!(e.message.contains("boom"), "{}", e.message);
+    assert_eq!(e.line, 4);
+    let rt = run(r#"
+        assert(true, "ok")
+        state { let reached: bool = true }
+    "#);
+    assert!(b(&rt, "reached"));
+}
+
+#[test]
+fn reset_restores_initial_state() {
+    let mut rt = run(r#"
+        state { let count: float = 0.0 }
+        fn on_init(s: State) -> State {
+            s.count = 10.0
+            return s
+        }
+        fn on_update(s: State, input: Input) -> State {
+            s.count += 1.0
+            return s
+        }
+    "#);
+    tick(&mut rt);
+    tick(&mut rt);
+    assert_eq!(f(&rt, "count"), 12.0);
+    rt.reset().expect("reset failed");
+    assert_eq!(f(&rt, "count"), 10.0);
+    tick(&mut rt);
+    assert_eq!(f(&rt, "count"), 11.0);
+}
+
+#[test]
+fn external_state_edits_seen_by_next_update() {
+    let mut rt = run(r#"
+        state {
+            let speed: float = 1.0
+            let x: float = 0.0
+        }
+        fn on_update(s: State, input: Input) -> State {
+            s.x += s.speed
+            return s
+        }
+    "#);
+    tick(&mut rt);
+    rt.set_field("speed", Value::Float(5.0));
+    tick(&mut rt);
+    assert_eq!(f(&rt, "x"), 6.0);
+    rt.state_mut().0.insert("x".into(), Value::Float(100.0));
+    tick(&mut rt);
+    assert_eq!(f(&rt, "x"), 105.0);
+}
+
+#[test]
+fn infinite_loop_in_init_hits_iteration_limit() {
+    let prog = compile(r#"
+        state { let x: float = 0.0 }
+        fn on_init(s: State) -> State {
+            while true {
+                s.x += 1.0
+            }
+            return s
+        }
+    "#).expect("compile failed");
+    let e = match Runtime::with_iteration_limit(prog, 1000) {
+        Ok(_)  => panic!("expected Runtime::new to fail but it succeeded"),
+        Err(e) => e,
+    };
+    assert!(e.message.contains("iteration limit exceeded"), "{}", e.message);
+    assert_eq!(e.line, 4);
+}
+
+#[test]
+fn iteration_limit_counts_for_loops() {
+    let prog = compile(r#"
+        state { let n: float = 0.0 }
+        fn on_update(s: State, input: Input) -> State {
+            for let i = 0.0; i < 10.0; i += 1.0 { s.n += 1.0 }
+            return s
+        }
+    "#).expect("compile failed");
+    let mut rt = Runtime::with_iteration_limit(prog, 10).expect("init failed");
+    tick(&mut rt);
+    assert_eq!(f(&rt, "n"), 10.0);
+
+    let prog = compile(r#"
+        fn on_update(s: State, input: Input) -> State {
+            for let i = 0.0; i < 11.0; i += 1.0 { }
+            return s
+        }
+    "#).expect("compile failed");
+    let mut rt = Runtime::with_iteration_limit(prog, 10).expect("init failed");
+    assert!(tick_err(&mut rt).message.contains("iteration limit exceeded"));
+}
+
+#[test]
+fn iteration_limit_counts_foreach_and_comprehensions() {
+    let prog = compile(r#"
+        fn on_update(s: State, input: Input) -> State {
+            for i in 0..100000 { }
+            return s
+        }
+    "#).expect("compile failed");
+    let mut rt = Runtime::with_iteration_limit(prog, 1000).expect("init failed");
+    assert!(tick_err(&mut rt).message.contains("iteration limit exceeded"));
+
+    let prog = compile(r#"
+        fn on_update(s: State, input: Input) -> State {
+            let ys = [x * 2.0 for x in 0..100000]
+            return s
+        }
+    "#).expect("compile failed");
+    let mut rt = Runtime::with_iteration_limit(prog, 1000).expect("init failed");
+    assert!(tick_err(&mut rt).message.contains("iteration limit exceeded"));
+}
+
+// ─── Draw output ──────────────────────────────────────────────────────────────
+
+#[test]
+fn draw_static_emits_circle() {
+    let mut rt = run(r#"
+        import shapes { circle }
+        out << circle(vec2(0.0, 0.0), 0.5)
+    "#);
+    let cmds = tick(&mut rt);
+    assert_eq!(cmds.len(), 1);
+    let DrawCommand::DrawShape(data) = &cmds[0];
+    assert!(matches!(data.desc, ShapeDesc::Circle { .. }));
+}
+
+#[test]
+fn draw_shape_fill_and_stroke_color() {
+    let mut rt = run(r#"
+        import shapes { circle }
+        out << circle(vec2(0.0, 0.0), 0.5, fill: #ff0000, stroke_color: #0000ff80)
+        out << circle(vec2(0.0, 0.0), 0.5)
+    "#);
+    let cmds = tick(&mut rt);
+    let DrawCommand::DrawShape(data) = &cmds[0];
+    assert_eq!(data.fill, Some(Color { r: 1.0, g: 0.0, b: 0.0, a: 1.0 }));
+    assert_eq!(data.stroke_color, Some(Color { r: 0.0, g: 0.0, b: 1.0, a: 128.0 / 255.0 }));
+    let DrawCommand::DrawShape(plain) = &cmds[1];
+    assert_eq!((plain.fill, plain.stroke_color), (None, None));
+}
+
+#[test]
+fn draw_output_sorted_by_layer() {
+    let mut rt = run(r#"
+        import shapes { circle, rect }
+        out << circle(vec2(0.0, 0.0), 0.5, layer: 1.0)
+        out << rect(vec2(0.0, 0.0), vec2(1.0, 1.0))
+        out << circle(vec2(1.0, 0.0), 0.5, layer: 1.0)
+        out << rect(vec2(1.0, 0.0), vec2(1.0, 1.0), layer: -1.0)
+    "#);
+    let cmds = tick(&mut rt);
+    let order: Vec<(f64, f64)> = cmds.iter().map(|c| {
+        let DrawCommand::DrawShape(data) = c;
+        (data.layer, data.desc.anchor().0)
+    }).collect();
+    assert_eq!(order, vec![(-1.0, 1.0), (0.0, 0.0), (1.0, 0.0), (1.0, 1.0)]);
+}
+
+#[test]
+fn draw_shape_dashed_render_mode() {
+    let mut rt = run(r#"
+        import shapes { circle }
+        import render { dashed }
+        out << circle(vec2(0.0, 0.0), 0.5, render: dashed(2.0, 5.0, 3.0))
+    "#);
+    let cmds = tick(&mut rt);
+    let DrawCommand::DrawShape(data) = &cmds[0];
+    assert!(matches!(
+        data.render_mode,
+        rustle_lang::RenderMode::Dashed { width: 2.0, on: 5.0, off: 3.0 }
+    ), "{:?}", data.render_mode);
+    let e = run_err(r#"
+        import render { dashed }
+        let m = dashed(1.0, 0.0, 3.0)
+    "#);
+    assert!(e.message.contains("dashed"), "{}", e.message);
+}
+
+#[test]
+fn draw_shape_alpha_named_arg() {
+    let mut rt = run(r#"
+        import shapes { circle }
+        out << circle(vec2(0.0, 0.0), 0.5, alpha: 0.5)
+        out << circle(vec2(0.0, 0.0), 0.5, alpha: 3.0)
+        out << circle(vec2(0.0, 0.0), 0.5)
+    "#);
+    let alphas: Vec<f64> = tick(&mut rt).iter().map(|c| {
+        let DrawCommand::DrawShape(data) = c;
+        data.alpha
+    }).collect();
+    assert_eq!(alphas, vec![0.5, 1.0, 1.0]);
+}
+
+#[test]
+fn draw_shape_fill_must_be_color() {
+    let e = run_err(r#"
+        import shapes { circle }
+        out << circle(vec2(0.0, 0.0), 0.5, fill: 1.0)
+    "#);
+    assert!(e.message.contains("fill"));
+}
+
+#[test]
+fn draw_static_emits_rect() {
+    let mut rt = run(r#"
+        import shapes { rect }
+        out << rect(vec2(0.0, 0.0), vec2(1.0, 1.0))
+    "#);
+    let cmds = tick(&mut rt);
+    assert_eq!(cmds.len(), 1);
+    let DrawCommand::DrawShape(data) = &cmds[0];
+    assert!(matches!(data.desc, ShapeDesc::Rect { .. }));
+}
+
+#[test]
+fn draw_static_multiple_shapes() {
+    let mut rt = run(r#"
+        import shapes { circle, rect }
+        out << rect(vec2(0.0, 0.0), vec2(2.0, 2.0))
+        out << circle(vec2(0.0, 0.0), 0.3)
+    "#);
+    let cmds = tick(&mut rt);
+    assert_eq!(cmds.len(), 2);
+}
+
+#[test]
+fn draw_static_chained_out() {
+    let mut rt = run(r#"
+        import shapes { circle, rect }
+        let bg = rect(vec2(0.0, 0.0), vec2(2.0, 2.0))
+        let c  = circle(vec2(0.0, 0.0), 0.3)
+        out << bg << c
+    "#);
+    let cmds = tick(&mut rt);
+    assert_eq!(cmds.len(), 2);
+}
+
+#[test]
+fn draw_update_emits_each_tick() {
+    let mut rt = run(r#"
+        import shapes { circle }
+        state { let t: float = 0.0 }
+        fn on_update(s: State, input: Input) -> State {
+            s.t = s.t + input.dt
+            out << circle(vec2(sin(s.t) * 0.5, 0.0), 0.2)
+            return s
+        }
+    "#);
+    let c1 = tick(&mut rt);
+    let c2 = tick(&mut rt);
+    assert_eq!(c1.len(), 1);
+    assert_eq!(c2.len(), 1);
+}
+
+#[test]
+fn draw_foreach_emits_one_per_element() {
+    let mut rt = run(r#"
+        import shapes { circle }
+        state { let xs: list[float] = [] }
+        fn on_init(s: State) -> State {
+            for let i = 0.0; i < 5.0; i = i + 1.0 { s.xs.push(i * 0.1) }
+            return s
+        }
+        fn on_update(s: State, input: Input) -> State {
+            foreach v in s.xs { out << circle(vec2(v, 0.0), 0.05) }
+            return s
+        }
+    "#);
+    let cmds = tick(&mut rt);
+    assert_eq!(cmds.len(), 5);
+}
+
+#[test]
+fn draw_transform_attached_to_shape() {
+    let mut rt = run(r#"
+        import shapes { circle }
+        let t = transform().scale(2.0)
+        let s = circle(vec2(0.0, 0.0), 0.2)
+        out << s@t
+    "#);
+    let cmds = tick(&mut rt);
+    assert_eq!(cmds.len(), 1);
+    let DrawCommand::DrawShape(data) = &cmds[0];
+    assert_eq!(data.transforms.len(), 1);
+    assert_eq!(data.transforms[0].sx, 2.0);
+    assert_eq!(data.transforms[0].sy, 2.0);
+}
+
+#[test]
+fn draw_transform_shear_attached() {
+    let mut rt = run(r#"
+        import shapes { rect }
+        let t = transform().shear(0.5, 0.0).shear(0.25, 1.0)
+        out << rect(vec2(0.0, 0.0), vec2(1.0, 1.0))@t
+    "#);
+    let cmds = tick(&mut rt);
+    let DrawCommand::DrawShape(data) = &cmds[0];
+    assert_eq!(data.transforms[0].shx, 0.75);
+    assert_eq!(data.transforms[0].shy, 1.0);
+}
+
+#[test]
+fn draw_transform_rotate_around_sets_pivot() {
+    let mut rt = run(r#"
+        import shapes { circle }
+        let t = transform().rotate_around(1.0, 0.0, 180.0)
+        out << circle(vec2(0.0, 0.0), 0.2)@t
+    "#);
+    let cmds = tick(&mut rt);
+    let DrawCommand::DrawShape(data) = &cmds[0];
+    assert_eq!(data.transforms[0].pivot, Some((1.0, 0.0)));
+    assert!((data.transforms[0].angle - std::f64::consts::PI).abs() < 1e-10);
+}
+
+#[test]
+fn draw_transform_reset_is_identity() {
+    let mut rt = run(r#"
+        import shapes { circle }
+        let t = transform().move(3.0, 4.0).scale(3.0).reset()
+        out << circle(vec2(0.0, 0.0), 0.2)@(t, transform_identity())
+    "#);
+    let cmds = tick(&mut rt);
+    let DrawCommand::DrawShape(data) = &cmds[0];
+    for td in &data.transforms {
+        assert_eq!((td.sx, td.sy), (1.0, 1.0));
+        assert_eq!((td.tx, td.ty), (0.0, 0.0));
+        assert_eq!(td.angle, 0.0);
+    }
+}
+
+#[test]
+fn draw_multiple_transforms_accumulated() {
+    let mut rt = run(r#"
+        import shapes { circle }
+        let t1 = transform().scale(2.0)
+        let t2 = transform().move(0.5, 0.0)
+        let s  = circle(vec2(0.0, 0.0), 0.2)
+        out << s@(t1, t2)
+    "#);
+    let cmds = tick(&mut rt);
+    let DrawCommand::DrawShape(data) = &cmds[0];
+    assert_eq!(data.transforms.len(), 2);
+}
+
+#[test]
+fn transform_apply_to_point() {
+    let rt = run(r#"
+        state {
+            let moved: vec2 = transform().move(1.0, 0.0).apply(vec2(0.0, 0.0))
+            let turned: vec2 = transform().rotate(90.0).apply(vec2(1.0, 0.0))
+            let scaled: vec2 = transform().scale(2.0).move(0.0, 1.0).apply(vec2(1.0, 1.0))
+        }
+    "#);
+    assert_eq!(v2(&rt, "moved"), (1.0, 0.0));
+    let (x, y) = v2(&rt, "turned");
+    assert!(x.abs() < 1e-10 && (y - 1.0).abs() < 1e-10);
+    assert_eq!(v2(&rt, "scaled"), (2.0, 3.0));
+}
+
+#[test]
+fn shape_bounds_untransformed() {
+    let rt = run(r#"
+        import shapes { circle, rect, line }
+        state {
+            let r: vec4 = rect(vec2(0.0, 0.0), vec2(1.0, 1.0)).bounds()
+            let c: vec4 = circle(vec2(1.0, 2.0), 0.5).bounds()
+            let l: vec4 = line(vec2(1.0, -1.0), vec2(-2.0, 3.0)).bounds()
+        }
+    "#);
+    assert_eq!(v4(&rt, "r"), (-0.5, -0.5, 0.5, 0.5));
+    assert_eq!(v4(&rt, "c"), (0.5, 1.5, 1.5, 2.5));
+    assert_eq!(v4(&rt, "l"), (-2.0, -1.0, 1.0, 3.0));
+}
+
+#[test]
+fn shape_bounds_follow_transforms() {
+    let rt = run(r#"
+        import shapes { rect }
+        state {
+            let a: vec4 = (rect(vec2(0.0, 0.0), vec2(1.0, 1.0))@transform().scale(2.0).move(3.0, 0.0)).bounds()
+            let b: vec4 = (rect(vec2(0.0, 0.0), vec2(2.0, 1.0))@transform().rotate(90.0)).bounds()
+        }
+    "#);
+    assert_eq!(v4(&rt, "a"), (2.0, -1.0, 4.0, 1.0));
+    let (x0, y0, x1, y1) = v4(&rt, "b");
+    assert!((x0 + 0.5).abs() < 1e-10 && (y0 + 1.0).abs() < 1e-10);
+    assert!((x1 - 0.5).abs() < 1e-10 && (y1 - 1.0).abs() < 1e-10);
+}
+
+#[test]
+fn shape_contains_point() {
+    let rt = run(r#"
+        import shapes { circle, rect, polygon, line }
+        state {
+            let c_center: bool = circle(vec2(1.0, 1.0), 0.5).contains(vec2(1.0, 1.0))
+            let c_far: bool = circle(vec2(1.0, 1.0), 0.5).contains(vec2(5.0, 5.0))
+            let r_in: bool = rect(vec2(0.0, 0.0), vec2(2.0, 1.0)).contains(vec2(0.9, 0.4))
+            let r_out: bool = rect(vec2(0.0, 0.0), vec2(2.0, 1.0)).contains(vec2(0.9, 0.6))
+            let p_in: bool = polygon([vec2(0.0, 0.0), vec2(2.0, 0.0), vec2(0.0, 2.0)]).contains(vec2(0.5, 0.5))
+            let p_out: bool = polygon([vec2(0.0, 0.0), vec2(2.0, 0.0), vec2(0.0, 2.0)]).contains(vec2(1.5, 1.5))
+            let l: bool = line(vec2(0.0, 0.0), vec2(1.0, 1.0)).contains(vec2(0.5, 0.5))
+        }
+    "#);
+    assert!(b(&rt, "c_center"));
+    assert!(!b(&rt, "c_far"));
+    assert!(b(&rt, "r_in"));
+    assert!(!b(&rt, "r_out"));
+    assert!(b(&rt, "p_in"));
+    assert!(!b(&rt, "p_out"));
+    assert!(!b(&rt, "l"));
+}
+
+#[test]
+fn shape_contains_respects_transforms() {
+    let rt = run(r#"
+        import shapes { rect }
+        state {
+            let moved: bool = (rect(vec2(0.0, 0.0), vec2(1.0, 1.0))@transform().move(5.0, 0.0)).contains(vec2(5.2, 0.0))
+            let origin: bool = (rect(vec2(0.0, 0.0), vec2(1.0, 1.0))@transform().move(5.0, 0.0)).contains(vec2(0.0, 0.0))
+            let turned: bool = (rect(vec2(0.0, 0.0), vec2(2.0, 0.2))@transform().rotate(90.0)).contains(vec2(0.0, 0.9))
+        }
+    "#);
+    assert!(b(&rt, "moved"));
+    assert!(!b(&rt, "origin"));
+    assert!(b(&rt, "turned"));
+}
+
+#[test]
+fn shape_area_and_perimeter() {
+    let rt = run(r#"
+        import shapes { circle, rect, line, polygon, wedge }
+        state {
+            let c_area: float = circle(vec2(3.0, 3.0), 1.0).area()
+            let c_perim: float = circle(vec2(3.0, 3.0), 1.0).perimeter()
+            let r_area: float = rect(vec2(0.0, 0.0), vec2(2.0, 3.0)).area()
+            let r_perim: float = rect(vec2(0.0, 0.0), vec2(2.0, 3.0)).perimeter()
+            let l_area: float = line(vec2(0.0, 0.0), vec2(3.0, 4.0)).area()
+            let l_perim: float = line(vec2(0.0, 0.0), vec2(3.0, 4.0)).perimeter()
+            let p_area: float = polygon([vec2(0.0, 0.0), vec2(4.0, 0.0), vec2(0.0, 3.0)]).area()
+            let p_perim: float = polygon([vec2(0.0, 0.0), vec2(4.0, 0.0), vec2(0.0, 3.0)]).perimeter()
+            let w_area: float = wedge(vec2(0.0, 0.0), 2.0, 0.0, PI / 2.0).area()
+        }
+    "#);
+    let pi = std::f64::consts::PI;
+    assert!((f(&rt, "c_area") - pi).abs() < 1e-12);
+    assert!((f(&rt, "c_perim") - 2.0 * pi).abs() < 1e-12);
+    assert_eq!(f(&rt, "r_area"), 6.0);
+    assert_eq!(f(&rt, "r_perim"), 10.0);
+    assert_eq!(f(&rt, "l_area"), 0.0);
+    assert_eq!(f(&rt, "l_perim"), 5.0);
+    assert_eq!(f(&rt, "p_area"), 6.0);
+    assert_eq!(f(&rt, "p_perim"), 12.0);
+    assert!((f(&rt, "w_area") - pi).abs() < 1e-12);
 }
 
-// ─── Draw output ──────────────────────────────────────────────────────────────
+#[test]
+fn shape_measures_follow_transform_scale() {
+    let rt = run(r#"
+        import shapes { circle, rect }
+        state {
+            let scaled: float = (rect(vec2(0.0, 0.0), vec2(2.0, 3.0))@transform().scale(2.0).rotate(30.0)).area()
+            let sheared_area: float = (rect(vec2(0.0, 0.0), vec2(2.0, 3.0))@transform().shear(1.0, 0.0)).area()
+            let sheared: float = (rect(vec2(0.0, 0.0), vec2(2.0, 3.0))@transform().shear(1.0, 0.0)).perimeter()
+            let grown: float = (circle(vec2(0.0, 0.0), 1.0)@transform().scale(3.0)).perimeter()
+        }
+    "#);
+    assert!((f(&rt, "scaled") - 24.0).abs() < 1e-9);
+    assert!((f(&rt, "sheared_area") - 6.0).abs() < 1e-9);
+    // Shear turns the vertical sides into 3×3 diagonals.
+    assert!((f(&rt, "sheared") - (4.0 + 6.0 * 2.0_f64.sqrt())).abs() < 1e-9);
+    assert!((f(&rt, "grown") - 6.0 * std::f64::consts::PI).abs() < 1e-9);
+}
 
 #[test]
-fn draw_static_emits_circle() {
-    let mut rt = run(r#"
-        import shapes { circle }
-        out << circle(vec2(0.0, 0.0), 0.5)
+fn wedge_fields_and_contains() {
+    let rt = run(r#"
+        import shapes { wedge }
+        state {
+            let r: float = wedge(vec2(1.0, 1.0), 2.0, 0.0, PI / 2.0).radius
+            let end: float = wedge(vec2(1.0, 1.0), 2.0, 0.0, PI / 2.0).end
+            let inside: bool = wedge(vec2(0.0, 0.0), 1.0, 0.0, PI / 2.0).contains(vec2(0.3, 0.3))
+            let wrong_side: bool = wedge(vec2(0.0, 0.0), 1.0, 0.0, PI / 2.0).contains(vec2(-0.3, 0.3))
+            let clockwise: bool = wedge(vec2(0.0, 0.0), 1.0, 0.0, -PI / 2.0).contains(vec2(0.3, -0.3))
+            let wraps: bool = wedge(vec2(0.0, 0.0), 1.0, 3.0 * PI / 2.0, 5.0 * PI / 2.0).contains(vec2(0.3, 0.3))
+            let bounds: vec4 = wedge(vec2(0.0, 0.0), 1.0, 0.0, PI / 2.0).bounds()
+        }
     "#);
-    let cmds = tick(&mut rt);
-    assert_eq!(cmds.len(), 1);
-    let DrawCommand::DrawShape(data) = &cmds[0];
-    assert!(matches!(data.desc, ShapeDesc::Circle { .. }));
+    assert_eq!(f(&rt, "r"), 2.0);
+    assert!((f(&rt, "end") - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+    assert!(b(&rt, "inside"));
+    assert!(!b(&rt, "wrong_side"));
+    assert!(b(&rt, "clockwise"));
+    assert!(b(&rt, "wraps"));
+    let (x0, y0, x1, y1) = v4(&rt, "bounds");
+    assert!(x0.abs() < 1e-9 && y0.abs() < 1e-9);
+    assert!((x1 - 1.0).abs() < 1e-9 && (y1 - 1.0).abs() < 1e-9);
 }
 
 #[test]
-fn draw_static_emits_rect() {
-    let mut rt = run(r#"
+fn shape_union_grows_bounds() {
+    let rt = run(r#"
         import shapes { rect }
-        out << rect(vec2(0.0, 0.0), vec2(1.0, 1.0))
+        state {
+            let a: vec4 = rect(vec2(0.0, 0.0), vec2(2.0, 2.0)).bounds()
+            let u: vec4 = rect(vec2(0.0, 0.0), vec2(2.0, 2.0)).union(rect(vec2(1.0, 1.0), vec2(2.0, 2.0))).bounds()
+            let mid: bool = rect(vec2(0.0, 0.0), vec2(2.0, 2.0)).union(rect(vec2(1.0, 1.0), vec2(2.0, 2.0))).contains(vec2(1.8, 1.8))
+        }
     "#);
-    let cmds = tick(&mut rt);
-    assert_eq!(cmds.len(), 1);
-    let DrawCommand::DrawShape(data) = &cmds[0];
-    assert!(matches!(data.desc, ShapeDesc::Rect { .. }));
+    assert_eq!(v4(&rt, "a"), (-1.0, -1.0, 1.0, 1.0));
+    let (x0, y0, x1, y1) = v4(&rt, "u");
+    assert!((x0 + 1.0).abs() < 1e-5 && (y0 + 1.0).abs() < 1e-5);
+    assert!((x1 - 2.0).abs() < 1e-5 && (y1 - 2.0).abs() < 1e-5);
+    assert!(b(&rt, "mid"));
 }
 
 #[test]
-fn draw_static_multiple_shapes() {
-    let mut rt = run(r#"
-        import shapes { circle, rect }
-        out << rect(vec2(0.0, 0.0), vec2(2.0, 2.0))
-        out << circle(vec2(0.0, 0.0), 0.3)
+fn shape_difference_cuts_area() {
+    let rt = run(r#"
+        import shapes { rect, circle }
+        state {
+            let kept: bool = rect(vec2(0.0, 0.0), vec2(2.0, 2.0)).difference(rect(vec2(1.0, 1.0), vec2(2.0, 2.0))).contains(vec2(-0.5, -0.5))
+            let cut: bool = rect(vec2(0.0, 0.0), vec2(2.0, 2.0)).difference(rect(vec2(1.0, 1.0), vec2(2.0, 2.0))).contains(vec2(0.5, 0.5))
+            let hole: bool = rect(vec2(0.0, 0.0), vec2(4.0, 4.0)).difference(circle(vec2(0.0, 0.0), 1.0)).contains(vec2(0.0, 0.0))
+            let ring: bool = rect(vec2(0.0, 0.0), vec2(4.0, 4.0)).difference(circle(vec2(0.0, 0.0), 1.0)).contains(vec2(1.5, 0.0))
+        }
     "#);
-    let cmds = tick(&mut rt);
-    assert_eq!(cmds.len(), 2);
+    assert!(b(&rt, "kept"));
+    assert!(!b(&rt, "cut"));
+    assert!(!b(&rt, "hole"));
+    assert!(b(&rt, "ring"));
 }
 
 #[test]
-fn draw_static_chained_out() {
-    let mut rt = run(r#"
-        import shapes { circle, rect }
-        let bg = rect(vec2(0.0, 0.0), vec2(2.0, 2.0))
-        let c  = circle(vec2(0.0, 0.0), 0.3)
-        out << bg << c
+fn shape_measures_subtract_holes() {
+    let rt = run(r#"
+        import shapes { rect, circle }
+        state {
+            let square_hole: float = rect(vec2(0.0, 0.0), vec2(4.0, 4.0)).difference(rect(vec2(0.0, 0.0), vec2(1.0, 1.0))).area()
+            let square_rim: float = rect(vec2(0.0, 0.0), vec2(4.0, 4.0)).difference(rect(vec2(0.0, 0.0), vec2(1.0, 1.0))).perimeter()
+            let round_hole: float = rect(vec2(0.0, 0.0), vec2(4.0, 4.0)).difference(circle(vec2(0.0, 0.0), 1.0)).area()
+            let pieces: float = rect(vec2(0.0, 0.0), vec2(1.0, 1.0)).union(rect(vec2(5.0, 0.0), vec2(1.0, 1.0))).perimeter()
+        }
     "#);
-    let cmds = tick(&mut rt);
-    assert_eq!(cmds.len(), 2);
+    assert!((f(&rt, "square_hole") - 15.0).abs() < 1e-5, "{}", f(&rt, "square_hole"));
+    assert!((f(&rt, "square_rim") - 20.0).abs() < 1e-5, "{}", f(&rt, "square_rim"));
+    // the circle is cut as its 64-segment outline
+    let polygon_area = 32.0 * (std::f64::consts::TAU / 64.0).sin();
+    assert!((f(&rt, "round_hole") - (16.0 - polygon_area)).abs() < 1e-5, "{}", f(&rt, "round_hole"));
+    assert!((f(&rt, "pieces") - 8.0).abs() < 1e-5, "{}", f(&rt, "pieces"));
 }
 
 #[test]
-fn draw_update_emits_each_tick() {
-    let mut rt = run(r#"
-        import shapes { circle }
-        state { let t: float = 0.0 }
-        fn on_update(s: State, input: Input) -> State {
-            s.t = s.t + input.dt
-            out << circle(vec2(sin(s.t) * 0.5, 0.0), 0.2)
+fn shape_boolean_follows_transforms() {
+    let rt = run(r#"
+        import shapes { rect }
+        state { let u: vec4 = vec4(0.0, 0.0, 0.0, 0.0) }
+        fn on_init(s: State) -> State {
+            let moved: shape = rect(vec2(0.0, 0.0), vec2(1.0, 1.0))@transform().move(5.0, 0.0)
+            s.u = rect(vec2(0.0, 0.0), vec2(1.0, 1.0)).union(moved).bounds()
             return s
         }
     "#);
-    let c1 = tick(&mut rt);
-    let c2 = tick(&mut rt);
-    assert_eq!(c1.len(), 1);
-    assert_eq!(c2.len(), 1);
+    let (x0, _, x1, _) = v4(&rt, "u");
+    assert!((x0 + 0.5).abs() < 1e-5 && (x1 - 5.5).abs() < 1e-5);
 }
 
 #[test]
-fn draw_foreach_emits_one_per_element() {
-    let mut rt = run(r#"
-        import shapes { circle }
-        state { let xs: list[float] = [] }
+fn shape_boolean_rejects_lines() {
+    let e = run_err(r#"
+        import shapes { rect, line }
+        state { let x: float = 0.0 }
         fn on_init(s: State) -> State {
-            for let i = 0.0; i < 5.0; i = i + 1.0 { s.xs.push(i * 0.1) }
-            return s
-        }
-        fn on_update(s: State, input: Input) -> State {
-            foreach v in s.xs { out << circle(vec2(v, 0.0), 0.05) }
+            let u: polygon = rect(vec2(0.0, 0.0), vec2(1.0, 1.0)).union(line(vec2(0.0, 0.0), vec2(1.0, 1.0)))
             return s
         }
     "#);
-    let cmds = tick(&mut rt);
-    assert_eq!(cmds.len(), 5);
+    assert!(e.message.contains("needs shapes with area"), "{}", e.message);
 }
 
 #[test]
-fn draw_transform_attached_to_shape() {
+fn import_member_alias_callable() {
     let mut rt = run(r#"
-        import shapes { circle }
-        let t = transform().scale(2.0)
-        let s = circle(vec2(0.0, 0.0), 0.2)
-        out << s@t
+        import shapes { circle as c }
+        import render { outline as o }
+        out << c(vec2(0.0, 0.0), 0.3, render: o)
     "#);
     let cmds = tick(&mut rt);
     assert_eq!(cmds.len(), 1);
     let DrawCommand::DrawShape(data) = &cmds[0];
-    assert_eq!(data.transforms.len(), 1);
-    assert_eq!(data.transforms[0].sx, 2.0);
-    assert_eq!(data.transforms[0].sy, 2.0);
+    assert!(matches!(data.desc, ShapeDesc::Circle { radius, .. } if radius == 0.3));
+    assert!(matches!(data.render_mode, rustle_lang::RenderMode::Outline));
 }
 
 #[test]
-fn draw_multiple_transforms_accumulated() {
+fn wildcard_import_brings_all_members() {
     let mut rt = run(r#"
-        import shapes { circle }
-        let t1 = transform().scale(2.0)
-        let t2 = transform().move(0.5, 0.0)
-        let s  = circle(vec2(0.0, 0.0), 0.2)
-        out << s@(t1, t2)
+        import core { * }
+        import shapes { * }
+        state {
+            let s: float = sin(0.0)
+            let c: float = cos(0.0)
+        }
+        out << rect(vec2(0.0, 0.0), vec2(0.2, 0.2))
+        out << circle(vec2(0.0, 0.0), 0.3)
     "#);
-    let cmds = tick(&mut rt);
-    let DrawCommand::DrawShape(data) = &cmds[0];
-    assert_eq!(data.transforms.len(), 2);
+    assert_eq!(f(&rt, "s"), 0.0);
+    assert_eq!(f(&rt, "c"), 1.0);
+    assert_eq!(tick(&mut rt).len(), 2);
+}
+
+#[test]
+fn named_out_channels_collect_separately() {
+    let mut rt = run(r#"
+        import shapes { circle, rect }
+        fn on_update(s: State, input: Input) -> State {
+            out background << rect(vec2(0.0, 0.0), vec2(2.0, 2.0)) << rect(vec2(0.0, 0.0), vec2(1.0, 1.0))
+            out << circle(vec2(0.0, 0.0), 0.5)
+            out overlay << circle(vec2(0.5, 0.5), 0.1)
+            return s
+        }
+    "#);
+    let channels = rt.tick_channels(&Input { dt: 0.016, ..Input::default() }).expect("tick");
+    assert_eq!(channels.len(), 3);
+    assert_eq!(channels[""].len(), 1);
+    assert_eq!(channels["background"].len(), 2);
+    assert_eq!(channels["overlay"].len(), 1);
+    let DrawCommand::DrawShape(bg) = &channels["background"][0];
+    assert!(matches!(bg.desc, ShapeDesc::Rect { .. }));
+    // Plain `tick` still returns only the default channel.
+    assert_eq!(tick(&mut rt).len(), 1);
 }
 
 // ─── Coordinate config ────────────────────────────────────────────────────────
@@ -1270,6 +3785,109 @@ fn resolution_in_init_persists_to_tick() {
     assert_eq!(data.coord_meta.px_height, 768.0);
 }
 
+#[test]
+fn canvas_size_reads_resolution() {
+    let rt = run(r#"
+        import coords { resolution, canvas_size }
+        state {
+            let before: vec2 = vec2(-1.0, -1.0)
+            let after: vec2 = vec2(0.0, 0.0)
+        }
+        fn on_init(s: State) -> State {
+            s.before = canvas_size()
+            resolution(800.0, 600.0)
+            s.after = canvas_size()
+            return s
+        }
+    "#);
+    assert_eq!(v2(&rt, "before"), (0.0, 0.0));
+    assert_eq!(v2(&rt, "after"), (800.0, 600.0));
+}
+
+#[test]
+fn origin_names_round_trip() {
+    for o in Origin::ALL {
+        assert_eq!(Origin::from_name(o.name()), Some(o));
+    }
+    assert_eq!(Origin::from_name("middle"), None);
+}
+
+#[test]
+fn every_origin_constant_reaches_coord_meta() {
+    for o in Origin::ALL {
+        let mut rt = run(&format!(r#"
+            import shapes {{ circle }}
+            import coords {{ resolution, origin, {name} }}
+            resolution(800.0, 600.0)
+            origin({name})
+            out << circle(vec2(10.0, 10.0), 5.0)
+        "#, name = o.name()));
+        let cmds = tick(&mut rt);
+        let DrawCommand::DrawShape(data) = &cmds[0];
+        assert_eq!(data.coord_meta.origin, o, "{}", o.name());
+    }
+}
+
+#[test]
+fn origin_bottom_right_in_emitted_shape() {
+    let mut rt = run(r#"
+        import shapes { rect }
+        import coords { resolution, origin, bottom_right }
+        resolution(800.0, 600.0)
+        origin(bottom_right)
+        out << rect(vec2(100.0, 100.0), vec2(20.0, 20.0))
+    "#);
+    let cmds = tick(&mut rt);
+    let DrawCommand::DrawShape(data) = &cmds[0];
+    assert_eq!(data.coord_meta.origin, Origin::BottomRight);
+}
+
+#[test]
+fn namespace_alias_resolution() {
+    let mut rt = run(r#"
+        import shapes { circle }
+        import coords as co
+        co.resolution(640.0, 480.0)
+        co.origin(co.top_left)
+        out << circle(vec2(10.0, 10.0), 5.0)
+    "#);
+    let cmds = tick(&mut rt);
+    let DrawCommand::DrawShape(data) = &cmds[0];
+    assert_eq!(data.coord_meta.px_width,  640.0);
+    assert_eq!(data.coord_meta.px_height, 480.0);
+    assert!(matches!(data.coord_meta.origin, rustle_lang::Origin::TopLeft));
+}
+
+// ─── Easing ───────────────────────────────────────────────────────────────────
+
+const EASINGS: &[&str] = &[
+    "ease_in_quad", "ease_out_quad", "ease_in_out_quad",
+    "ease_in_cubic", "ease_out_cubic", "ease_in_out_cubic",
+    "ease_in_sine", "ease_out_sine", "ease_in_out_sine",
+    "ease_in_back", "ease_out_back", "ease_out_elastic", "ease_out_bounce",
+];
+
+#[test]
+fn easing_curves_fix_endpoints() {
+    for name in EASINGS {
+        let rt = run(&format!(r#"
+            import easing {{ {name} }}
+            state {{ let ends: list[float] = [{name}(0.0), {name}(1.0)] }}
+        "#));
+        let ends = list_floats(&rt, "ends");
+        assert!(ends[0].abs() < 1e-12 && (ends[1] - 1.0).abs() < 1e-12, "{name}: {ends:?}");
+    }
+}
+
+#[test]
+fn ease_in_out_cubic_midpoint() {
+    let rt = run(r#"
+        import easing { ease_in_out_cubic }
+        state { let m: float = ease_in_out_cubic(0.5) }
+    "#);
+    assert_eq!(f(&rt, "m"), 0.5);
+}
+
 // ─── Complex / edge cases ─────────────────────────────────────────────────────
 
 #[test]
@@ -1367,3 +3985,245 @@ fn complex_state_persists_across_many_ticks() {
     }
     assert_eq!(f(&rt, "count"), 100.0);
 }
+
+#[test]
+fn type_alias_params_coerce_like_their_underlying_type() {
+    let rt = run(r#"
+        type Scale = float
+        fn half(x: Scale) -> Scale { return x / 2 }
+        state {
+            let h = half(3)
+        }
+    "#);
+    assert_eq!(f(&rt, "h"), 1.5);
+}
+
+// ─── Structs ──────────────────────────────────────────────────────────────────
+
+fn struct_field(rt: &Runtime, key: &str, field: &str) -> Value {
+    match rt.state().0.get(key) {
+        Some(Value::Struct { fields, .. }) => fields.iter()
+            .find(|(n, _)| n == field)
+            .map(|(_, v)| v.clone())
+            .unwrap_or_else(|| panic!("struct '{key}' has no field '{field}'")),
+        other => panic!("expected Struct for '{key}', got: {other:?}"),
+    }
+}
+
+#[test]
+fn struct_construction_orders_and_widens_fields() {
+    let rt = run(r#"
+        struct Particle { pos: vec2, vel: vec2, life: float }
+        state {
+            let p = Particle { vel: vec2(0.0, 1.0), life: 2, pos: vec2(3.0, 4.0) }
+        }
+    "#);
+    match rt.state().0.get("p") {
+        Some(Value::Struct { name, fields }) => {
+            assert_eq!(name, "Particle");
+            let names: Vec<&str> = fields.iter().map(|(n, _)| n.as_str()).collect();
+            assert_eq!(names, ["pos", "vel", "life"]);
+        }
+        other => panic!("expected Struct, got: {other:?}"),
+    }
+    assert!(matches!(struct_field(&rt, "p", "life"), Value::Float(x) if x == 2.0));
+}
+
+#[test]
+fn struct_field_read() {
+    let rt = run(r#"
+        struct Particle { pos: vec2, vel: vec2 }
+        fn speed(p: Particle) -> float { return p.vel.length() }
+        state {
+            let p = Particle { pos: vec2(1.0, 2.0), vel: vec2(3.0, 4.0) }
+            let x = 0.0
+            let s = 0.0
+        }
+        fn on_init(s: State) -> State {
+            s.x = s.p.pos.x
+            s.s = speed(s.p)
+            return s
+        }
+    "#);
+    assert_eq!(f(&rt, "x"), 1.0);
+    assert_eq!(f(&rt, "s"), 5.0);
+}
+
+#[test]
+fn struct_fields_mutate_inside_update() {
+    let mut rt = run(r#"
+        struct Particle { pos: vec2, vel: vec2 }
+        state {
+            let p = Particle { pos: vec2(0.0, 0.0), vel: vec2(1.0, 2.0) }
+            let ps = [Particle { pos: vec2(0.0, 0.0), vel: vec2(0.0, 1.0) }]
+        }
+        fn on_update(s: State, input: Input) -> State {
+            s.p.pos = s.p.pos + s.p.vel
+            s.p.vel.x = 10
+            let q = s.ps[0]
+            q.pos.y = q.pos.y + q.vel.y
+            s.ps[0] = q
+            return s
+        }
+    "#);
+    tick(&mut rt);
+    tick(&mut rt);
+    assert!(matches!(struct_field(&rt, "p", "pos"), Value::Vec2(x, y) if x == 11.0 && y == 4.0));
+    assert!(matches!(struct_field(&rt, "p", "vel"), Value::Vec2(x, y) if x == 10.0 && y == 2.0));
+    match rt.state().0.get("ps") {
+        Some(Value::List(items)) => match &items.borrow()[0] {
+            Value::Struct { fields, .. } => assert!(matches!(fields[0].1, Value::Vec2(_, y) if y == 2.0)),
+            other => panic!("expected Struct, got: {other:?}"),
+        },
+        other => panic!("expected List, got: {other:?}"),
+    }
+}
+
+#[test]
+fn structs_compare_by_value() {
+    let rt = run(r#"
+        struct P { a: float }
+        state {
+            let same = P { a: 1.0 } == P { a: 1.0 }
+            let diff = P { a: 1.0 } == P { a: 2.0 }
+        }
+    "#);
+    assert!(b(&rt, "same"));
+    assert!(!b(&rt, "diff"));
+}
+
+// ─── Enums ────────────────────────────────────────────────────────────────────
+
+#[test]
+fn enum_variant_equality() {
+    let rt = run(r#"
+        enum Mode { Menu, Playing, Paused }
+        state {
+            let same = Mode.Menu == Mode.Menu
+            let diff = Mode.Menu == Mode.Paused
+            let ne = Mode.Playing != Mode.Paused
+            let shown = "{Mode.Playing}"
+        }
+    "#);
+    assert!(b(&rt, "same"));
+    assert!(!b(&rt, "diff"));
+    assert!(b(&rt, "ne"));
+    assert_eq!(str_val(&rt, "shown"), "Mode.Playing");
+}
+
+#[test]
+fn enum_match_over_all_variants() {
+    let mut rt = run(r#"
+        enum Mode { Menu, Playing, Paused }
+        fn next(m: Mode) -> Mode {
+            return match m {
+                Mode.Menu => Mode.Playing,
+                Mode.Playing => Mode.Paused,
+                Mode.Paused => Mode.Menu,
+            }
+        }
+        state {
+            let mode: Mode = Mode.Menu
+            let code = 0.0
+        }
+        fn on_update(s: State, input: Input) -> State {
+            s.mode = next(s.mode)
+            match s.mode {
+                Mode.Menu    => { s.code = 0.0 }
+                Mode.Playing => { s.code = 1.0 }
+                Mode.Paused  => { s.code = 2.0 }
+            }
+            return s
+        }
+    "#);
+    let mut codes = Vec::new();
+    for _ in 0..4 {
+        tick(&mut rt);
+        codes.push(f(&rt, "code"));
+    }
+    assert_eq!(codes, [1.0, 2.0, 0.0, 1.0]);
+    assert!(matches!(rt.state().0.get("mode"), Some(Value::EnumVariant { variant, .. }) if variant == "Playing"));
+}
+
+#[test]
+fn to_string_and_format_float() {
+    let rt = run(r#"
+        state {
+            let pi = format_float(3.14159, 2.0)
+            let whole = format_float(2.5, 0.0)
+            let padded = format_float(1, 3.0)
+            let yes = to_string(true)
+            let n = to_string(42)
+            let v = to_string(vec2(1.0, 0.5))
+            let s = to_string("hi")
+        }
+    "#);
+    assert_eq!(str_val(&rt, "pi"), "3.14");
+    assert_eq!(str_val(&rt, "whole"), "3");
+    assert_eq!(str_val(&rt, "padded"), "1.000");
+    assert_eq!(str_val(&rt, "yes"), "true");
+    assert_eq!(str_val(&rt, "n"), "42");
+    assert_eq!(str_val(&rt, "v"), "vec2(1, 0.5)");
+    assert_eq!(str_val(&rt, "s"), "hi");
+}
+
+#[test]
+fn format_float_rejects_out_of_range_decimals() {
+    for decimals in ["-1.0", "16.0", "1e12"] {
+        let e = run_err(&format!(r#"
+            fn on_init(s: State) -> State {{
+                let t = format_float(1.0, {decimals})
+                return s
+            }}
+        "#));
+        assert!(e.message.contains("decimals in 0..=15"), "{}", e.message);
+    }
+}
+
+#[test]
+fn parse_float_returns_res() {
+    let rt = run(r#"
+        state {
+            let n = parse_float("3.5").value
+            let padded = parse_float(" -2 ").value
+            let bad = parse_float("abc").ok
+            let bad_msg = parse_float("abc").error
+            let inf = parse_float("inf").ok
+            let fallback = parse_float("").unwrap_or(1.0)
+        }
+    "#);
+    assert_eq!(f(&rt, "n"), 3.5);
+    assert_eq!(f(&rt, "padded"), -2.0);
+    assert!(!b(&rt, "bad"));
+    assert_eq!(str_val(&rt, "bad_msg"), "cannot parse `abc` as float");
+    assert!(!b(&rt, "inf"));
+    assert_eq!(f(&rt, "fallback"), 1.0);
+}
+
+#[test]
+fn chained_comparison() {
+    let rt = run(r#"
+        state {
+            let inside = 1.0 < 2.0 < 3.0
+            let outside = 1.0 < 5.0 < 3.0
+            let mixed = 0 <= 3 < 3.5 >= 1
+            let calls = 0.0
+            let first_false = 0.0
+        }
+        fn on_init(s: State) -> State {
+            let n = 0.0
+            let r = 0.0 < ++n <= 1.0
+            s.calls = n
+            // a false link stops the chain before later operands run
+            let m = 0.0
+            let q = 5.0 < 1.0 < ++m
+            s.first_false = m
+            return s
+        }
+    "#);
+    assert!(b(&rt, "inside"));
+    assert!(!b(&rt, "outside"));
+    assert!(b(&rt, "mixed"));
+    assert_eq!(f(&rt, "calls"), 1.0);
+    assert_eq!(f(&rt, "first_false"), 0.0);
+}